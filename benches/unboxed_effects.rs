@@ -0,0 +1,123 @@
+//! Compares the boxed continuation effects with their unboxed `-Fn` alternatives.
+//!
+//! Run with `cargo bench --bench unboxed_effects`.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy_pipe_affect::prelude::*;
+
+const ENTITIES: u32 = 100_000;
+const WARMUP_UPDATES: u32 = 3;
+const MEASURED_UPDATES: u32 = 20;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+struct Value(u32);
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+struct Total(u32);
+
+/// Produces one boxed `ResSetWith` per entity.
+fn sum_values_boxed() -> QueryMapAnd<&'static Value, ResSetWith<Total>, ()> {
+    query_map_and(|value: &Value| {
+        let value = value.0;
+        effect_out(
+            res_set_with(move |total: &Total| Total(total.0.wrapping_add(black_box(value)))),
+            (),
+        )
+    })
+}
+
+/// Produces one unboxed `ResSetWithFn` per entity.
+fn sum_values_unboxed() -> impl Effect + use<> {
+    query_map_and_fn::<_, &'static Value, _, (), ()>(|value: &Value| {
+        let value = value.0;
+        effect_out(
+            res_set_with_fn(move |total: &Total| Total(total.0.wrapping_add(black_box(value)))),
+            (),
+        )
+    })
+}
+
+/// Produces one boxed `AffectOrHandle` per entity.
+fn increment_values_boxed()
+-> QueryMapAnd<(Entity, &'static Value), AffectOrHandle<EntityCommandInsert<Value>, BevyError>, ()>
+{
+    query_map_and(|(entity, value): (Entity, &Value)| {
+        let result = Ok(entity_command_insert(
+            entity,
+            Value(value.0.wrapping_add(1)),
+        ));
+        effect_out(affect_or_handle(result, bevy::ecs::error::warn), ())
+    })
+}
+
+/// Produces one unboxed `AffectOrHandleFn` per entity.
+fn increment_values_unboxed() -> impl Effect + use<> {
+    query_map_and_fn::<_, (Entity, &'static Value), _, (), ()>(
+        |(entity, value): (Entity, &Value)| {
+            let result: Result<_, BevyError> = Ok(entity_command_insert(
+                entity,
+                Value(value.0.wrapping_add(1)),
+            ));
+            effect_out(affect_or_handle_fn(result, bevy::ecs::error::warn), ())
+        },
+    )
+}
+
+fn app_setup() -> App {
+    let mut app = App::new();
+    app.init_resource::<Total>();
+    app.world_mut().spawn_batch((0..ENTITIES).map(Value));
+    app
+}
+
+fn bench<M>(name: &str, system: impl IntoSystem<(), (), M>) -> Duration {
+    let mut app = app_setup();
+    app.add_systems(Update, system);
+
+    for _ in 0..WARMUP_UPDATES {
+        app.update();
+    }
+
+    let start = Instant::now();
+    for _ in 0..MEASURED_UPDATES {
+        app.update();
+    }
+    let per_update = start.elapsed() / MEASURED_UPDATES;
+
+    println!(
+        "{name:<32} {per_update:>12?} per update, {:>8.1?} per entity",
+        per_update / ENTITIES
+    );
+
+    per_update
+}
+
+fn compare<MB, MU>(
+    name: &str,
+    boxed: impl IntoSystem<(), (), MB>,
+    unboxed: impl IntoSystem<(), (), MU>,
+) {
+    let boxed = bench(&format!("{name} (boxed)"), boxed);
+    let unboxed = bench(&format!("{name} (unboxed)"), unboxed);
+
+    println!(
+        "{name:<32} unboxed is {:.2}x the speed of boxed\n",
+        boxed.as_secs_f64() / unboxed.as_secs_f64()
+    );
+}
+
+fn main() {
+    compare(
+        "res_set_with per entity",
+        sum_values_boxed.pipe(affect),
+        sum_values_unboxed.pipe(affect),
+    );
+
+    compare(
+        "affect_or_handle per entity",
+        increment_values_boxed.pipe(affect),
+        increment_values_unboxed.pipe(affect),
+    );
+}
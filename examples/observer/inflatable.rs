@@ -17,11 +17,11 @@ fn inflate(
 }
 // ANCHOR_END: observer_system
 
-// ANCHOR: spawn_observer
-pub fn spawn_observer() -> CommandSpawn<Observer> {
-    command_spawn(Observer::new(inflate.pipe(affect)))
+// ANCHOR: add_observer
+pub fn add_observer() -> CommandSpawn<Observer> {
+    command_add_observer(inflate.pipe(affect))
 }
-// ANCHOR_END: spawn_observer
+// ANCHOR_END: add_observer
 
 // ANCHOR: trigger_observer
 pub fn trigger_observer(input: Res<ButtonInput<KeyCode>>) -> Option<CommandTrigger<InflateEvent>> {
@@ -5,14 +5,14 @@
 use bevy::prelude::*;
 use bevy_pipe_affect::prelude::*;
 
-use crate::inflatable::{Inflatable, spawn_observer, trigger_observer};
+use crate::inflatable::{Inflatable, add_observer, trigger_observer};
 
 mod inflatable;
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
-        .add_systems(Startup, (setup.pipe(affect), spawn_observer.pipe(affect)))
+        .add_systems(Startup, (setup.pipe(affect), add_observer.pipe(affect)))
         .add_systems(Update, trigger_observer.pipe(affect))
         .run();
 }
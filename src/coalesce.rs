@@ -0,0 +1,167 @@
+//! Types for merging redundant [`Effect`]s before they are performed.
+//!
+//! See the [`Coalesce`] trait for the main entrypoint.
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::Effect;
+
+/// Merge redundant [`Effect`]s of the same type, without changing the resulting state.
+///
+/// When many effects of the same type are performed together, some of them can be made redundant
+/// by later ones. For example, only the last of several [`ResSet`]s for the same resource
+/// survives. Performing the redundant effects anyway still triggers change detection and does
+/// extra work. Coalescing removes them before they are performed.
+///
+/// Implemented for...
+/// - [`ResSet`]: only the last one is kept
+/// - [`QueryEntityAffect`] of a [`ComponentSet`]: only the last one per entity is kept
+/// - [`EntityCommandInsert`]: only the last one per entity is kept
+/// - [`EntityCommandDespawn`]: only the last one per entity is kept
+/// - `Either<EntityCommandInsert, EntityCommandDespawn>`: inserts followed by a despawn of the
+///   same entity are dropped, on top of the above
+///
+/// The remaining effects keep their relative order. Note that any hooks or observers that would
+/// have been triggered by the removed effects are not triggered.
+///
+/// Collections of effects can be coalesced with the [`coalesce`] composition function and the
+/// [`in_and_coalesce`] system combinator, or in an [`EffectQueuePlugin`] preprocessor.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
+/// struct Level(u32);
+///
+/// let mut effects = vec![res_set(Level(1)), res_set(Level(2)), res_set(Level(3))];
+///
+/// ResSet::coalesce(&mut effects);
+///
+/// assert_eq!(effects, vec![res_set(Level(3))]);
+/// ```
+///
+/// [`ResSet`]: crate::effects::resource::ResSet
+/// [`QueryEntityAffect`]: crate::effects::query_entity::QueryEntityAffect
+/// [`ComponentSet`]: crate::query_data_effects::ComponentSet
+/// [`EntityCommandInsert`]: crate::effects::entity_command::EntityCommandInsert
+/// [`EntityCommandDespawn`]: crate::effects::entity_command::EntityCommandDespawn
+/// [`coalesce`]: crate::effect_composition::coalesce
+/// [`in_and_coalesce`]: crate::system_combinators::in_and_coalesce
+/// [`EffectQueuePlugin`]: crate::effects::queue::EffectQueuePlugin
+pub trait Coalesce: Effect + Sized {
+    /// Remove the redundant effects from the collection, keeping the order of the rest.
+    fn coalesce(effects: &mut Vec<Self>);
+}
+
+/// Remove all effects except the last one of each key, keeping the order of the rest.
+pub(crate) fn retain_last_by_key<E, K>(effects: &mut Vec<E>, key: impl Fn(&E) -> K)
+where
+    K: Eq + Hash,
+{
+    let mut seen = HashSet::new();
+
+    let mut keep = effects
+        .iter()
+        .rev()
+        .map(|effect| seen.insert(key(effect)))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev();
+
+    effects.retain(|_| keep.next().unwrap_or(true));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashSet};
+
+    use bevy::prelude::*;
+    use either::Either;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::effects::entity_command::{entity_command_despawn, entity_command_insert};
+    use crate::effects::number_data::NumberComponent;
+    use crate::effects::query_entity::query_entity_affect;
+    use crate::query_data_effects::component_set;
+    use crate::system_combinators::affect;
+
+    fn spawn_entities(app: &mut App, count: usize) -> Vec<Entity> {
+        (0..count)
+            .map(|_| app.world_mut().spawn(NumberComponent::<0>(0)).id())
+            .collect()
+    }
+
+    /// Keyed by entity, since despawn order affects query iteration order but not the state.
+    fn test_state(world: &mut World) -> BTreeMap<Entity, Option<NumberComponent<0>>> {
+        world
+            .query::<(Entity, Option<&NumberComponent<0>>)>()
+            .iter(world)
+            .map(|(entity, component)| (entity, component.copied()))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn coalesced_component_sets_produce_same_state(
+            sets in prop::collection::vec((0..8usize, any::<NumberComponent<0>>()), 0..32),
+        ) {
+            let apps = [false, true].map(|coalesced| {
+                let mut app = App::new();
+                let entities = spawn_entities(&mut app, 8);
+
+                let mut effects = sets
+                    .iter()
+                    .map(|(index, component)| query_entity_affect::<_, ()>(entities[*index], component_set(*component)))
+                    .collect::<Vec<_>>();
+
+                if coalesced {
+                    Coalesce::coalesce(&mut effects);
+
+                    let unique_entities = sets.iter().map(|(index, _)| index).collect::<HashSet<_>>();
+                    assert_eq!(effects.len(), unique_entities.len());
+                }
+
+                app.add_systems(Update, (move || effects.clone()).pipe(affect));
+                app.update();
+                app
+            });
+
+            let [mut uncoalesced_app, mut coalesced_app] = apps;
+
+            prop_assert_eq!(test_state(uncoalesced_app.world_mut()), test_state(coalesced_app.world_mut()));
+        }
+
+        #[test]
+        fn coalesced_inserts_and_despawns_produce_same_state(
+            commands in prop::collection::vec((0..8usize, prop::option::of(any::<NumberComponent<0>>())), 0..32),
+        ) {
+            let apps = [false, true].map(|coalesced| {
+                let mut app = App::new();
+                let entities = spawn_entities(&mut app, 8);
+
+                let mut effects = commands
+                    .iter()
+                    .map(|(index, component)| match component {
+                        Some(component) => Either::Left(entity_command_insert(entities[*index], *component)),
+                        None => Either::Right(entity_command_despawn(entities[*index])),
+                    })
+                    .collect::<Vec<_>>();
+
+                if coalesced {
+                    Coalesce::coalesce(&mut effects);
+                }
+
+                app.add_systems(Update, (move || effects.clone()).pipe(affect));
+                app.update();
+                app
+            });
+
+            let [mut uncoalesced_app, mut coalesced_app] = apps;
+
+            prop_assert_eq!(test_state(uncoalesced_app.world_mut()), test_state(coalesced_app.world_mut()));
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! Types for evaluating [`Effect`]s against the `World` without performing them.
+//!
+//! See the [`affect_dry_run`] system for the main entrypoint.
+//!
+//! [`affect_dry_run`]: crate::system_combinators::affect_dry_run
+use std::any::{Any, TypeId, type_name};
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
+
+use bevy::ecs::query::{QueryData, QueryFilter};
+use bevy::prelude::*;
+
+use crate::{Effect, QueryDataEffect};
+
+/// A single state change that an effect would cause, with values in their `Debug` representation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StateChange {
+    /// A `Resource` would be set.
+    Resource {
+        /// The type name of the resource.
+        type_name: &'static str,
+        /// The value of the resource before the change, or `None` if it doesn't exist.
+        before: Option<String>,
+        /// The value of the resource after the change.
+        after: String,
+    },
+    /// A `Component` on an entity would be set.
+    Component {
+        /// The entity the component belongs to.
+        entity: Entity,
+        /// The type name of the component.
+        type_name: &'static str,
+        /// The value of the component before the change, or `None` if it doesn't exist.
+        before: Option<String>,
+        /// The value of the component after the change.
+        after: String,
+    },
+}
+
+impl Display for StateChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (before, after) = match self {
+            StateChange::Resource {
+                type_name,
+                before,
+                after,
+            } => {
+                write!(f, "resource {type_name}: ")?;
+                (before, after)
+            }
+            StateChange::Component {
+                entity,
+                type_name,
+                before,
+                after,
+            } => {
+                write!(f, "entity {entity} component {type_name}: ")?;
+                (before, after)
+            }
+        };
+
+        match before {
+            Some(before) => write!(f, "{before} -> {after}"),
+            None => write!(f, "(none) -> {after}"),
+        }
+    }
+}
+
+/// The list of [`StateChange`]s that an effect would cause, in the order they would happen.
+///
+/// Returned by the [`affect_dry_run`] system.
+///
+/// [`affect_dry_run`]: crate::system_combinators::affect_dry_run
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deref, DerefMut)]
+pub struct StateDiff(pub Vec<StateChange>);
+
+impl Display for StateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+            .iter()
+            .try_for_each(|state_change| writeln!(f, "{state_change}"))
+    }
+}
+
+/// Read-only view of the `World` that accumulates the [`StateChange`]s of dry-run effects.
+///
+/// Values set earlier in the dry run are visible to effects evaluated later in the same dry run,
+/// so sequential effects are diffed against each other rather than just the `World`. The exception
+/// is the query input of mapping effects like [`QueryMap`], which is read from the `World` as-is.
+///
+/// [`QueryMap`]: crate::effects::query::QueryMap
+pub struct DryRun<'w> {
+    world: &'w World,
+    changes: Vec<StateChange>,
+    pending: HashMap<(Option<Entity>, TypeId), Box<dyn Any>>,
+}
+
+impl<'w> DryRun<'w> {
+    /// Construct a new [`DryRun`] against the given `World`.
+    pub fn new(world: &'w World) -> Self {
+        DryRun {
+            world,
+            changes: Vec::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    /// The `World` being dry-run against, not including any pending changes.
+    pub fn world(&self) -> &'w World {
+        self.world
+    }
+
+    /// The current value of the resource `R`, including any pending change.
+    pub fn resource<R>(&self) -> Option<&R>
+    where
+        R: Resource,
+    {
+        match self.pending.get(&(None, TypeId::of::<R>())) {
+            Some(pending) => pending.downcast_ref::<R>(),
+            None => self.world.get_resource::<R>(),
+        }
+    }
+
+    /// The current value of the component `C` on `entity`, including any pending change.
+    pub fn component<C>(&self, entity: Entity) -> Option<&C>
+    where
+        C: Component,
+    {
+        match self.pending.get(&(Some(entity), TypeId::of::<C>())) {
+            Some(pending) => pending.downcast_ref::<C>(),
+            None => self.world.get::<C>(entity),
+        }
+    }
+
+    /// Record that the resource `R` would be set to `value`.
+    pub fn set_resource<R>(&mut self, value: R)
+    where
+        R: Resource + Debug,
+    {
+        let before = self.resource::<R>().map(|before| format!("{before:?}"));
+
+        self.changes.push(StateChange::Resource {
+            type_name: type_name::<R>(),
+            before,
+            after: format!("{value:?}"),
+        });
+
+        self.pending
+            .insert((None, TypeId::of::<R>()), Box::new(value));
+    }
+
+    /// Record that the component `C` on `entity` would be set to `value`.
+    pub fn set_component<C>(&mut self, entity: Entity, value: C)
+    where
+        C: Component + Debug,
+    {
+        let before = self
+            .component::<C>(entity)
+            .map(|before| format!("{before:?}"));
+
+        self.changes.push(StateChange::Component {
+            entity,
+            type_name: type_name::<C>(),
+            before,
+            after: format!("{value:?}"),
+        });
+
+        self.pending
+            .insert((Some(entity), TypeId::of::<C>()), Box::new(value));
+    }
+
+    /// All entities in the `World` that match the given `QueryData` and `QueryFilter`.
+    pub fn matching_entities<D, F>(&self) -> Vec<Entity>
+    where
+        D: QueryData,
+        F: QueryFilter,
+    {
+        self.world
+            .try_query_filtered::<(Entity, D), F>()
+            .map(|state| {
+                state
+                    .iter_manual(self.world)
+                    .map(|(entity, _)| entity)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns true if `entity` matches the given `QueryData` and `QueryFilter`.
+    pub fn matches<D, F>(&self, entity: Entity) -> bool
+    where
+        D: QueryData,
+        F: QueryFilter,
+    {
+        self.world
+            .try_query_filtered::<D, F>()
+            .is_some_and(|state| state.get_manual(self.world, entity).is_ok())
+    }
+
+    /// Consume the dry run, returning the [`StateDiff`] of all changes recorded so far.
+    pub fn into_diff(self) -> StateDiff {
+        StateDiff(self.changes)
+    }
+}
+
+/// An [`Effect`] whose state changes can be evaluated against the `World` without performing
+/// them.
+///
+/// Implemented for this library's resource and query effects, and algebraic effects like tuples,
+/// `Vec`s and `Option`s of them. Effects that are otherwise deferred, like commands, are not
+/// supported.
+///
+/// Effects that would fail (for example, a [`QueryEntityAffect`] on an entity that doesn't match
+/// the query) don't produce any [`StateChange`]s.
+///
+/// Custom effects built from supported effects can implement it by dry-running those effects in
+/// the order they would be performed.
+///
+/// [`QueryEntityAffect`]: crate::effects::query_entity::QueryEntityAffect
+pub trait DryRunEffect: Effect {
+    /// Record the state changes of this effect in the dry run.
+    fn dry_run(self, dry_run: &mut DryRun<'_>);
+}
+
+/// A [`QueryDataEffect`] whose state changes on an entity can be evaluated without performing
+/// them.
+///
+/// Used for implementing [`DryRunEffect`] on query effects.
+pub trait DryRunQueryDataEffect: QueryDataEffect {
+    /// Record the state changes of this effect on `entity` in the dry run.
+    fn dry_run(self, entity: Entity, dry_run: &mut DryRun<'_>);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effects::number_data::{NumberComponent, NumberResource};
+
+    #[test]
+    fn pending_changes_are_visible_to_later_changes() {
+        let mut world = World::new();
+        world.insert_resource(NumberResource(0));
+        let entity = world.spawn(NumberComponent::<0>(0)).id();
+
+        let mut dry_run = DryRun::new(&world);
+
+        dry_run.set_resource(NumberResource(1));
+        dry_run.set_resource(NumberResource(2));
+        dry_run.set_component(entity, NumberComponent::<0>(1));
+
+        assert_eq!(
+            dry_run.resource::<NumberResource>(),
+            Some(&NumberResource(2))
+        );
+
+        let diff = dry_run.into_diff();
+
+        assert_eq!(
+            diff.0,
+            vec![
+                StateChange::Resource {
+                    type_name: type_name::<NumberResource>(),
+                    before: Some(format!("{:?}", NumberResource(0))),
+                    after: format!("{:?}", NumberResource(1)),
+                },
+                StateChange::Resource {
+                    type_name: type_name::<NumberResource>(),
+                    before: Some(format!("{:?}", NumberResource(1))),
+                    after: format!("{:?}", NumberResource(2)),
+                },
+                StateChange::Component {
+                    entity,
+                    type_name: type_name::<NumberComponent<0>>(),
+                    before: Some(format!("{:?}", NumberComponent::<0>(0))),
+                    after: format!("{:?}", NumberComponent::<0>(1)),
+                },
+            ]
+        );
+
+        assert_eq!(world.resource::<NumberResource>(), &NumberResource(0));
+    }
+}
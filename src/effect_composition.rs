@@ -6,8 +6,8 @@
 
 use bevy::ecs::error::BevyError;
 
-use crate::Effect;
 use crate::effects::error::AffectOrHandle;
+use crate::{Coalesce, Effect};
 
 /// [`Effect`] composition function that returns the first effect.
 ///
@@ -138,6 +138,33 @@ where
     e0
 }
 
+/// [`Effect`] composition function for `Vec` effects that concatenates them, then removes the
+/// redundant effects with [`Coalesce`].
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::effect_composition::coalesce;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Debug, PartialEq, Eq, Resource)]
+/// struct Level(u32);
+///
+/// let effect = coalesce(
+///     vec![res_set(Level(0)), res_set(Level(1))],
+///     vec![res_set(Level(2))],
+/// );
+///
+/// assert_eq!(effect, vec![res_set(Level(2))]);
+/// ```
+pub fn coalesce<E>(mut e0: Vec<E>, e1: Vec<E>) -> Vec<E>
+where
+    E: Coalesce,
+{
+    e0.extend(e1);
+    E::coalesce(&mut e0);
+    e0
+}
+
 /// Returns an [`Effect`] composition function that applies the given composition to the
 /// `Some`-wrapped left effect and the right effect, otherwise `None`.
 ///
@@ -0,0 +1,154 @@
+//! Types for recording [`Effect`]s instead of performing them.
+//!
+//! See the [`record`] system for the main entrypoint.
+//!
+//! [`record`]: crate::system_combinators::record
+use std::any::type_name;
+use std::fmt::Debug;
+
+use bevy::prelude::*;
+
+use crate::Effect;
+
+/// A structured, `Debug`-printable record of an [`Effect`].
+///
+/// Produced by [`RecordEffect`] implementations and stored in the [`RecordedEffects`] resource by
+/// the [`record`] system.
+///
+/// [`record`]: crate::system_combinators::record
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EffectRecord {
+    /// The type name of the recorded effect.
+    pub type_name: &'static str,
+    /// The `Debug` representation of the recorded effect.
+    pub debug: String,
+    /// Records of the effects produced by this effect's continuation.
+    ///
+    /// Only populated for continuation effects that can be expanded without `World` access, like
+    /// [`CommandSpawnAnd`].
+    ///
+    /// [`CommandSpawnAnd`]: crate::effects::command::CommandSpawnAnd
+    pub continuation: Vec<EffectRecord>,
+}
+
+impl EffectRecord {
+    /// Construct a new [`EffectRecord`] for the given effect, without any continuation.
+    pub fn new<E>(effect: &E) -> Self
+    where
+        E: Debug,
+    {
+        EffectRecord {
+            type_name: type_name::<E>(),
+            debug: format!("{effect:?}"),
+            continuation: Vec::new(),
+        }
+    }
+}
+
+/// Accumulates [`EffectRecord`]s, and supplies placeholder values for expanding continuations.
+///
+/// Placeholder entities are allocated from the top of the `Entity` index space, so they are
+/// distinct from one another and (in practice) from the entities of the world being tested.
+#[derive(Debug, Default)]
+pub struct EffectRecorder {
+    records: Vec<EffectRecord>,
+    placeholder_entities: u32,
+}
+
+impl EffectRecorder {
+    /// Push a record of the given effect.
+    pub fn record<E>(&mut self, effect: &E)
+    where
+        E: Debug,
+    {
+        self.push(EffectRecord::new(effect));
+    }
+
+    /// Push the given record.
+    pub fn push(&mut self, record: EffectRecord) {
+        self.records.push(record);
+    }
+
+    /// Push the given record, with a continuation recorded by `continuation`.
+    pub fn push_and(
+        &mut self,
+        mut record: EffectRecord,
+        continuation: impl FnOnce(&mut EffectRecorder),
+    ) {
+        let mut continuation_recorder = EffectRecorder {
+            records: Vec::new(),
+            placeholder_entities: self.placeholder_entities,
+        };
+
+        continuation(&mut continuation_recorder);
+
+        self.placeholder_entities = continuation_recorder.placeholder_entities;
+        record
+            .continuation
+            .extend(continuation_recorder.into_records());
+        self.push(record);
+    }
+
+    /// Allocate a new placeholder `Entity` for expanding a continuation.
+    pub fn placeholder_entity(&mut self) -> Entity {
+        let index = u32::MAX - 1 - self.placeholder_entities;
+        self.placeholder_entities += 1;
+
+        Entity::from_raw_u32(index).expect("placeholder entity indices should be below u32::MAX")
+    }
+
+    /// Consume the recorder, returning all records pushed so far.
+    pub fn into_records(self) -> Vec<EffectRecord> {
+        self.records
+    }
+}
+
+/// An [`Effect`] that can be recorded as [`EffectRecord`]s instead of being performed.
+///
+/// Implemented for all of this library's effects. Algebraic effects like tuples, `Vec`s and
+/// `Option`s record their inner effects in place, rather than recording themselves.
+///
+/// Continuation effects record the effects they produce as an [`EffectRecord::continuation`]
+/// when the continuation can be supplied without `World` access. For example, [`CommandSpawnAnd`]
+/// supplies a placeholder entity (see [`EffectRecorder::placeholder_entity`]) to its function.
+/// Otherwise, only the continuation effect itself is recorded.
+///
+/// Custom effects with a `Debug` implementation can usually implement it by passing themselves to
+/// [`EffectRecorder::record`].
+///
+/// [`CommandSpawnAnd`]: crate::effects::command::CommandSpawnAnd
+pub trait RecordEffect: Effect {
+    /// Record this effect in the recorder.
+    fn record(self, recorder: &mut EffectRecorder);
+}
+
+/// `Resource` storing the [`EffectRecord`]s of all effects piped into [`record`] systems.
+///
+/// Must be initialized in the world for [`record`] systems to run.
+///
+/// [`record`]: crate::system_combinators::record
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deref, DerefMut, Resource)]
+pub struct RecordedEffects(pub Vec<EffectRecord>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholder_entities_are_distinct_across_continuations() {
+        let mut recorder = EffectRecorder::default();
+
+        let first = recorder.placeholder_entity();
+
+        let mut nested = None;
+        recorder.push_and(EffectRecord::new(&()), |recorder| {
+            nested = Some(recorder.placeholder_entity());
+        });
+
+        let last = recorder.placeholder_entity();
+
+        assert_ne!(first, nested.unwrap());
+        assert_ne!(nested.unwrap(), last);
+        assert_ne!(first, last);
+    }
+}
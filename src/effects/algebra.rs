@@ -5,6 +5,8 @@
 //! - `Either<Left, Right>` where the `Left` and `Right` are both effects
 //! - the unit type `()` (trivial no-op effect)
 //!
+//! These are also [`WorldEffect`]s when their inner effects are.
+//!
 //! It's also worth noting that `Option<T>` where `T: Effect` also implements [`Effect`], but that is
 //! implemented in the [`iter`] module.
 //!
@@ -19,7 +21,10 @@ use bevy::prelude::*;
 use either::Either;
 use variadics_please::all_tuples;
 
-use crate::Effect;
+use crate::dry_run::{DryRun, DryRunEffect};
+use crate::effect_record::{EffectRecorder, RecordEffect};
+use crate::undo::InvertibleEffect;
+use crate::{Effect, WorldEffect};
 
 macro_rules! impl_effect {
     ($(($E:ident, $e:ident, $p:ident)),*) => {
@@ -32,6 +37,30 @@ macro_rules! impl_effect {
                 $($e.affect(&mut param.$p());)*
             }
         }
+
+        impl<$($E),*> RecordEffect for ($($E,)*)
+        where $($E: RecordEffect,)* {
+            fn record(self, recorder: &mut EffectRecorder) {
+                let ($($e,)*) = self;
+                $($e.record(recorder);)*
+            }
+        }
+
+        impl<$($E),*> DryRunEffect for ($($E,)*)
+        where $($E: DryRunEffect,)* {
+            fn dry_run(self, dry_run: &mut DryRun<'_>) {
+                let ($($e,)*) = self;
+                $($e.dry_run(dry_run);)*
+            }
+        }
+
+        impl<$($E),*> WorldEffect for ($($E,)*)
+        where $($E: WorldEffect,)* {
+            fn affect_world(self, world: &mut World) {
+                let ($($e,)*) = self;
+                $($e.affect_world(world);)*
+            }
+        }
     };
 }
 
@@ -43,6 +72,18 @@ impl Effect for () {
     fn affect(self, _: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {}
 }
 
+impl RecordEffect for () {
+    fn record(self, _: &mut EffectRecorder) {}
+}
+
+impl DryRunEffect for () {
+    fn dry_run(self, _: &mut DryRun<'_>) {}
+}
+
+impl WorldEffect for () {
+    fn affect_world(self, _: &mut World) {}
+}
+
 impl<E0, E1> Effect for Either<E0, E1>
 where
     E0: Effect,
@@ -58,6 +99,72 @@ where
     }
 }
 
+impl<E0, E1> RecordEffect for Either<E0, E1>
+where
+    E0: RecordEffect,
+    E1: RecordEffect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        match self {
+            Either::Left(e0) => e0.record(recorder),
+            Either::Right(e1) => e1.record(recorder),
+        }
+    }
+}
+
+impl<E0, E1> DryRunEffect for Either<E0, E1>
+where
+    E0: DryRunEffect,
+    E1: DryRunEffect,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        match self {
+            Either::Left(e0) => e0.dry_run(dry_run),
+            Either::Right(e1) => e1.dry_run(dry_run),
+        }
+    }
+}
+
+impl<E0, E1> WorldEffect for Either<E0, E1>
+where
+    E0: WorldEffect,
+    E1: WorldEffect,
+{
+    fn affect_world(self, world: &mut World) {
+        match self {
+            Either::Left(e0) => e0.affect_world(world),
+            Either::Right(e1) => e1.affect_world(world),
+        }
+    }
+}
+
+impl<E0, E1> InvertibleEffect for Either<E0, E1>
+where
+    E0: InvertibleEffect,
+    E1: InvertibleEffect,
+{
+    type Inverse = Either<E0::Inverse, E1::Inverse>;
+
+    fn affect_invertible(
+        self,
+        param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>,
+        inverses: &mut Vec<Self::Inverse>,
+    ) {
+        match self {
+            Either::Left(e0) => {
+                let mut e0_inverses = Vec::new();
+                e0.affect_invertible(&mut param.p0(), &mut e0_inverses);
+                inverses.extend(e0_inverses.into_iter().map(Either::Left));
+            }
+            Either::Right(e1) => {
+                let mut e1_inverses = Vec::new();
+                e1.affect_invertible(&mut param.p1(), &mut e1_inverses);
+                inverses.extend(e1_inverses.into_iter().map(Either::Right));
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -1,8 +1,13 @@
 //! [`Effect`]s that operate on `Assets` stores and the `AssetServer`.
 use bevy::asset::{AssetPath, InvalidGenerationError};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
 use bevy::prelude::*;
 
 use crate::Effect;
+use crate::effect_record::{EffectRecord, EffectRecorder, RecordEffect};
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
 
 /// [`Effect`] that loads an asset, then supplies the asset handle to the provided
 /// effect-producing function to cause another effect.
@@ -112,6 +117,180 @@ where
     }
 }
 
+impl<'a, A, E> WorldEffect for AssetServerLoadAnd<'a, A, E>
+where
+    A: Asset,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<'a, A, E> RecordEffect for AssetServerLoadAnd<'a, A, E>
+where
+    A: Asset,
+    E: RecordEffect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        let record = EffectRecord::new(&self);
+
+        recorder.push_and(record, |recorder| {
+            (self.f)(Handle::default()).record(recorder)
+        });
+    }
+}
+
+/// [`Effect`] that loads an asset, then supplies the asset handle to the provided
+/// effect-producing function to cause another effect, without boxing the function.
+///
+/// This is the unboxed alternative to [`AssetServerLoadAnd`]. It is generic over the function type
+/// `F` instead, so constructing it doesn't allocate and performing it doesn't use dynamic
+/// dispatch. The function type can be hidden in the system's return type with `impl FnOnce`.
+///
+/// Can be constructed with [`asset_server_load_and_fn`].
+///
+/// *Requires the `asset` feature to be enabled.*
+///
+/// # Example
+/// In this example, a system is written that spawns a sprite with the "player.png" image.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// /// Pure system using effects.
+/// fn spawn_player_pure() -> AssetServerLoadAndFn<
+///     'static,
+///     impl FnOnce(Handle<Image>) -> CommandSpawn<Sprite>,
+///     Image,
+///     CommandSpawn<Sprite>,
+/// > {
+///     asset_server_load_and_fn("player.png", |handle| {
+///         command_spawn(Sprite::from_image(handle))
+///     })
+/// }
+///
+/// /// Equivalent impure system.
+/// fn spawn_player_impure(asset_server: Res<AssetServer>, mut commands: Commands) {
+///     let handle = asset_server.load("player.png");
+///     commands.spawn(Sprite::from_image(handle));
+/// }
+/// #
+/// # fn app_setup() -> App {
+/// #     let mut app = App::new();
+/// #     app.add_plugins((
+/// #         MinimalPlugins,
+/// #         AssetPlugin::default(),
+/// #         ImagePlugin::default_linear(),
+/// #     ));
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<Handle<Image>>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Sprite>)>();
+/// #     query
+/// #         .iter(world)
+/// #         .map(|(entity, sprite)| (entity, sprite.map(|sprite| sprite.image.clone())))
+/// #         .collect()
+/// # }
+/// #
+/// # fn main() {
+/// #     let mut pure_app = app_setup();
+/// #     pure_app.add_systems(Update, spawn_player_pure.pipe(affect));
+/// #
+/// #     let mut impure_app = app_setup();
+/// #     impure_app.add_systems(Update, spawn_player_impure);
+/// #
+/// #     for _ in 0..3 {
+/// #         assert_eq!(
+/// #             test_state(pure_app.world_mut()),
+/// #             test_state(impure_app.world_mut())
+/// #         );
+/// #         pure_app.update();
+/// #         impure_app.update();
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - in this example, a `CommandSpawn` is used as the additional [`Effect`], but other
+///   [`Effect`]s are available.
+#[derive(derive_more::Debug)]
+pub struct AssetServerLoadAndFn<'a, F, A, E>
+where
+    F: FnOnce(Handle<A>) -> E,
+    A: Asset,
+    E: Effect,
+{
+    /// The path to the asset to load.
+    pub path: AssetPath<'a>,
+    /// The `Handle<A> -> Effect` function that may cause another effect.
+    #[debug("{0} -> {1}", std::any::type_name::<Handle<A>>(), std::any::type_name::<E>())]
+    pub f: F,
+    #[debug(skip)]
+    phantom: PhantomData<fn(A) -> E>,
+}
+
+/// Construct a new [`AssetServerLoadAndFn`] [`Effect`].
+///
+/// *Requires the `asset` feature to be enabled.*
+pub fn asset_server_load_and_fn<'a, P, F, A, E>(path: P, f: F) -> AssetServerLoadAndFn<'a, F, A, E>
+where
+    P: Into<AssetPath<'a>>,
+    F: FnOnce(Handle<A>) -> E,
+    A: Asset,
+    E: Effect,
+{
+    AssetServerLoadAndFn {
+        path: path.into(),
+        f,
+        phantom: PhantomData,
+    }
+}
+
+impl<'a, F, A, E> Effect for AssetServerLoadAndFn<'a, F, A, E>
+where
+    F: FnOnce(Handle<A>) -> E,
+    A: Asset,
+    E: Effect,
+{
+    type MutParam = (Res<'static, AssetServer>, E::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        let handle = param.0.load(self.path);
+        (self.f)(handle).affect(&mut param.1);
+    }
+}
+
+impl<'a, F, A, E> WorldEffect for AssetServerLoadAndFn<'a, F, A, E>
+where
+    F: FnOnce(Handle<A>) -> E,
+    A: Asset,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<'a, F, A, E> RecordEffect for AssetServerLoadAndFn<'a, F, A, E>
+where
+    F: FnOnce(Handle<A>) -> E,
+    A: Asset,
+    E: RecordEffect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        let record = EffectRecord::new(&self);
+
+        recorder.push_and(record, |recorder| {
+            (self.f)(Handle::default()).record(recorder)
+        });
+    }
+}
+
 /// [`Effect`] that adds an asset to the asset store, then supplies the asset handle to the provided
 /// effect-producing function to cause another effect.
 ///
@@ -255,6 +434,31 @@ where
     }
 }
 
+impl<A, E> WorldEffect for AssetAddAnd<A, E>
+where
+    A: Asset,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<A, E> RecordEffect for AssetAddAnd<A, E>
+where
+    A: Asset + Debug,
+    E: RecordEffect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        let record = EffectRecord::new(&self);
+
+        recorder.push_and(record, |recorder| {
+            (self.f)(Handle::default()).record(recorder)
+        });
+    }
+}
+
 /// [`Effect`] that inserts an `Asset` to the asset store with the given `AssetId` (overwriting any
 /// existing asset at that id).
 ///
@@ -390,6 +594,24 @@ where
     }
 }
 
+impl<A> WorldEffect for AssetInsert<A>
+where
+    A: Asset,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<A> RecordEffect for AssetInsert<A>
+where
+    A: Asset + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -0,0 +1,220 @@
+//! [`Effect`] that erases the type of other effects.
+use std::any::type_name;
+
+use bevy::prelude::*;
+
+use crate::effect_record::{EffectRecorder, RecordEffect};
+use crate::{Effect, WorldEffect};
+
+/// Object-safe counterpart of [`WorldEffect`], used for type-erasing effects in a [`BoxedEffect`].
+///
+/// Implemented for all `Send` [`WorldEffect`]s, which includes all of this library's `Send`
+/// [`Effect`]s.
+pub trait DynEffect: Send {
+    /// Perform the state transition of the boxed effect on the `World`.
+    fn affect_boxed(self: Box<Self>, world: &mut World);
+}
+
+impl<E> DynEffect for E
+where
+    E: WorldEffect + Send + 'static,
+{
+    fn affect_boxed(self: Box<Self>, world: &mut World) {
+        (*self).affect_world(world);
+    }
+}
+
+/// [`Effect`] that causes a type-erased effect.
+///
+/// Since [`Effect::MutParam`] is an associated type, effects of different types can't normally be
+/// stored in the same collection without nesting `Either`s or defining an enum. Boxing them erases
+/// their type, so a `Vec<BoxedEffect>` can contain any combination of effects chosen at runtime.
+///
+/// Type erasure comes at a cost. The boxed effect can't access its `SystemParam` from the system,
+/// so it's performed with exclusive `World` access instead (see [`WorldEffect`] for caveats).
+/// When performed by [`affect`], this happens in a command. Prefer concrete effect types when
+/// possible.
+///
+/// Any `Send` [`WorldEffect`] can be boxed, including [`WorldEffect`]s that aren't [`Effect`]s.
+/// Effects storing non-`Send` functions, like [`ResSetWith`], can't be boxed.
+///
+/// Can be constructed with [`boxed_effect`].
+///
+/// # Example
+/// In this example, a system is written that increments the `Score`, and writes a `Milestone`
+/// message every 10 points.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u32);
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Message)]
+/// struct Milestone(u32);
+///
+/// /// Pure system using effects.
+/// fn increment_score_pure(score: Res<Score>) -> Vec<BoxedEffect> {
+///     let new_score = score.0.saturating_add(1);
+///
+///     let mut effects = vec![boxed_effect(res_set(Score(new_score)))];
+///
+///     if new_score % 10 == 0 {
+///         effects.push(boxed_effect(message_write(Milestone(new_score))));
+///     }
+///
+///     effects
+/// }
+///
+/// /// Equivalent impure system.
+/// fn increment_score_impure(mut score: ResMut<Score>, mut milestones: MessageWriter<Milestone>) {
+///     score.0 = score.0.saturating_add(1);
+///
+///     if score.0 % 10 == 0 {
+///         milestones.write(Milestone(score.0));
+///     }
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(score: Score) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(score).add_message::<Milestone>();
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &World) -> (Score, usize) {
+/// #     (
+/// #         *world.resource::<Score>(),
+/// #         world.resource::<Messages<Milestone>>().len(),
+/// #     )
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(score: Score) {
+/// #         let mut pure_app = app_setup(score);
+/// #         pure_app.add_systems(Update, increment_score_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(score);
+/// #         impure_app.add_systems(Update, increment_score_impure);
+/// #
+/// #         for _ in 0..12 {
+/// #              prop_assert_eq!(test_state(pure_app.world()), test_state(impure_app.world()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[doc = include_str!("defer_command_note.md")]
+///
+/// [`affect`]: crate::system_combinators::affect
+/// [`ResSetWith`]: crate::effects::resource::ResSetWith
+#[derive(derive_more::Debug)]
+pub struct BoxedEffect {
+    type_name: &'static str,
+    #[debug(skip)]
+    effect: Box<dyn DynEffect>,
+}
+
+impl BoxedEffect {
+    /// Box the given effect, erasing its type.
+    pub fn new<E>(effect: E) -> Self
+    where
+        E: WorldEffect + Send + 'static,
+    {
+        BoxedEffect {
+            type_name: type_name::<E>(),
+            effect: Box::new(effect),
+        }
+    }
+
+    /// The type name of the boxed effect.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+/// Construct a new [`BoxedEffect`] [`Effect`].
+pub fn boxed_effect<E>(effect: E) -> BoxedEffect
+where
+    E: WorldEffect + Send + 'static,
+{
+    BoxedEffect::new(effect)
+}
+
+impl Effect for BoxedEffect {
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.queue(move |world: &mut World| self.affect_world(world));
+    }
+}
+
+impl WorldEffect for BoxedEffect {
+    fn affect_world(self, world: &mut World) {
+        self.effect.affect_boxed(world);
+    }
+}
+
+impl RecordEffect for BoxedEffect {
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::effect_composition::extend;
+    use crate::effects::message::message_write;
+    use crate::effects::number_data::{NumberMessage, NumberResource};
+    use crate::effects::resource::res_set;
+    use crate::prelude::{affect, in_and_then_compose};
+
+    proptest! {
+        #[test]
+        fn boxed_effects_of_different_types_can_be_extended(
+            initial in any::<NumberResource>(),
+            put in any::<NumberResource>(),
+            messages in prop::collection::vec(any::<NumberMessage>(), 0..8),
+        ) {
+            let mut app = App::new();
+
+            let messages_to_write = messages.clone();
+
+            app.insert_resource(initial)
+                .add_message::<NumberMessage>()
+                .add_systems(
+                    Update,
+                    (move || vec![boxed_effect(res_set(put))])
+                        .pipe(in_and_then_compose(
+                            move || {
+                                messages_to_write
+                                    .iter()
+                                    .map(|message| boxed_effect(message_write(*message)))
+                                    .collect::<Vec<_>>()
+                            },
+                            extend,
+                        ))
+                        .pipe(affect),
+                );
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &put);
+
+            let written = app
+                .world()
+                .resource::<Messages<NumberMessage>>()
+                .iter_current_update_messages()
+                .copied()
+                .collect::<Vec<_>>();
+
+            prop_assert_eq!(written, messages);
+        }
+    }
+}
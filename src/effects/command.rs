@@ -1,9 +1,14 @@
 //! [`Effect`]s that queue `Commands`.
+use std::fmt::Debug;
 use std::marker::PhantomData;
 
+use bevy::ecs::bundle::NoBundleEffect;
+use bevy::ecs::system::{IntoObserverSystem, SystemId};
 use bevy::prelude::*;
 
 use crate::Effect;
+use crate::effect_record::{EffectRecord, EffectRecorder, RecordEffect};
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
 
 /// [`Effect`] that pushes a generic command to the command queue.
 ///
@@ -119,6 +124,24 @@ where
     }
 }
 
+impl<C> WorldEffect for CommandQueue<C>
+where
+    C: Command,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<C> RecordEffect for CommandQueue<C>
+where
+    C: Command + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
 /// [`Effect`] that queues a command for inserting the provided `Resource` in the `World`.
 ///
 /// Can be constucted with [`command_insert_resource`].
@@ -203,6 +226,24 @@ where
     }
 }
 
+impl<R> WorldEffect for CommandInsertResource<R>
+where
+    R: Resource,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<R> RecordEffect for CommandInsertResource<R>
+where
+    R: Resource + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
 /// [`Effect`] that queues a command for removing a `Resource` from the `World`.
 ///
 /// Can be constructed with [`command_remove_resource`].
@@ -300,6 +341,24 @@ where
     }
 }
 
+impl<R> WorldEffect for CommandRemoveResource<R>
+where
+    R: Resource,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<R> RecordEffect for CommandRemoveResource<R>
+where
+    R: Resource + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
 /// [`Effect`] that queues a command for spawning an entity with the provided `Bundle`.
 ///
 /// See [`CommandSpawnAnd`] if you need to produce an extra effect with the spawned `Entity` id.
@@ -380,6 +439,24 @@ where
     }
 }
 
+impl<B> WorldEffect for CommandSpawn<B>
+where
+    B: Bundle,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<B> RecordEffect for CommandSpawn<B>
+where
+    B: Bundle + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
 /// [`Effect`] that queues a command for spawning an entity with the provided `Bundle`, then
 /// supplies the entity id to the provided effect-producing function to cause another effect.
 ///
@@ -501,142 +578,1256 @@ where
     }
 }
 
-/// [`Effect`] that queues a command for triggering the given event.
+impl<B, E> WorldEffect for CommandSpawnAnd<B, E>
+where
+    B: Bundle,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<B, E> RecordEffect for CommandSpawnAnd<B, E>
+where
+    B: Bundle + Debug,
+    E: RecordEffect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        let record = EffectRecord::new(&self);
+        let entity = recorder.placeholder_entity();
+
+        recorder.push_and(record, |recorder| (self.f)(entity).record(recorder));
+    }
+}
+
+/// [`Effect`] that queues a command for spawning an entity with the provided `Bundle`, then
+/// supplies the entity id to the provided effect-producing function to cause another effect,
+/// without boxing the function.
 ///
-/// Can be constructed with [`command_trigger`].
+/// This is the unboxed alternative to [`CommandSpawnAnd`]. It is generic over the function type
+/// `F` instead, so constructing it doesn't allocate and performing it doesn't use dynamic
+/// dispatch. The function type can be hidden in the system's return type with `impl FnOnce`.
+///
+/// Can be constructed with [`command_spawn_and_fn`].
 ///
 /// # Example
-/// In this example, a system is written that triggers a `Winner` event if any entity has a score
-/// above 100.
+/// In this example, a system is written that spawns a `Player`, and a `Sword` as a child of the
+/// player.
 /// ```
 /// use bevy::prelude::*;
 /// use bevy_pipe_affect::prelude::*;
 ///
 /// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
-/// # #[derive(proptest_derive::Arbitrary)]
-/// struct Score(u8);
+/// struct Player;
 ///
-/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Event)]
-/// struct Winner(Entity);
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// struct Sword;
 ///
 /// /// Pure system using effects.
-/// fn declare_winner_pure(query: Query<(Entity, &Score)>) -> Option<CommandTrigger<Winner>> {
-///     query
-///         .iter()
-///         .find(|(_, score)| score.0 >= 100)
-///         .map(|(entity, _)| command_trigger(Winner(entity)))
+/// fn spawn_armed_player_pure() -> CommandSpawnAndFn<
+///     impl FnOnce(Entity) -> CommandSpawn<(Sword, ChildOf)>,
+///     Player,
+///     CommandSpawn<(Sword, ChildOf)>,
+/// > {
+///     command_spawn_and_fn(Player, |player_entity| {
+///         command_spawn((Sword, ChildOf(player_entity)))
+///     })
 /// }
 ///
 /// /// Equivalent impure system.
-/// fn declare_winner_impure(query: Query<(Entity, &Score)>, mut commands: Commands) {
-///     if let Some((entity, _)) = query.iter().find(|(_, score)| score.0 >= 100) {
-///         commands.trigger(Winner(entity))
-///     }
+/// fn spawn_armed_player_impure(mut commands: Commands) {
+///     commands.spawn(Player).with_children(|parent| {
+///         parent.spawn(Sword);
+///     });
 /// }
 /// #
-/// # use proptest::prelude::*;
-/// #
-/// # fn reset_winner_score(winner: On<Winner>) -> QueryEntityAffect<ComponentSet<Score>> {
-/// #     query_entity_affect(winner.0, component_set(Score(0)))
-/// # }
-/// #
-/// # fn app_setup(component_table: Vec<Option<Score>>) -> App {
-/// #     let mut app = App::new();
-/// #     app.add_systems(
-/// #         Update,
-/// #         (|| command_spawn(Observer::new(reset_winner_score.pipe(affect)))).pipe(affect),
-/// #     );
-/// #     component_table.into_iter().for_each(|score| {
-/// #         let mut entity = app.world_mut().spawn_empty();
-/// #         if let Some(score) = score {
-/// #             entity.insert(score);
-/// #         }
-/// #     });
-/// #
-/// #     app
+/// # fn app_setup() -> App {
+/// #     App::new()
 /// # }
 /// #
-/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Score>)> {
-/// #     let mut query = world.query::<(Entity, Option<&Score>)>();
+/// # fn test_state(
+/// #     world: &mut World,
+/// # ) -> Vec<(Entity, Option<&Player>, Option<&Sword>, Option<&ChildOf>)> {
+/// #     let mut query =
+/// #         world.query::<(Entity, Option<&Player>, Option<&Sword>, Option<&ChildOf>)>();
 /// #     query.iter(world).collect()
 /// # }
 /// #
-/// # proptest! {
-/// #     fn main(component_table: Vec<Option<Score>>) {
-/// #         let mut pure_app = app_setup(component_table.clone());
-/// #         pure_app.add_systems(Update, declare_winner_pure.pipe(affect));
+/// # fn main() {
+/// #     let mut pure_app = app_setup();
+/// #     pure_app.add_systems(Update, spawn_armed_player_pure.pipe(affect));
 /// #
-/// #         let mut impure_app = app_setup(component_table.clone());
-/// #         impure_app.add_systems(Update, declare_winner_impure);
+/// #     let mut impure_app = app_setup();
+/// #     impure_app.add_systems(Update, spawn_armed_player_impure);
 /// #
-/// #         for _ in 0..3 {
-/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
-/// #             pure_app.update();
-/// #             impure_app.update();
-/// #         }
+/// #     for _ in 0..32 {
+/// #         assert_eq!(
+/// #             test_state(pure_app.world_mut()),
+/// #             test_state(impure_app.world_mut())
+/// #         );
+/// #         pure_app.update();
+/// #         impure_app.update();
 /// #     }
 /// # }
 /// ```
+///
+/// Not shown...
+/// - In this example, [`CommandSpawn`] is used as the additional [`Effect`], but any other effect
+/// could be produced.
 #[doc = include_str!("defer_command_note.md")]
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
-pub struct CommandTrigger<E>
+#[derive(derive_more::Debug)]
+pub struct CommandSpawnAndFn<F, B, E>
 where
-    E: Event,
-    for<'a> E::Trigger<'a>: Default,
+    F: FnOnce(Entity) -> E,
+    B: Bundle,
+    E: Effect,
 {
-    /// The event being triggered.
-    pub event: E,
+    /// The bundle to spawn.
+    pub bundle: B,
+    /// The `Entity -> Effect` function that may cause another effect.
+    #[debug("Entity -> {}", std::any::type_name::<E>())]
+    pub f: F,
 }
 
-/// Construct a new [`CommandTrigger`] [`Effect`].
-pub fn command_trigger<E>(event: E) -> CommandTrigger<E>
+/// Construct a new [`CommandSpawnAndFn`] [`Effect`].
+pub fn command_spawn_and_fn<F, B, E>(bundle: B, f: F) -> CommandSpawnAndFn<F, B, E>
 where
-    E: Event,
-    for<'a> E::Trigger<'a>: Default,
+    F: FnOnce(Entity) -> E,
+    B: Bundle,
+    E: Effect,
 {
-    CommandTrigger { event }
+    CommandSpawnAndFn { bundle, f }
 }
 
-impl<E> Effect for CommandTrigger<E>
+impl<F, B, E> Effect for CommandSpawnAndFn<F, B, E>
 where
-    E: Event,
-    for<'a> E::Trigger<'a>: Default,
+    F: FnOnce(Entity) -> E,
+    B: Bundle,
+    E: Effect,
 {
-    type MutParam = Commands<'static, 'static>;
+    type MutParam = (Commands<'static, 'static>, E::MutParam);
 
     fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
-        param.trigger(self.event);
+        let entity = param.0.spawn(self.bundle).id();
+
+        (self.f)(entity).affect(&mut param.1);
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use proptest::prelude::*;
-
-    use super::*;
-    use crate::effects::number_data::{NumberComponent, NumberEvent, NumberResource};
-    use crate::prelude::affect;
+impl<F, B, E> WorldEffect for CommandSpawnAndFn<F, B, E>
+where
+    F: FnOnce(Entity) -> E,
+    B: Bundle,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
 
-    proptest! {
-        #[test]
-        fn command_queue_can_spawn_entities_non_exclusively(component in any::<NumberComponent<0>>()) {
-            let mut app = App::new();
+impl<F, B, E> RecordEffect for CommandSpawnAndFn<F, B, E>
+where
+    F: FnOnce(Entity) -> E,
+    B: Bundle + Debug,
+    E: RecordEffect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        let record = EffectRecord::new(&self);
+        let entity = recorder.placeholder_entity();
 
-            let component_count = app.world_mut().query_filtered::<(), With<NumberComponent<0>>>().iter(app.world()).count();
+        recorder.push_and(record, |recorder| (self.f)(entity).record(recorder));
+    }
+}
 
-            assert_eq!(component_count, 0);
+/// [`Effect`] that queues a command for spawning many entities with the provided `Bundle`s.
+///
+/// Unlike a `Vec` of [`CommandSpawn`]s, the entities are spawned with a single
+/// `Commands::spawn_batch` call, which is faster for large amounts of entities.
+///
+/// Can be constructed with [`command_spawn_batch`].
+///
+/// # Example
+/// In this example, a system is written that spawns a row of `Tile`s.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// struct Tile(u32);
+///
+/// /// Pure system using effects.
+/// fn spawn_tiles_pure() -> CommandSpawnBatch<Tile> {
+///     command_spawn_batch((0..100).map(Tile))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn spawn_tiles_impure(mut commands: Commands) {
+///     commands.spawn_batch((0..100).map(Tile));
+/// }
+/// #
+/// # fn app_setup() -> App {
+/// #     App::new()
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Tile>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Tile>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # fn main() {
+/// #     let mut pure_app = app_setup();
+/// #     pure_app.add_systems(Update, spawn_tiles_pure.pipe(affect));
+/// #
+/// #     let mut impure_app = app_setup();
+/// #     impure_app.add_systems(Update, spawn_tiles_impure);
+/// #
+/// #     for _ in 0..3 {
+/// #         assert_eq!(
+/// #             test_state(pure_app.world_mut()),
+/// #             test_state(impure_app.world_mut())
+/// #         );
+/// #         pure_app.update();
+/// #         impure_app.update();
+/// #     }
+/// # }
+/// ```
+#[doc = include_str!("defer_command_note.md")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandSpawnBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect>,
+{
+    /// The bundles to spawn, one entity each.
+    pub bundles: Vec<B>,
+}
 
-            let spawn_component_system = move || {
-                command_queue(move |world: &mut World| {
-                    world.spawn(component.clone());
-                })
-            };
+/// Construct a new [`CommandSpawnBatch`] [`Effect`].
+pub fn command_spawn_batch<B>(bundles: impl IntoIterator<Item = B>) -> CommandSpawnBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect>,
+{
+    CommandSpawnBatch {
+        bundles: bundles.into_iter().collect(),
+    }
+}
 
+impl<B> Effect for CommandSpawnBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect>,
+{
+    type MutParam = Commands<'static, 'static>;
 
-            assert!(!IntoSystem::into_system(spawn_component_system.pipe(affect)).is_exclusive());
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.spawn_batch(self.bundles);
+    }
+}
 
-            app.add_systems(
-                Update,
+impl<B> WorldEffect for CommandSpawnBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect>,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<B> RecordEffect for CommandSpawnBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect> + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that queues a command for inserting the provided `Bundle`s on their paired entities.
+///
+/// The bundles are inserted with a single `Commands::insert_batch` call, which is faster than
+/// inserting them one entity at a time. The command panics if any of the entities don't exist, see
+/// [`CommandTryInsertBatch`] for ignoring them instead.
+///
+/// Can be constructed with [`command_insert_batch`].
+///
+/// # Example
+/// In this example, a system is written that gives all `Player`s a `Score` of 0.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Player;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u32);
+///
+/// /// Pure system using effects.
+/// fn init_scores_pure(players: Query<Entity, With<Player>>) -> CommandInsertBatch<Score> {
+///     command_insert_batch(players.iter().map(|player| (player, Score(0))))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn init_scores_impure(players: Query<Entity, With<Player>>, mut commands: Commands) {
+///     let batch = players
+///         .iter()
+///         .map(|player| (player, Score(0)))
+///         .collect::<Vec<_>>();
+///
+///     commands.insert_batch(batch);
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(component_table: Vec<(Option<Player>, Option<Score>)>) -> App {
+/// #     let mut app = App::new();
+/// #     component_table.into_iter().for_each(|(player, score)| {
+/// #         let mut entity = app.world_mut().spawn_empty();
+/// #         if let Some(player) = player {
+/// #             entity.insert(player);
+/// #         }
+/// #         if let Some(score) = score {
+/// #             entity.insert(score);
+/// #         }
+/// #     });
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Player>, Option<&Score>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Player>, Option<&Score>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<(Option<Player>, Option<Score>)>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, init_scores_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table.clone());
+/// #         impure_app.add_systems(Update, init_scores_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[doc = include_str!("defer_command_note.md")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandInsertBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect>,
+{
+    /// The entities and the bundles to insert on them.
+    pub batch: Vec<(Entity, B)>,
+}
+
+/// Construct a new [`CommandInsertBatch`] [`Effect`].
+pub fn command_insert_batch<B>(
+    batch: impl IntoIterator<Item = (Entity, B)>,
+) -> CommandInsertBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect>,
+{
+    CommandInsertBatch {
+        batch: batch.into_iter().collect(),
+    }
+}
+
+impl<B> Effect for CommandInsertBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect>,
+{
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.insert_batch(self.batch);
+    }
+}
+
+impl<B> WorldEffect for CommandInsertBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect>,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<B> RecordEffect for CommandInsertBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect> + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that queues a command for inserting the provided `Bundle`s on their paired entities,
+/// ignoring entities that don't exist.
+///
+/// Like [`CommandInsertBatch`], but uses `Commands::try_insert_batch`, so a warning is logged
+/// instead of panicking for missing entities.
+///
+/// Can be constructed with [`command_try_insert_batch`].
+///
+/// # Example
+/// In this example, a system is written that gives all `Player`s a `Score` of 0, even if some of
+/// them might be despawned before the command is applied.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Player;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u32);
+///
+/// /// Pure system using effects.
+/// fn init_scores_pure(players: Query<Entity, With<Player>>) -> CommandTryInsertBatch<Score> {
+///     command_try_insert_batch(players.iter().map(|player| (player, Score(0))))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn init_scores_impure(players: Query<Entity, With<Player>>, mut commands: Commands) {
+///     let batch = players
+///         .iter()
+///         .map(|player| (player, Score(0)))
+///         .collect::<Vec<_>>();
+///
+///     commands.try_insert_batch(batch);
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(component_table: Vec<(Option<Player>, Option<Score>)>) -> App {
+/// #     let mut app = App::new();
+/// #     component_table.into_iter().for_each(|(player, score)| {
+/// #         let mut entity = app.world_mut().spawn_empty();
+/// #         if let Some(player) = player {
+/// #             entity.insert(player);
+/// #         }
+/// #         if let Some(score) = score {
+/// #             entity.insert(score);
+/// #         }
+/// #     });
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Player>, Option<&Score>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Player>, Option<&Score>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<(Option<Player>, Option<Score>)>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, init_scores_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table.clone());
+/// #         impure_app.add_systems(Update, init_scores_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[doc = include_str!("defer_command_note.md")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandTryInsertBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect>,
+{
+    /// The entities and the bundles to insert on them.
+    pub batch: Vec<(Entity, B)>,
+}
+
+/// Construct a new [`CommandTryInsertBatch`] [`Effect`].
+pub fn command_try_insert_batch<B>(
+    batch: impl IntoIterator<Item = (Entity, B)>,
+) -> CommandTryInsertBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect>,
+{
+    CommandTryInsertBatch {
+        batch: batch.into_iter().collect(),
+    }
+}
+
+impl<B> Effect for CommandTryInsertBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect>,
+{
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.try_insert_batch(self.batch);
+    }
+}
+
+impl<B> WorldEffect for CommandTryInsertBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect>,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<B> RecordEffect for CommandTryInsertBatch<B>
+where
+    B: Bundle<Effect: NoBundleEffect> + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that queues a command for triggering the given event.
+///
+/// Can be constructed with [`command_trigger`].
+///
+/// # Example
+/// In this example, a system is written that triggers a `Winner` event if any entity has a score
+/// above 100.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u8);
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Event)]
+/// struct Winner(Entity);
+///
+/// /// Pure system using effects.
+/// fn declare_winner_pure(query: Query<(Entity, &Score)>) -> Option<CommandTrigger<Winner>> {
+///     query
+///         .iter()
+///         .find(|(_, score)| score.0 >= 100)
+///         .map(|(entity, _)| command_trigger(Winner(entity)))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn declare_winner_impure(query: Query<(Entity, &Score)>, mut commands: Commands) {
+///     if let Some((entity, _)) = query.iter().find(|(_, score)| score.0 >= 100) {
+///         commands.trigger(Winner(entity))
+///     }
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn reset_winner_score(winner: On<Winner>) -> QueryEntityAffect<ComponentSet<Score>> {
+/// #     query_entity_affect(winner.0, component_set(Score(0)))
+/// # }
+/// #
+/// # fn app_setup(component_table: Vec<Option<Score>>) -> App {
+/// #     let mut app = App::new();
+/// #     app.add_systems(
+/// #         Update,
+/// #         (|| command_spawn(Observer::new(reset_winner_score.pipe(affect)))).pipe(affect),
+/// #     );
+/// #     component_table.into_iter().for_each(|score| {
+/// #         let mut entity = app.world_mut().spawn_empty();
+/// #         if let Some(score) = score {
+/// #             entity.insert(score);
+/// #         }
+/// #     });
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Score>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Score>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<Option<Score>>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, declare_winner_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table.clone());
+/// #         impure_app.add_systems(Update, declare_winner_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[doc = include_str!("defer_command_note.md")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandTrigger<E>
+where
+    E: Event,
+    for<'a> E::Trigger<'a>: Default,
+{
+    /// The event being triggered.
+    pub event: E,
+}
+
+/// Construct a new [`CommandTrigger`] [`Effect`].
+pub fn command_trigger<E>(event: E) -> CommandTrigger<E>
+where
+    E: Event,
+    for<'a> E::Trigger<'a>: Default,
+{
+    CommandTrigger { event }
+}
+
+impl<E> Effect for CommandTrigger<E>
+where
+    E: Event,
+    for<'a> E::Trigger<'a>: Default,
+{
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.trigger(self.event);
+    }
+}
+
+impl<E> WorldEffect for CommandTrigger<E>
+where
+    E: Event,
+    for<'a> E::Trigger<'a>: Default,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<E> RecordEffect for CommandTrigger<E>
+where
+    E: Event + Debug,
+    for<'a> E::Trigger<'a>: Default,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that queues a command for running the registered one-shot system with the provided
+/// `SystemId`.
+///
+/// See [`CommandRunSystemWith`] for systems that take an input, and [`command_register_system_and`]
+/// for registering one-shot systems.
+///
+/// Can be constructed with [`command_run_system`].
+///
+/// # Example
+/// In this example, a system is written that runs the `OnClick` callback of a button.
+/// ```
+/// use bevy::ecs::system::SystemId;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u8);
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
+/// struct OnClick(SystemId);
+///
+/// /// Pure system using effects.
+/// fn click_pure(on_click: Res<OnClick>) -> CommandRunSystem {
+///     command_run_system(on_click.0)
+/// }
+///
+/// /// Equivalent impure system.
+/// fn click_impure(on_click: Res<OnClick>, mut commands: Commands) {
+///     commands.run_system(on_click.0);
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn add_point(mut score: ResMut<Score>) {
+/// #     score.0 = score.0.saturating_add(1);
+/// # }
+/// #
+/// # fn app_setup(score: Score) -> App {
+/// #     let mut app = App::new();
+/// #     let on_click = app.world_mut().register_system(add_point);
+/// #     app.insert_resource(score).insert_resource(OnClick(on_click));
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &Score {
+/// #     world.resource::<Score>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(score: Score) {
+/// #         let mut pure_app = app_setup(score);
+/// #         pure_app.add_systems(Update, click_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(score);
+/// #         impure_app.add_systems(Update, click_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[doc = include_str!("defer_command_note.md")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CommandRunSystem {
+    /// The id of the one-shot system to run.
+    pub system: SystemId,
+}
+
+/// Construct a new [`CommandRunSystem`] [`Effect`].
+pub fn command_run_system(system: SystemId) -> CommandRunSystem {
+    CommandRunSystem { system }
+}
+
+impl Effect for CommandRunSystem {
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.run_system(self.system);
+    }
+}
+
+impl WorldEffect for CommandRunSystem {
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl RecordEffect for CommandRunSystem {
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that queues a command for running the registered one-shot system with the provided
+/// `SystemId`, passing it the provided input.
+///
+/// See [`CommandRunSystem`] for systems that don't take an input.
+///
+/// Can be constructed with [`command_run_system_with`].
+///
+/// # Example
+/// In this example, a system is written that runs the `OnClick` callback of a button, passing it
+/// the points the button is worth.
+/// ```
+/// use bevy::ecs::system::SystemId;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u8);
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
+/// struct OnClick(SystemId<In<u8>>);
+///
+/// const POINTS: u8 = 10;
+///
+/// /// Pure system using effects.
+/// fn click_pure(on_click: Res<OnClick>) -> CommandRunSystemWith<u8> {
+///     command_run_system_with(on_click.0, POINTS)
+/// }
+///
+/// /// Equivalent impure system.
+/// fn click_impure(on_click: Res<OnClick>, mut commands: Commands) {
+///     commands.run_system_with(on_click.0, POINTS);
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn add_points(In(points): In<u8>, mut score: ResMut<Score>) {
+/// #     score.0 = score.0.saturating_add(points);
+/// # }
+/// #
+/// # fn app_setup(score: Score) -> App {
+/// #     let mut app = App::new();
+/// #     let on_click = app.world_mut().register_system(add_points);
+/// #     app.insert_resource(score).insert_resource(OnClick(on_click));
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &Score {
+/// #     world.resource::<Score>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(score: Score) {
+/// #         let mut pure_app = app_setup(score);
+/// #         pure_app.add_systems(Update, click_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(score);
+/// #         impure_app.add_systems(Update, click_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[doc = include_str!("defer_command_note.md")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CommandRunSystemWith<I>
+where
+    I: Send + 'static,
+{
+    /// The id of the one-shot system to run.
+    pub system: SystemId<In<I>>,
+    /// The input passed to the one-shot system.
+    pub input: I,
+}
+
+/// Construct a new [`CommandRunSystemWith`] [`Effect`].
+pub fn command_run_system_with<I>(system: SystemId<In<I>>, input: I) -> CommandRunSystemWith<I>
+where
+    I: Send + 'static,
+{
+    CommandRunSystemWith { system, input }
+}
+
+impl<I> Effect for CommandRunSystemWith<I>
+where
+    I: Send + 'static,
+{
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.run_system_with(self.system, self.input);
+    }
+}
+
+impl<I> WorldEffect for CommandRunSystemWith<I>
+where
+    I: Send + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<I> RecordEffect for CommandRunSystemWith<I>
+where
+    I: Send + Debug + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// Type alias for the effect-producing function used in [`CommandRegisterSystemAnd`].
+pub type BoxedCommandRegisterSystemAndFn<S, E> =
+    Box<dyn FnOnce(SystemId<<S as System>::In, <S as System>::Out>) -> E>;
+
+/// [`Effect`] that queues a command for registering the provided one-shot system, then supplies
+/// its `SystemId` to the provided effect-producing function to cause another effect.
+///
+/// This follows the continuation style of [`CommandSpawnAnd`], so the `SystemId` can be stored,
+/// e.g. with [`command_insert_resource`], and run later with [`CommandRunSystem`] or
+/// [`CommandRunSystemWith`].
+///
+/// Can be constructed with [`command_register_system_and`].
+///
+/// # Example
+/// In this example, a system is written that registers a `reset_score` callback and stores its
+/// `SystemId` in a resource.
+/// ```
+/// use bevy::ecs::system::SystemId;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
+/// struct Score(u8);
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
+/// struct OnReset(SystemId);
+///
+/// fn reset_score() -> ResSet<Score> {
+///     res_set(Score(0))
+/// }
+///
+/// /// Pure system using effects.
+/// fn register_reset_pure() -> CommandRegisterSystemAnd<
+///     impl System<In = (), Out = ()>,
+///     CommandInsertResource<OnReset>,
+/// > {
+///     command_register_system_and(reset_score.pipe(affect), |system| {
+///         command_insert_resource(OnReset(system))
+///     })
+/// }
+///
+/// /// Equivalent impure system.
+/// fn register_reset_impure(mut commands: Commands) {
+///     let system = commands.register_system(reset_score.pipe(affect));
+///     commands.insert_resource(OnReset(system));
+/// }
+/// #
+/// # fn app_setup() -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(Score(10));
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> (Option<OnReset>, Score) {
+/// #     if let Some(on_reset) = world.get_resource::<OnReset>().copied() {
+/// #         world.run_system(on_reset.0).unwrap();
+/// #     }
+/// #
+/// #     (world.get_resource::<OnReset>().copied(), *world.resource::<Score>())
+/// # }
+/// #
+/// # fn main() {
+/// #     let mut pure_app = app_setup();
+/// #     pure_app.add_systems(Startup, register_reset_pure.pipe(affect));
+/// #
+/// #     let mut impure_app = app_setup();
+/// #     impure_app.add_systems(Startup, register_reset_impure);
+/// #
+/// #     for _ in 0..3 {
+/// #         assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #         pure_app.update();
+/// #         impure_app.update();
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - In this example, [`CommandInsertResource`] is used as the additional [`Effect`], but any
+///   other effect could be produced.
+#[doc = include_str!("defer_command_note.md")]
+#[derive(derive_more::Debug)]
+pub struct CommandRegisterSystemAnd<S, E>
+where
+    S: System,
+    E: Effect,
+{
+    /// The one-shot system to register.
+    #[debug("{}", std::any::type_name::<S>())]
+    pub system: S,
+    /// The `SystemId -> Effect` function that may cause another effect.
+    #[debug(
+        "{0} -> {1}",
+        std::any::type_name::<SystemId<S::In, S::Out>>(),
+        std::any::type_name::<E>()
+    )]
+    pub f: BoxedCommandRegisterSystemAndFn<S, E>,
+}
+
+/// Construct a new [`CommandRegisterSystemAnd`] [`Effect`], with an extra effect using the
+/// `SystemId`.
+pub fn command_register_system_and<S, I, O, M, F, E>(
+    system: S,
+    f: F,
+) -> CommandRegisterSystemAnd<S::System, E>
+where
+    S: IntoSystem<I, O, M>,
+    I: SystemInput + Send + 'static,
+    O: Send + 'static,
+    F: FnOnce(SystemId<I, O>) -> E + 'static,
+    E: Effect,
+{
+    CommandRegisterSystemAnd {
+        system: IntoSystem::into_system(system),
+        f: Box::new(f),
+    }
+}
+
+impl<S, E> Effect for CommandRegisterSystemAnd<S, E>
+where
+    S: System,
+    S::In: Send,
+    S::Out: Send,
+    E: Effect,
+{
+    type MutParam = (Commands<'static, 'static>, E::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        let system = param.0.register_system(self.system);
+
+        (self.f)(system).affect(&mut param.1);
+    }
+}
+
+impl<S, E> WorldEffect for CommandRegisterSystemAnd<S, E>
+where
+    S: System,
+    S::In: Send,
+    S::Out: Send,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<S, E> RecordEffect for CommandRegisterSystemAnd<S, E>
+where
+    S: System,
+    S::In: Send,
+    S::Out: Send,
+    E: RecordEffect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        let record = EffectRecord::new(&self);
+        let system = SystemId::from_entity(recorder.placeholder_entity());
+
+        recorder.push_and(record, |recorder| (self.f)(system).record(recorder));
+    }
+}
+
+/// [`Effect`] that queues a command for triggering the given event with the given `Trigger`.
+///
+/// Unlike [`CommandTrigger`], this doesn't require the event's `Trigger` to implement `Default`,
+/// so it supports events with custom triggers.
+///
+/// Can be constructed with [`command_trigger_with`].
+///
+/// # Example
+/// In this example, a system is written that triggers a `Winner` event with an explicit
+/// `GlobalTrigger` for the first entity with a score of 100 or more.
+/// ```
+/// use bevy::ecs::event::GlobalTrigger;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u8);
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Event)]
+/// struct Winner(Entity);
+///
+/// /// Pure system using effects.
+/// fn declare_winner_pure(query: Query<(Entity, &Score)>) -> Option<CommandTriggerWith<Winner>> {
+///     query
+///         .iter()
+///         .find(|(_, score)| score.0 >= 100)
+///         .map(|(entity, _)| command_trigger_with(Winner(entity), GlobalTrigger))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn declare_winner_impure(query: Query<(Entity, &Score)>, mut commands: Commands) {
+///     if let Some((entity, _)) = query.iter().find(|(_, score)| score.0 >= 100) {
+///         commands.trigger_with(Winner(entity), GlobalTrigger)
+///     }
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn reset_winner_score(winner: On<Winner>) -> QueryEntityAffect<ComponentSet<Score>> {
+/// #     query_entity_affect(winner.0, component_set(Score(0)))
+/// # }
+/// #
+/// # fn app_setup(component_table: Vec<Option<Score>>) -> App {
+/// #     let mut app = App::new();
+/// #     app.add_systems(
+/// #         Startup,
+/// #         (|| command_add_observer(reset_winner_score.pipe(affect))).pipe(affect),
+/// #     );
+/// #     component_table.into_iter().for_each(|score| {
+/// #         let mut entity = app.world_mut().spawn_empty();
+/// #         if let Some(score) = score {
+/// #             entity.insert(score);
+/// #         }
+/// #     });
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Score>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Score>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<Option<Score>>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, declare_winner_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table.clone());
+/// #         impure_app.add_systems(Update, declare_winner_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[doc = include_str!("defer_command_note.md")]
+#[derive(derive_more::Debug)]
+pub struct CommandTriggerWith<E>
+where
+    E: Event<Trigger<'static>: Send + Sync>,
+{
+    /// The event being triggered.
+    pub event: E,
+    /// The trigger used for triggering the event.
+    #[debug("{}", std::any::type_name::<E::Trigger<'static>>())]
+    pub trigger: E::Trigger<'static>,
+}
+
+/// Construct a new [`CommandTriggerWith`] [`Effect`].
+pub fn command_trigger_with<E>(event: E, trigger: E::Trigger<'static>) -> CommandTriggerWith<E>
+where
+    E: Event<Trigger<'static>: Send + Sync>,
+{
+    CommandTriggerWith { event, trigger }
+}
+
+impl<E> Effect for CommandTriggerWith<E>
+where
+    E: Event<Trigger<'static>: Send + Sync>,
+{
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.trigger_with(self.event, self.trigger);
+    }
+}
+
+impl<E> WorldEffect for CommandTriggerWith<E>
+where
+    E: Event<Trigger<'static>: Send + Sync>,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<E> RecordEffect for CommandTriggerWith<E>
+where
+    E: Event<Trigger<'static>: Send + Sync> + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// Construct a [`CommandSpawn`] [`Effect`] that spawns an `Observer` running the given observer
+/// system.
+///
+/// This is the effect equivalent of `Commands::add_observer`. See [`entity_command_observe`] for
+/// observing a specific entity.
+///
+/// # Example
+/// In this example, a system is written that adds an observer resetting the score of a `Winner`.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Component)]
+/// struct Score(u8);
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Event)]
+/// struct Winner(Entity);
+///
+/// fn reset_winner_score(winner: On<Winner>) -> QueryEntityAffect<ComponentSet<Score>> {
+///     query_entity_affect(winner.0, component_set(Score(0)))
+/// }
+///
+/// /// Pure system using effects.
+/// fn add_observer_pure() -> CommandSpawn<Observer> {
+///     command_add_observer(reset_winner_score.pipe(affect))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn add_observer_impure(mut commands: Commands) {
+///     commands.add_observer(reset_winner_score.pipe(affect));
+/// }
+/// #
+/// # fn app_setup() -> App {
+/// #     let mut app = App::new();
+/// #     let winner = app.world_mut().spawn(Score(100)).id();
+/// #     app.add_systems(Update, move |mut commands: Commands| commands.trigger(Winner(winner)));
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Score>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Score>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # fn main() {
+/// #     let mut pure_app = app_setup();
+/// #     pure_app.add_systems(Startup, add_observer_pure.pipe(affect));
+/// #
+/// #     let mut impure_app = app_setup();
+/// #     impure_app.add_systems(Startup, add_observer_impure);
+/// #
+/// #     for _ in 0..3 {
+/// #         assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #         pure_app.update();
+/// #         impure_app.update();
+/// #     }
+/// # }
+/// ```
+///
+/// [`entity_command_observe`]: crate::effects::entity_command::entity_command_observe
+pub fn command_add_observer<E, B, M>(
+    observer: impl IntoObserverSystem<E, B, M>,
+) -> CommandSpawn<Observer>
+where
+    E: Event,
+    B: Bundle,
+{
+    command_spawn(Observer::new(observer))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::effects::number_data::{NumberComponent, NumberEvent, NumberResource};
+    use crate::prelude::affect;
+
+    proptest! {
+        #[test]
+        fn command_queue_can_spawn_entities_non_exclusively(component in any::<NumberComponent<0>>()) {
+            let mut app = App::new();
+
+            let component_count = app.world_mut().query_filtered::<(), With<NumberComponent<0>>>().iter(app.world()).count();
+
+            assert_eq!(component_count, 0);
+
+            let spawn_component_system = move || {
+                command_queue(move |world: &mut World| {
+                    world.spawn(component.clone());
+                })
+            };
+
+
+            assert!(!IntoSystem::into_system(spawn_component_system.pipe(affect)).is_exclusive());
+
+            app.add_systems(
+                Update,
                 spawn_component_system.pipe(affect),
             );
 
@@ -705,6 +1896,68 @@ mod tests {
 
             assert_eq!(app.world().get_resource::<NumberResource>(), Some(&NumberResource(event.0)));
         }
+
+        #[test]
+        fn command_register_system_and_provides_runnable_system_id(resource in any::<NumberResource>()) {
+            let mut app = App::new();
+
+            #[derive(Resource)]
+            struct Callback(SystemId<In<NumberResource>>);
+
+            app.add_systems(
+                Startup,
+                (|| {
+                    command_register_system_and(
+                        (|In(resource): In<NumberResource>| command_insert_resource(resource)).pipe(affect),
+                        |system| command_insert_resource(Callback(system)),
+                    )
+                })
+                .pipe(affect),
+            )
+            .add_systems(
+                Update,
+                (move |callback: Res<Callback>| command_run_system_with(callback.0, resource)).pipe(affect),
+            );
+
+            app.update();
+
+            assert_eq!(app.world().get_resource::<NumberResource>(), Some(&resource));
+        }
+
+        #[test]
+        fn command_spawn_batch_spawns_all_bundles(components in prop::collection::vec(any::<NumberComponent<0>>(), 0..10)) {
+            let mut app = App::new();
+
+            let components_clone = components.clone();
+            app.add_systems(
+                Startup,
+                (move || command_spawn_batch(components_clone.clone())).pipe(affect),
+            );
+
+            app.update();
+
+            let spawned = app.world_mut().query::<&NumberComponent<0>>().iter(app.world()).cloned().collect::<Vec<_>>();
+
+            prop_assert_eq!(spawned, components);
+        }
+
+        #[test]
+        fn command_try_insert_batch_ignores_missing_entities(component in any::<NumberComponent<0>>()) {
+            let mut app = App::new();
+
+            let existing = app.world_mut().spawn_empty().id();
+            let despawned = app.world_mut().spawn_empty().id();
+            app.world_mut().despawn(despawned);
+
+            app.add_systems(
+                Update,
+                (move || command_try_insert_batch([(despawned, component), (existing, component)])).pipe(affect),
+            );
+
+            app.update();
+
+            prop_assert_eq!(app.world().get::<NumberComponent<0>>(existing), Some(&component));
+        }
     }
 
     #[test]
@@ -0,0 +1,480 @@
+//! [`Effect`]s that are performed later, after a number of frames or a duration of `Time`.
+//!
+//! Requires the [`DelayedEffectsPlugin`] to be added to the app.
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use bevy::platform::cell::SyncCell;
+use bevy::prelude::*;
+
+use crate::Effect;
+use crate::effect_record::{EffectRecorder, RecordEffect};
+use crate::effects::boxed::BoxedEffect;
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
+
+/// Handle to an effect scheduled by a [`Delayed`] effect, used for cancelling it with
+/// [`cancel_delayed`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DelayedHandle(u64);
+
+impl DelayedHandle {
+    /// Allocate a new handle, distinct from all handles allocated before it.
+    fn next() -> Self {
+        static NEXT_HANDLE: AtomicU64 = AtomicU64::new(0);
+
+        DelayedHandle(NEXT_HANDLE.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// How long a [`Delayed`] effect waits before being performed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Delay {
+    /// Wait for the given number of frames.
+    ///
+    /// `Frames(0)` is performed at the end of the frame it was scheduled in, `Frames(1)` at the
+    /// end of the next frame, and so on.
+    Frames(u32),
+    /// Wait for the given duration of `Time`.
+    ///
+    /// The remaining duration is counted down by `Time::delta` once per frame, starting with the
+    /// frame after the one it was scheduled in, and the effect is performed at the end of the
+    /// frame it reaches zero.
+    Duration(Duration),
+}
+
+impl Delay {
+    /// Count the delay down by one frame, returning `true` if it has passed.
+    fn tick(&mut self, delta: Duration) -> bool {
+        match self {
+            Delay::Frames(0) => true,
+            Delay::Frames(frames) => {
+                *frames -= 1;
+                false
+            }
+            Delay::Duration(duration) => {
+                *duration = duration.saturating_sub(delta);
+                duration.is_zero()
+            }
+        }
+    }
+}
+
+/// An effect in the [`DelayedEffects`] resource, waiting to be performed.
+#[derive(derive_more::Debug)]
+struct PendingEffect {
+    handle: DelayedHandle,
+    delay: Delay,
+    /// Whether the effect was scheduled since the last time delays were counted down.
+    ///
+    /// The `Time::delta` of the frame an effect is scheduled in elapsed before it was
+    /// scheduled, so it isn't subtracted from the delay.
+    fresh: bool,
+    #[debug(skip)]
+    effect: SyncCell<BoxedEffect>,
+}
+
+/// `Resource` storing the effects scheduled by [`Delayed`] effects.
+///
+/// Initialized by the [`DelayedEffectsPlugin`].
+#[derive(Debug, Default, Resource)]
+pub struct DelayedEffects {
+    pending: Vec<PendingEffect>,
+}
+
+impl DelayedEffects {
+    /// The number of effects waiting to be performed.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns true if there are no effects waiting to be performed.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns true if the effect with the given handle is still waiting to be performed.
+    pub fn contains(&self, handle: DelayedHandle) -> bool {
+        self.pending.iter().any(|pending| pending.handle == handle)
+    }
+
+    /// Count all delays down by one frame, returning the effects whose delay has passed.
+    fn take_ready(&mut self, delta: Duration) -> Vec<BoxedEffect> {
+        let mut ready = Vec::new();
+
+        std::mem::take(&mut self.pending)
+            .into_iter()
+            .for_each(|mut pending| {
+                let delta = if std::mem::take(&mut pending.fresh) {
+                    Duration::ZERO
+                } else {
+                    delta
+                };
+
+                if pending.delay.tick(delta) {
+                    ready.push(SyncCell::to_inner(pending.effect));
+                } else {
+                    self.pending.push(pending);
+                }
+            });
+
+        ready
+    }
+}
+
+/// Exclusive `bevy` system that performs the [`Delayed`] effects whose delay has passed.
+///
+/// Added to the `Last` schedule by the [`DelayedEffectsPlugin`]. Effects are performed in the
+/// order they were scheduled.
+pub fn apply_delayed_effects(world: &mut World) {
+    let delta = world
+        .get_resource::<Time>()
+        .map(Time::delta)
+        .unwrap_or_default();
+
+    let Some(mut delayed_effects) = world.get_resource_mut::<DelayedEffects>() else {
+        return;
+    };
+
+    let ready = delayed_effects.take_ready(delta);
+
+    ready
+        .into_iter()
+        .for_each(|effect| effect.affect_world(world));
+}
+
+/// `Plugin` that initializes the [`DelayedEffects`] resource and performs [`Delayed`] effects.
+///
+/// See [`apply_delayed_effects`] for more details.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DelayedEffectsPlugin;
+
+impl Plugin for DelayedEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DelayedEffects>()
+            .add_systems(Last, apply_delayed_effects);
+    }
+}
+
+/// [`Effect`] that schedules an effect to be performed after a [`Delay`].
+///
+/// The effect is parked in the [`DelayedEffects`] resource and performed by the
+/// [`apply_delayed_effects`] system with exclusive `World` access (see [`WorldEffect`] for
+/// caveats). Requires the [`DelayedEffectsPlugin`]. Durations are measured with `Time`.
+///
+/// The scheduled effect can be cancelled by passing this effect's `handle` to [`cancel_delayed`].
+/// This effect isn't `Clone`, so every scheduled effect has a handle of its own.
+///
+/// Can be constructed with [`after_frames`] or [`after`].
+///
+/// # Example
+/// In this example, a system is written that sets the `Trail` to the `Position` from 2 frames ago.
+/// ```
+/// use std::collections::VecDeque;
+///
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Position(u32);
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// struct Trail(u32);
+///
+/// /// Pure system using effects.
+/// fn trail_position_pure(position: Res<Position>) -> Delayed<ResSet<Trail>> {
+///     after_frames(2, res_set(Trail(position.0)))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn trail_position_impure(
+///     position: Res<Position>,
+///     mut history: Local<VecDeque<u32>>,
+///     mut trail: ResMut<Trail>,
+/// ) {
+///     history.push_back(position.0);
+///
+///     if history.len() > 2 {
+///         trail.0 = history.pop_front().unwrap();
+///     }
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(position: Position) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(position)
+/// #         .init_resource::<Trail>()
+/// #         .add_systems(First, |mut position: ResMut<Position>| {
+/// #             position.0 = position.0.wrapping_mul(3).wrapping_add(1)
+/// #         });
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &Trail {
+/// #     world.get_resource::<Trail>().unwrap()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(position: Position) {
+/// #         let mut pure_app = app_setup(position);
+/// #         pure_app
+/// #             .add_plugins(DelayedEffectsPlugin)
+/// #             .add_systems(Update, trail_position_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(position);
+/// #         impure_app.add_systems(Update, trail_position_impure);
+/// #
+/// #         for _ in 0..6 {
+/// #              prop_assert_eq!(resource_state(pure_app.world()), resource_state(impure_app.world()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct Delayed<E>
+where
+    E: WorldEffect + Send + 'static,
+{
+    /// The handle for cancelling the scheduled effect.
+    pub handle: DelayedHandle,
+    /// How long to wait before performing the effect.
+    pub delay: Delay,
+    /// The effect to perform.
+    pub effect: E,
+}
+
+impl<E> Delayed<E>
+where
+    E: WorldEffect + Send + 'static,
+{
+    /// Construct a new [`Delayed`] effect with a new handle.
+    pub fn new(delay: Delay, effect: E) -> Self {
+        Delayed {
+            handle: DelayedHandle::next(),
+            delay,
+            effect,
+        }
+    }
+}
+
+/// Construct a new [`Delayed`] [`Effect`] that waits for the given number of frames.
+pub fn after_frames<E>(frames: u32, effect: E) -> Delayed<E>
+where
+    E: WorldEffect + Send + 'static,
+{
+    Delayed::new(Delay::Frames(frames), effect)
+}
+
+/// Construct a new [`Delayed`] [`Effect`] that waits for the given duration of `Time`.
+pub fn after<E>(duration: Duration, effect: E) -> Delayed<E>
+where
+    E: WorldEffect + Send + 'static,
+{
+    Delayed::new(Delay::Duration(duration), effect)
+}
+
+impl<E> Effect for Delayed<E>
+where
+    E: WorldEffect + Send + 'static,
+{
+    type MutParam = ResMut<'static, DelayedEffects>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.pending.push(PendingEffect {
+            handle: self.handle,
+            delay: self.delay,
+            fresh: true,
+            effect: SyncCell::new(BoxedEffect::new(self.effect)),
+        });
+    }
+}
+
+impl<E> WorldEffect for Delayed<E>
+where
+    E: WorldEffect + Send + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<E> RecordEffect for Delayed<E>
+where
+    E: WorldEffect + Send + Debug + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that cancels an effect scheduled by a [`Delayed`] effect.
+///
+/// Does nothing if the effect has already been performed or cancelled.
+///
+/// Can be constructed with [`cancel_delayed`].
+///
+/// # Example
+/// In this example, an alarm is scheduled and then cancelled before it goes off.
+/// ```
+/// use bevy::ecs::system::RunSystemOnce;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
+/// struct Alarm(bool);
+///
+/// let mut app = App::new();
+/// app.add_plugins(DelayedEffectsPlugin)
+///     .insert_resource(Alarm(false));
+///
+/// let set_alarm = after_frames(1, res_set(Alarm(true)));
+/// let handle = set_alarm.handle;
+///
+/// set_alarm.affect_world(app.world_mut());
+///
+/// app.world_mut()
+///     .run_system_once((move || cancel_delayed(handle)).pipe(affect))
+///     .unwrap();
+///
+/// app.update();
+/// app.update();
+///
+/// assert_eq!(app.world().resource::<Alarm>(), &Alarm(false));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CancelDelayed {
+    /// The handle of the scheduled effect to cancel.
+    pub handle: DelayedHandle,
+}
+
+/// Construct a new [`CancelDelayed`] [`Effect`].
+pub fn cancel_delayed(handle: DelayedHandle) -> CancelDelayed {
+    CancelDelayed { handle }
+}
+
+impl Effect for CancelDelayed {
+    type MutParam = ResMut<'static, DelayedEffects>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param
+            .pending
+            .retain(|pending| pending.handle != self.handle);
+    }
+}
+
+impl WorldEffect for CancelDelayed {
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl RecordEffect for CancelDelayed {
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::effects::number_data::NumberResource;
+    use crate::effects::resource::res_set;
+    use crate::prelude::affect;
+
+    fn app_setup(initial: NumberResource) -> App {
+        let mut app = App::new();
+
+        app.add_plugins(DelayedEffectsPlugin)
+            .init_resource::<Time>()
+            .insert_resource(initial);
+
+        app
+    }
+
+    proptest! {
+        #[test]
+        fn after_frames_performs_effect_after_frames(frames in 0u32..8, initial in any::<NumberResource>(), put in any::<NumberResource>()) {
+            prop_assume!(initial != put);
+
+            let mut app = app_setup(initial);
+
+            app.add_systems(Startup, (move || after_frames(frames, res_set(put))).pipe(affect));
+
+            for _ in 0..frames {
+                app.update();
+
+                prop_assert_eq!(app.world().resource::<NumberResource>(), &initial);
+            }
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &put);
+            prop_assert!(app.world().resource::<DelayedEffects>().is_empty());
+        }
+
+        #[test]
+        fn after_performs_effect_after_duration(millis in 1u64..1000, step_millis in 1u64..100, initial in any::<NumberResource>(), put in any::<NumberResource>()) {
+            prop_assume!(initial != put);
+
+            let mut app = app_setup(initial);
+
+            app.add_systems(Startup, (move || after(Duration::from_millis(millis), res_set(put))).pipe(affect));
+
+            // The frame the effect is scheduled in doesn't count towards the delay.
+            app.world_mut().resource_mut::<Time>().advance_by(Duration::from_millis(millis));
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &initial);
+
+            let mut elapsed = 0;
+
+            while elapsed + step_millis < millis {
+                app.world_mut().resource_mut::<Time>().advance_by(Duration::from_millis(step_millis));
+                elapsed += step_millis;
+
+                app.update();
+
+                prop_assert_eq!(app.world().resource::<NumberResource>(), &initial);
+            }
+
+            app.world_mut().resource_mut::<Time>().advance_by(Duration::from_millis(step_millis));
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &put);
+        }
+
+        #[test]
+        fn cancel_delayed_prevents_only_cancelled_effect(initial in any::<NumberResource>(), put in any::<NumberResource>()) {
+            prop_assume!(initial != put);
+
+            let mut app = app_setup(initial);
+
+            let cancelled = after_frames(0, res_set(put));
+            let cancelled_handle = cancelled.handle;
+            let kept = after_frames(1, res_set(initial));
+            let kept_handle = kept.handle;
+
+            prop_assert_ne!(cancelled_handle, kept_handle);
+
+            (cancelled, kept, cancel_delayed(cancelled_handle)).affect_world(app.world_mut());
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &initial);
+            prop_assert!(!app.world().resource::<DelayedEffects>().contains(cancelled_handle));
+            prop_assert!(app.world().resource::<DelayedEffects>().contains(kept_handle));
+
+            app.update();
+
+            prop_assert!(app.world().resource::<DelayedEffects>().is_empty());
+        }
+    }
+}
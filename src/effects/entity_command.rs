@@ -1,9 +1,21 @@
 //! [`Effect`]s that queue entity-specific `Commands`.
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, PoisonError};
 
+use bevy::ecs::bundle::BundleFromComponents;
+use bevy::ecs::entity::EntityHashSet;
+use bevy::ecs::system::IntoObserverSystem;
 use bevy::prelude::*;
+use either::Either;
 
 use crate::Effect;
+use crate::coalesce::{Coalesce, retain_last_by_key};
+use crate::effect_record::{EffectRecord, EffectRecorder, RecordEffect};
+use crate::undo::InvertibleEffect;
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
 
 /// [`Effect`] that pushes a generic entity command to the command queue.
 ///
@@ -136,6 +148,24 @@ where
     }
 }
 
+impl<C> WorldEffect for EntityCommandQueue<C>
+where
+    C: EntityCommand,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<C> RecordEffect for EntityCommandQueue<C>
+where
+    C: EntityCommand + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
 /// [`Effect`] that queues a command for inserting the provided `Bundle` onto the `Entity`.
 ///
 /// Can be constructed with [`entity_command_insert`].
@@ -236,12 +266,73 @@ where
     }
 }
 
-/// [`Effect`] that queues a command for removing the `Bundle` from the `Entity`.
+impl<B> WorldEffect for EntityCommandInsert<B>
+where
+    B: Bundle,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<B> RecordEffect for EntityCommandInsert<B>
+where
+    B: Bundle + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<B> InvertibleEffect for EntityCommandInsert<B>
+where
+    B: Bundle + BundleFromComponents,
+{
+    type Inverse = EntityCommandRestore<B>;
+
+    fn affect_invertible(
+        self,
+        param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>,
+        inverses: &mut Vec<Self::Inverse>,
+    ) {
+        let inverse = EntityCommandRestore::pending(self.entity);
+        let captured = inverse.captured.clone();
+
+        param
+            .entity(self.entity)
+            .queue(move |mut entity: EntityWorldMut| {
+                let prior = take_bundle::<B>(&mut entity)?;
+                entity.insert(self.bundle);
+                set_captured(&captured, prior.into());
+
+                Ok::<_, PartialBundleError>(())
+            });
+
+        inverses.push(inverse);
+    }
+}
+
+impl<B> Coalesce for EntityCommandInsert<B>
+where
+    B: Bundle,
+{
+    fn coalesce(effects: &mut Vec<Self>) {
+        retain_last_by_key(effects, |effect| effect.entity);
+    }
+}
+
+/// [`Effect`] that queues a command for inserting the provided `Bundle` onto the `Entity`, if
+/// the entity still exists when the command is applied.
 ///
-/// Can be constructed with [`entity_command_remove`].
+/// Unlike [`EntityCommandInsert`], this does not panic or warn if the entity has been despawned
+/// by the time commands are applied. This is useful for entities that may be despawned by
+/// other systems in the same frame, like pooled or short-lived entities.
+///
+/// Can be constructed with [`entity_command_try_insert`].
 ///
 /// # Example
-/// In this example, a system is written that nerfs the `TopPlayer` by removing her `Shield`.
+/// In this example, a system is written that gives the `TopPlayer` a `Crown`, even if the top
+/// player may have already been despawned.
 /// ```
 /// use bevy::prelude::*;
 /// use bevy_pipe_affect::prelude::*;
@@ -250,57 +341,52 @@ where
 /// struct TopPlayer(Entity);
 ///
 /// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
-/// # #[derive(proptest_derive::Arbitrary)]
-/// struct Shield;
+/// struct Crown;
 ///
 /// /// Pure system using effects.
-/// fn nerf_top_player_pure(top_player: Res<TopPlayer>) -> EntityCommandRemove<Shield> {
-///     entity_command_remove::<Shield>(top_player.0)
+/// fn crown_top_player_pure(top_player: Res<TopPlayer>) -> EntityCommandTryInsert<Crown> {
+///     entity_command_try_insert(top_player.0, Crown)
 /// }
 ///
 /// /// Equivalent impure system.
-/// fn nerf_top_player_impure(top_player: Res<TopPlayer>, mut commands: Commands) {
-///     commands.entity(top_player.0).remove::<Shield>();
+/// fn crown_top_player_impure(top_player: Res<TopPlayer>, mut commands: Commands) {
+///     commands.entity(top_player.0).try_insert(Crown);
 /// }
 /// # use proptest::prelude::*;
 /// #
-/// # fn app_setup(component_table: Vec<Option<Shield>>, top_player_index: usize) -> App {
+/// # fn app_setup(entity_count: u8, top_player_index: usize, despawned: bool) -> App {
 /// #     let mut app = App::new();
 /// #
 /// #     let once_entity = app.world_mut().spawn_empty().id();
 /// #
-/// #     let entities = component_table
-/// #         .into_iter()
-/// #         .map(|shield| {
-/// #             let mut entity = app.world_mut().spawn_empty();
-/// #             if let Some(shield) = shield {
-/// #                 entity.insert(shield);
-/// #             }
-/// #
-/// #             entity.id()
-/// #         })
+/// #     let entities = (0..entity_count)
+/// #         .map(|_| app.world_mut().spawn_empty().id())
 /// #         .chain(std::iter::once(once_entity))
 /// #         .collect::<Vec<_>>();
 /// #
 /// #     let top_player = entities[top_player_index % entities.len()];
 /// #
+/// #     if despawned {
+/// #         app.world_mut().despawn(top_player);
+/// #     }
+/// #
 /// #     app.world_mut().insert_resource(TopPlayer(top_player));
 /// #
 /// #     app
 /// # }
 /// #
-/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Shield>)> {
-/// #     let mut query = world.query::<(Entity, Option<&Shield>)>();
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Crown>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Crown>)>();
 /// #     query.iter(world).collect()
 /// # }
 /// #
 /// # proptest! {
-/// #     fn main(component_table: Vec<Option<Shield>>, player_index: usize) {
-/// #         let mut pure_app = app_setup(component_table.clone(), player_index);
-/// #         pure_app.add_systems(Update, nerf_top_player_pure.pipe(affect));
+/// #     fn main(entity_count: u8, player_index: usize, despawned: bool) {
+/// #         let mut pure_app = app_setup(entity_count, player_index, despawned);
+/// #         pure_app.add_systems(Update, crown_top_player_pure.pipe(affect));
 /// #
-/// #         let mut impure_app = app_setup(component_table, player_index);
-/// #         impure_app.add_systems(Update, nerf_top_player_impure);
+/// #         let mut impure_app = app_setup(entity_count, player_index, despawned);
+/// #         impure_app.add_systems(Update, crown_top_player_impure);
 /// #
 /// #         for _ in 0..3 {
 /// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
@@ -312,114 +398,139 @@ where
 /// ```
 ///
 /// Not shown...
-/// - A single component is used in this example, but the removed type is a `Bundle`, so it can be
-/// a `Bundle` struct or tuple of components.
+/// - A single component is used in this example, but the inserted value is a `Bundle`, so it can
+/// be a `Bundle` struct or tuple of components.
 #[doc = include_str!("defer_command_note.md")]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct EntityCommandRemove<B>
+pub struct EntityCommandTryInsert<B>
 where
     B: Bundle,
 {
-    entity: Entity,
-    bundle: PhantomData<B>,
+    /// The entity to insert to.
+    pub entity: Entity,
+    /// The bundle to insert.
+    pub bundle: B,
 }
 
-impl<B> EntityCommandRemove<B>
+/// Construct a new [`EntityCommandTryInsert`] [`Effect`].
+pub fn entity_command_try_insert<B>(entity: Entity, bundle: B) -> EntityCommandTryInsert<B>
 where
     B: Bundle,
 {
-    /// Construct a new [`EntityCommandRemove`]
-    pub fn new(entity: Entity) -> Self {
-        EntityCommandRemove {
-            entity,
-            bundle: PhantomData,
-        }
-    }
+    EntityCommandTryInsert { entity, bundle }
 }
 
-/// Construct a new [`EntityCommandRemove`] [`Effect`].
-pub fn entity_command_remove<B>(entity: Entity) -> EntityCommandRemove<B>
+impl<B> Effect for EntityCommandTryInsert<B>
 where
     B: Bundle,
 {
-    EntityCommandRemove::new(entity)
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.entity(self.entity).try_insert(self.bundle);
+    }
 }
 
-impl<B> Effect for EntityCommandRemove<B>
+impl<B> WorldEffect for EntityCommandTryInsert<B>
 where
     B: Bundle,
 {
-    type MutParam = Commands<'static, 'static>;
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
 
-    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
-        param.entity(self.entity).remove::<B>();
+impl<B> RecordEffect for EntityCommandTryInsert<B>
+where
+    B: Bundle + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
     }
 }
 
-/// [`Effect`] that queues a command for despawning an `Entity`.
+impl<B> Coalesce for EntityCommandTryInsert<B>
+where
+    B: Bundle,
+{
+    fn coalesce(effects: &mut Vec<Self>) {
+        retain_last_by_key(effects, |effect| effect.entity);
+    }
+}
+
+/// [`Effect`] that queues a command for inserting the provided `Bundle` onto the `Entity`,
+/// without overwriting any components the entity already has.
 ///
-/// Can be constructed with [`entity_command_despawn`].
+/// Like [`EntityCommandTryInsert`], this does not panic or warn if the entity has been despawned
+/// by the time commands are applied.
+///
+/// Can be constructed with [`entity_command_insert_if_new`].
 ///
 /// # Example
-/// In this example, a system is written that despawns entites with 0 `Health`.
+/// In this example, a system is written that gives every `Projectile` a default `Damage`, unless
+/// it already has one.
 /// ```
 /// use bevy::prelude::*;
 /// use bevy_pipe_affect::prelude::*;
 ///
-/// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
+/// #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Component)]
 /// # #[derive(proptest_derive::Arbitrary)]
-/// struct Health {
-///     # #[proptest(strategy = "0..3 as u32")]
-///     value: u32,
-/// }
+/// struct Projectile;
+///
+/// #[derive(Debug, Copy, Clone, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Damage(u32);
 ///
 /// /// Pure system using effects.
-/// fn death_pure(query: Query<(Entity, &Health)>) -> Vec<EntityCommandDespawn> {
-///     query
+/// fn default_damage_pure(
+///     projectiles: Query<Entity, With<Projectile>>,
+/// ) -> Vec<EntityCommandInsertIfNew<Damage>> {
+///     projectiles
 ///         .iter()
-///         .filter(|(_, health)| health.value == 0)
-///         .map(|(entity, _)| entity_command_despawn(entity))
+///         .map(|projectile| entity_command_insert_if_new(projectile, Damage(10)))
 ///         .collect()
 /// }
 ///
 /// /// Equivalent impure system.
-/// fn death_impure(query: Query<(Entity, &Health)>, mut commands: Commands) {
-///     for (entity, health) in query.iter() {
-///         if health.value == 0 {
-///             commands.entity(entity).despawn();
-///         }
+/// fn default_damage_impure(projectiles: Query<Entity, With<Projectile>>, mut commands: Commands) {
+///     for projectile in projectiles.iter() {
+///         commands.entity(projectile).try_insert_if_new(Damage(10));
 ///     }
 /// }
 /// # use proptest::prelude::*;
 /// #
-/// # fn app_setup(component_table: Vec<Option<Health>>) -> App {
+/// # fn app_setup(component_table: Vec<(Option<Projectile>, Option<Damage>)>) -> App {
 /// #     let mut app = App::new();
 /// #
 /// #     component_table
 /// #         .into_iter()
-/// #         .for_each(|health| {
+/// #         .for_each(|(projectile, damage)| {
 /// #             let mut entity = app.world_mut().spawn_empty();
 /// #
-/// #             if let Some(health) = health {
-/// #                 entity.insert(health);
+/// #             if let Some(projectile) = projectile {
+/// #                 entity.insert(projectile);
+/// #             }
+/// #
+/// #             if let Some(damage) = damage {
+/// #                 entity.insert(damage);
 /// #             }
 /// #         });
 /// #
 /// #     app
 /// # }
 /// #
-/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Health>)> {
-/// #     let mut query = world.query::<(Entity, Option<&Health>)>();
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Projectile>, Option<&Damage>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Projectile>, Option<&Damage>)>();
 /// #     query.iter(world).collect()
 /// # }
 /// #
 /// # proptest! {
-/// #     fn main(component_table: Vec<Option<Health>>) {
+/// #     fn main(component_table: Vec<(Option<Projectile>, Option<Damage>)>) {
 /// #         let mut pure_app = app_setup(component_table.clone());
-/// #         pure_app.add_systems(Update, death_pure.pipe(affect));
+/// #         pure_app.add_systems(Update, default_damage_pure.pipe(affect));
 /// #
 /// #         let mut impure_app = app_setup(component_table);
-/// #         impure_app.add_systems(Update, death_impure);
+/// #         impure_app.add_systems(Update, default_damage_impure);
 /// #
 /// #         for _ in 0..3 {
 /// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
@@ -431,117 +542,1708 @@ where
 /// ```
 #[doc = include_str!("defer_command_note.md")]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub struct EntityCommandDespawn {
-    /// The entity to despawn.
+pub struct EntityCommandInsertIfNew<B>
+where
+    B: Bundle,
+{
+    /// The entity to insert to.
     pub entity: Entity,
+    /// The bundle to insert, skipping components the entity already has.
+    pub bundle: B,
 }
 
-/// Construct a new [`EntityCommandDespawn`] [`Effect`].
-pub fn entity_command_despawn(entity: Entity) -> EntityCommandDespawn {
-    EntityCommandDespawn { entity }
+/// Construct a new [`EntityCommandInsertIfNew`] [`Effect`].
+pub fn entity_command_insert_if_new<B>(entity: Entity, bundle: B) -> EntityCommandInsertIfNew<B>
+where
+    B: Bundle,
+{
+    EntityCommandInsertIfNew { entity, bundle }
 }
 
-impl Effect for EntityCommandDespawn {
+impl<B> Effect for EntityCommandInsertIfNew<B>
+where
+    B: Bundle,
+{
     type MutParam = Commands<'static, 'static>;
 
     fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
-        param.entity(self.entity).despawn();
+        param.entity(self.entity).try_insert_if_new(self.bundle);
     }
 }
 
-/// [`Effect`] that inserts a component/bundle recursively on an entity and its relationships.
+impl<B> WorldEffect for EntityCommandInsertIfNew<B>
+where
+    B: Bundle,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<B> RecordEffect for EntityCommandInsertIfNew<B>
+where
+    B: Bundle + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that queues a command for removing the `Bundle` from the `Entity`.
 ///
-/// Can be constructed with [`entity_command_insert_recursive`].
+/// Can be constructed with [`entity_command_remove`].
 ///
 /// # Example
-/// In this example, a system is written that marks entities and their childern as `Frozen` if
-/// their `Temperature` is below zero.
+/// In this example, a system is written that nerfs the `TopPlayer` by removing her `Shield`.
 /// ```
 /// use bevy::prelude::*;
 /// use bevy_pipe_affect::prelude::*;
 ///
-/// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
-/// # #[derive(proptest_derive::Arbitrary)]
-/// struct Temperature(i8);
+/// #[derive(Debug, Copy, Clone, PartialEq, Eq, Resource)]
+/// struct TopPlayer(Entity);
 ///
 /// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
 /// # #[derive(proptest_derive::Arbitrary)]
-/// struct Frozen;
+/// struct Shield;
 ///
 /// /// Pure system using effects.
-/// fn freeze_pure(
-///     query: Query<(Entity, &Temperature)>,
-/// ) -> Vec<EntityCommandInsertRecursive<Children, Frozen>> {
-///     query
-///         .iter()
-///         .filter(|(_, temp)| temp.0 <= 0)
-///         .map(|(entity, _)| entity_command_insert_recursive(entity, Frozen))
-///         .collect()
+/// fn nerf_top_player_pure(top_player: Res<TopPlayer>) -> EntityCommandRemove<Shield> {
+///     entity_command_remove::<Shield>(top_player.0)
 /// }
 ///
 /// /// Equivalent impure system.
-/// fn freeze_impure(query: Query<(Entity, &Temperature)>, mut commands: Commands) {
-///     for (entity, temp) in query.iter() {
-///         if temp.0 <= 0 {
-///             commands.entity(entity).insert_recursive::<Children>(Frozen);
-///         }
-///     }
+/// fn nerf_top_player_impure(top_player: Res<TopPlayer>, mut commands: Commands) {
+///     commands.entity(top_player.0).remove::<Shield>();
 /// }
 /// # use proptest::prelude::*;
 /// #
-/// # #[derive(Debug, Copy, Clone, PartialEq, Eq, proptest_derive::Arbitrary)]
-/// # struct ParentIndex(usize);
-/// #
-/// # fn app_setup(component_table: Vec<(Option<Temperature>, Option<ParentIndex>)>) -> App {
+/// # fn app_setup(component_table: Vec<Option<Shield>>, top_player_index: usize) -> App {
 /// #     let mut app = App::new();
 /// #
-/// #     let _entities = component_table.into_iter().fold(
-/// #         vec![app.world_mut().spawn_empty().id()],
-/// #         |mut entities, (temp, parent_index)| {
-/// #             let mut entity = app.world_mut().spawn_empty();
+/// #     let once_entity = app.world_mut().spawn_empty().id();
 /// #
-/// #             if let Some(temp) = temp {
-/// #                 entity.insert(temp);
+/// #     let entities = component_table
+/// #         .into_iter()
+/// #         .map(|shield| {
+/// #             let mut entity = app.world_mut().spawn_empty();
+/// #             if let Some(shield) = shield {
+/// #                 entity.insert(shield);
 /// #             }
 /// #
-/// #             if let Some(parent_index) = parent_index {
-/// #                 let parent = entities[parent_index.0 % entities.len()];
-/// #                 entity.insert(ChildOf(parent));
+/// #             entity.id()
+/// #         })
+/// #         .chain(std::iter::once(once_entity))
+/// #         .collect::<Vec<_>>();
+/// #
+/// #     let top_player = entities[top_player_index % entities.len()];
+/// #
+/// #     app.world_mut().insert_resource(TopPlayer(top_player));
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Shield>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Shield>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<Option<Shield>>, player_index: usize) {
+/// #         let mut pure_app = app_setup(component_table.clone(), player_index);
+/// #         pure_app.add_systems(Update, nerf_top_player_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table, player_index);
+/// #         impure_app.add_systems(Update, nerf_top_player_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - A single component is used in this example, but the removed type is a `Bundle`, so it can be
+/// a `Bundle` struct or tuple of components.
+#[doc = include_str!("defer_command_note.md")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EntityCommandRemove<B>
+where
+    B: Bundle,
+{
+    entity: Entity,
+    bundle: PhantomData<B>,
+}
+
+impl<B> EntityCommandRemove<B>
+where
+    B: Bundle,
+{
+    /// Construct a new [`EntityCommandRemove`]
+    pub fn new(entity: Entity) -> Self {
+        EntityCommandRemove {
+            entity,
+            bundle: PhantomData,
+        }
+    }
+}
+
+/// Construct a new [`EntityCommandRemove`] [`Effect`].
+pub fn entity_command_remove<B>(entity: Entity) -> EntityCommandRemove<B>
+where
+    B: Bundle,
+{
+    EntityCommandRemove::new(entity)
+}
+
+impl<B> Effect for EntityCommandRemove<B>
+where
+    B: Bundle,
+{
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.entity(self.entity).remove::<B>();
+    }
+}
+
+impl<B> WorldEffect for EntityCommandRemove<B>
+where
+    B: Bundle,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<B> RecordEffect for EntityCommandRemove<B>
+where
+    B: Bundle + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<B> InvertibleEffect for EntityCommandRemove<B>
+where
+    B: Bundle + BundleFromComponents,
+{
+    type Inverse = EntityCommandRestore<B>;
+
+    fn affect_invertible(
+        self,
+        param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>,
+        inverses: &mut Vec<Self::Inverse>,
+    ) {
+        let inverse = EntityCommandRestore::pending(self.entity);
+        let captured = inverse.captured.clone();
+
+        param
+            .entity(self.entity)
+            .queue(move |mut entity: EntityWorldMut| {
+                let prior = take_bundle::<B>(&mut entity)?;
+                set_captured(&captured, prior.into());
+
+                Ok::<_, PartialBundleError>(())
+            });
+
+        inverses.push(inverse);
+    }
+}
+
+/// [`Effect`] that queues a command for restoring the `Bundle` on an `Entity` to a captured state.
+///
+/// "Restoring" means taking any existing `B` from the entity, and then inserting the captured `B`
+/// if there is one. So, this can be used to reinsert a bundle or to remove it.
+///
+/// This is most commonly produced as the inverse of [`EntityCommandInsert`] and
+/// [`EntityCommandRemove`] when they are performed invertibly (see [`InvertibleEffect`]). Since
+/// commands are deferred, the state being overwritten is only captured once the command is
+/// applied. The captured state is shared with the inverse effect until then.
+///
+/// Bundles are captured whole. If the entity only has some of the components in `B`, its state
+/// can't be captured, so performing [`EntityCommandInsert`] or [`EntityCommandRemove`] invertibly
+/// leaves the entity unchanged instead, and produces a [`PartialBundleError`] (handled by `bevy`'s
+/// `FallbackErrorHandler`). The same goes for restoring onto an entity that only has some of the
+/// components in `B`. The inverse of an effect that left the entity unchanged does nothing.
+///
+/// Can be constructed with [`entity_command_restore`].
+///
+/// # Example
+/// In this example, a system is written that restores the `TopPlayer`'s `Shield`.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Debug, Copy, Clone, PartialEq, Eq, Resource)]
+/// struct TopPlayer(Entity);
+///
+/// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Shield;
+///
+/// /// Pure system using effects.
+/// fn restore_shield_pure(top_player: Res<TopPlayer>) -> EntityCommandRestore<Shield> {
+///     entity_command_restore(top_player.0, Some(Shield))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn restore_shield_impure(top_player: Res<TopPlayer>, mut commands: Commands) {
+///     commands.entity(top_player.0).remove::<Shield>().insert(Shield);
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(component_table: Vec<Option<Shield>>, top_player_index: usize) -> App {
+/// #     let mut app = App::new();
+/// #
+/// #     let once_entity = app.world_mut().spawn_empty().id();
+/// #
+/// #     let entities = component_table
+/// #         .into_iter()
+/// #         .map(|shield| {
+/// #             let mut entity = app.world_mut().spawn_empty();
+/// #             if let Some(shield) = shield {
+/// #                 entity.insert(shield);
+/// #             }
+/// #
+/// #             entity.id()
+/// #         })
+/// #         .chain(std::iter::once(once_entity))
+/// #         .collect::<Vec<_>>();
+/// #
+/// #     let top_player = entities[top_player_index % entities.len()];
+/// #
+/// #     app.world_mut().insert_resource(TopPlayer(top_player));
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Shield>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Shield>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<Option<Shield>>, player_index: usize) {
+/// #         let mut pure_app = app_setup(component_table.clone(), player_index);
+/// #         pure_app.add_systems(Update, restore_shield_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table, player_index);
+/// #         impure_app.add_systems(Update, restore_shield_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[doc = include_str!("defer_command_note.md")]
+#[derive(derive_more::Debug)]
+pub struct EntityCommandRestore<B>
+where
+    B: Bundle,
+{
+    /// The entity to restore the bundle on.
+    pub entity: Entity,
+    #[debug("{}", type_name::<Option<B>>())]
+    captured: Arc<Mutex<Captured<B>>>,
+}
+
+impl<B> EntityCommandRestore<B>
+where
+    B: Bundle,
+{
+    /// Construct a new [`EntityCommandRestore`]
+    pub fn new(entity: Entity, bundle: Option<B>) -> Self {
+        EntityCommandRestore {
+            entity,
+            captured: Arc::new(Mutex::new(bundle.into())),
+        }
+    }
+
+    /// Construct an inverse whose state is captured when its effect's command is applied.
+    ///
+    /// Until then, or if the command leaves the entity unchanged, restoring does nothing.
+    fn pending(entity: Entity) -> Self {
+        EntityCommandRestore {
+            entity,
+            captured: Arc::new(Mutex::new(Captured::Unchanged)),
+        }
+    }
+}
+
+/// Construct a new [`EntityCommandRestore`] [`Effect`].
+pub fn entity_command_restore<B>(entity: Entity, bundle: Option<B>) -> EntityCommandRestore<B>
+where
+    B: Bundle,
+{
+    EntityCommandRestore::new(entity, bundle)
+}
+
+/// State of a `Bundle` on an entity, as captured by an [`EntityCommandRestore`].
+#[derive(Default)]
+enum Captured<B> {
+    /// The entity had none of the components in `B`.
+    Absent,
+    /// The entity had all of the components in `B`.
+    Present(B),
+    /// The entity wasn't changed, so restoring it does nothing.
+    #[default]
+    Unchanged,
+}
+
+impl<B> From<Option<B>> for Captured<B> {
+    fn from(bundle: Option<B>) -> Self {
+        match bundle {
+            Some(bundle) => Captured::Present(bundle),
+            None => Captured::Absent,
+        }
+    }
+}
+
+/// Error produced when invertibly changing a `Bundle` on an entity that only has some of its
+/// components.
+///
+/// The entity is left unchanged, since the components it has couldn't be restored.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PartialBundleError {
+    /// The entity that only has some of the components in the bundle.
+    pub entity: Entity,
+    /// The type name of the bundle.
+    pub type_name: &'static str,
+}
+
+impl PartialBundleError {
+    /// Construct a new [`PartialBundleError`] for the bundle `B` on the given entity.
+    pub fn new<B>(entity: Entity) -> Self
+    where
+        B: Bundle,
+    {
+        PartialBundleError {
+            entity,
+            type_name: type_name::<B>(),
+        }
+    }
+}
+
+impl Display for PartialBundleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "entity {} only has some of the components in bundle {}",
+            self.entity, self.type_name
+        )
+    }
+}
+
+impl Error for PartialBundleError {}
+
+/// Takes `B` from the entity if it has all of its components.
+///
+/// Fails without changing the entity if it only has some of them.
+fn take_bundle<B>(entity: &mut EntityWorldMut) -> Result<Option<B>, PartialBundleError>
+where
+    B: Bundle + BundleFromComponents,
+{
+    if let Some(taken) = entity.take::<B>() {
+        return Ok(Some(taken));
+    }
+
+    let partially_present = B::get_component_ids(entity.world().components())
+        .flatten()
+        .any(|component_id| entity.contains_id(component_id));
+
+    if partially_present {
+        Err(PartialBundleError::new::<B>(entity.id()))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Restores `B` on the entity to the captured state, returning the state it replaced.
+fn restore_bundle<B>(
+    entity: &mut EntityWorldMut,
+    captured: Captured<B>,
+) -> Result<Captured<B>, PartialBundleError>
+where
+    B: Bundle + BundleFromComponents,
+{
+    let bundle = match captured {
+        Captured::Unchanged => return Ok(Captured::Unchanged),
+        Captured::Absent => None,
+        Captured::Present(bundle) => Some(bundle),
+    };
+
+    let prior = take_bundle::<B>(entity)?;
+
+    if let Some(bundle) = bundle {
+        entity.insert(bundle);
+    }
+
+    Ok(prior.into())
+}
+
+fn take_captured<B>(captured: &Mutex<Captured<B>>) -> Captured<B> {
+    std::mem::take(&mut *captured.lock().unwrap_or_else(PoisonError::into_inner))
+}
+
+fn set_captured<B>(captured: &Mutex<Captured<B>>, state: Captured<B>) {
+    *captured.lock().unwrap_or_else(PoisonError::into_inner) = state;
+}
+
+impl<B> Effect for EntityCommandRestore<B>
+where
+    B: Bundle + BundleFromComponents,
+{
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param
+            .entity(self.entity)
+            .queue(move |mut entity: EntityWorldMut| {
+                restore_bundle(&mut entity, take_captured(&self.captured)).map(drop)
+            });
+    }
+}
+
+impl<B> WorldEffect for EntityCommandRestore<B>
+where
+    B: Bundle + BundleFromComponents,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<B> RecordEffect for EntityCommandRestore<B>
+where
+    B: Bundle + BundleFromComponents,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<B> InvertibleEffect for EntityCommandRestore<B>
+where
+    B: Bundle + BundleFromComponents,
+{
+    type Inverse = EntityCommandRestore<B>;
+
+    fn affect_invertible(
+        self,
+        param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>,
+        inverses: &mut Vec<Self::Inverse>,
+    ) {
+        let inverse = EntityCommandRestore::pending(self.entity);
+        let captured = inverse.captured.clone();
+
+        param
+            .entity(self.entity)
+            .queue(move |mut entity: EntityWorldMut| {
+                let prior = restore_bundle(&mut entity, take_captured(&self.captured))?;
+                set_captured(&captured, prior);
+
+                Ok::<_, PartialBundleError>(())
+            });
+
+        inverses.push(inverse);
+    }
+}
+
+/// [`Effect`] that queues a command for despawning an `Entity`.
+///
+/// Can be constructed with [`entity_command_despawn`].
+///
+/// # Example
+/// In this example, a system is written that despawns entites with 0 `Health`.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Health {
+///     # #[proptest(strategy = "0..3 as u32")]
+///     value: u32,
+/// }
+///
+/// /// Pure system using effects.
+/// fn death_pure(query: Query<(Entity, &Health)>) -> Vec<EntityCommandDespawn> {
+///     query
+///         .iter()
+///         .filter(|(_, health)| health.value == 0)
+///         .map(|(entity, _)| entity_command_despawn(entity))
+///         .collect()
+/// }
+///
+/// /// Equivalent impure system.
+/// fn death_impure(query: Query<(Entity, &Health)>, mut commands: Commands) {
+///     for (entity, health) in query.iter() {
+///         if health.value == 0 {
+///             commands.entity(entity).despawn();
+///         }
+///     }
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(component_table: Vec<Option<Health>>) -> App {
+/// #     let mut app = App::new();
+/// #
+/// #     component_table
+/// #         .into_iter()
+/// #         .for_each(|health| {
+/// #             let mut entity = app.world_mut().spawn_empty();
+/// #
+/// #             if let Some(health) = health {
+/// #                 entity.insert(health);
+/// #             }
+/// #         });
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Health>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Health>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<Option<Health>>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, death_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table);
+/// #         impure_app.add_systems(Update, death_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[doc = include_str!("defer_command_note.md")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EntityCommandDespawn {
+    /// The entity to despawn.
+    pub entity: Entity,
+}
+
+/// Construct a new [`EntityCommandDespawn`] [`Effect`].
+pub fn entity_command_despawn(entity: Entity) -> EntityCommandDespawn {
+    EntityCommandDespawn { entity }
+}
+
+impl Effect for EntityCommandDespawn {
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.entity(self.entity).despawn();
+    }
+}
+
+impl WorldEffect for EntityCommandDespawn {
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl RecordEffect for EntityCommandDespawn {
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl Coalesce for EntityCommandDespawn {
+    fn coalesce(effects: &mut Vec<Self>) {
+        retain_last_by_key(effects, |effect| effect.entity);
+    }
+}
+
+impl<B> Coalesce for Either<EntityCommandInsert<B>, EntityCommandDespawn>
+where
+    B: Bundle,
+{
+    fn coalesce(effects: &mut Vec<Self>) {
+        let mut inserted = EntityHashSet::new();
+        let mut despawned = EntityHashSet::new();
+
+        let mut keep = effects
+            .iter()
+            .rev()
+            .map(|effect| match effect {
+                Either::Left(insert) => {
+                    !despawned.contains(&insert.entity) && inserted.insert(insert.entity)
+                }
+                Either::Right(despawn) => despawned.insert(despawn.entity),
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev();
+
+        effects.retain(|_| keep.next().unwrap_or(true));
+    }
+}
+
+/// [`Effect`] that queues a command for despawning an `Entity`, if it still exists when the
+/// command is applied.
+///
+/// Unlike [`EntityCommandDespawn`], this does not warn if the entity has already been despawned
+/// by the time commands are applied, e.g. by another system despawning the same entity.
+///
+/// Can be constructed with [`entity_command_try_despawn`].
+///
+/// # Example
+/// In this example, a system is written that despawns `Projectile`s that have run out of
+/// `Lifetime`, while another system may despawn them on collision in the same frame.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Lifetime {
+///     # #[proptest(strategy = "0..3 as u32")]
+///     frames: u32,
+/// }
+///
+/// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Collided;
+///
+/// /// Pure system using effects.
+/// fn expire_pure(query: Query<(Entity, &Lifetime)>) -> Vec<EntityCommandTryDespawn> {
+///     query
+///         .iter()
+///         .filter(|(_, lifetime)| lifetime.frames == 0)
+///         .map(|(entity, _)| entity_command_try_despawn(entity))
+///         .collect()
+/// }
+///
+/// /// Equivalent impure system.
+/// fn expire_impure(query: Query<(Entity, &Lifetime)>, mut commands: Commands) {
+///     for (entity, lifetime) in query.iter() {
+///         if lifetime.frames == 0 {
+///             commands.entity(entity).try_despawn();
+///         }
+///     }
+/// }
+///
+/// /// Another system that may despawn the same entities.
+/// fn collide(query: Query<Entity, With<Collided>>) -> Vec<EntityCommandTryDespawn> {
+///     query.iter().map(entity_command_try_despawn).collect()
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(component_table: Vec<(Option<Lifetime>, Option<Collided>)>) -> App {
+/// #     let mut app = App::new();
+/// #
+/// #     component_table
+/// #         .into_iter()
+/// #         .for_each(|(lifetime, collided)| {
+/// #             let mut entity = app.world_mut().spawn_empty();
+/// #
+/// #             if let Some(lifetime) = lifetime {
+/// #                 entity.insert(lifetime);
+/// #             }
+/// #
+/// #             if let Some(collided) = collided {
+/// #                 entity.insert(collided);
+/// #             }
+/// #         });
+/// #
+/// #     app.add_systems(Update, collide.pipe(affect));
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Lifetime>, Option<&Collided>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Lifetime>, Option<&Collided>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<(Option<Lifetime>, Option<Collided>)>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, expire_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table);
+/// #         impure_app.add_systems(Update, expire_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[doc = include_str!("defer_command_note.md")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EntityCommandTryDespawn {
+    /// The entity to despawn.
+    pub entity: Entity,
+}
+
+/// Construct a new [`EntityCommandTryDespawn`] [`Effect`].
+pub fn entity_command_try_despawn(entity: Entity) -> EntityCommandTryDespawn {
+    EntityCommandTryDespawn { entity }
+}
+
+impl Effect for EntityCommandTryDespawn {
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.entity(self.entity).try_despawn();
+    }
+}
+
+impl WorldEffect for EntityCommandTryDespawn {
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl RecordEffect for EntityCommandTryDespawn {
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl Coalesce for EntityCommandTryDespawn {
+    fn coalesce(effects: &mut Vec<Self>) {
+        retain_last_by_key(effects, |effect| effect.entity);
+    }
+}
+
+/// [`Effect`] that inserts a component/bundle recursively on an entity and its relationships.
+///
+/// Can be constructed with [`entity_command_insert_recursive`].
+///
+/// # Example
+/// In this example, a system is written that marks entities and their childern as `Frozen` if
+/// their `Temperature` is below zero.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Temperature(i8);
+///
+/// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Frozen;
+///
+/// /// Pure system using effects.
+/// fn freeze_pure(
+///     query: Query<(Entity, &Temperature)>,
+/// ) -> Vec<EntityCommandInsertRecursive<Children, Frozen>> {
+///     query
+///         .iter()
+///         .filter(|(_, temp)| temp.0 <= 0)
+///         .map(|(entity, _)| entity_command_insert_recursive(entity, Frozen))
+///         .collect()
+/// }
+///
+/// /// Equivalent impure system.
+/// fn freeze_impure(query: Query<(Entity, &Temperature)>, mut commands: Commands) {
+///     for (entity, temp) in query.iter() {
+///         if temp.0 <= 0 {
+///             commands.entity(entity).insert_recursive::<Children>(Frozen);
+///         }
+///     }
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # #[derive(Debug, Copy, Clone, PartialEq, Eq, proptest_derive::Arbitrary)]
+/// # struct ParentIndex(usize);
+/// #
+/// # fn app_setup(component_table: Vec<(Option<Temperature>, Option<ParentIndex>)>) -> App {
+/// #     let mut app = App::new();
+/// #
+/// #     let _entities = component_table.into_iter().fold(
+/// #         vec![app.world_mut().spawn_empty().id()],
+/// #         |mut entities, (temp, parent_index)| {
+/// #             let mut entity = app.world_mut().spawn_empty();
+/// #
+/// #             if let Some(temp) = temp {
+/// #                 entity.insert(temp);
+/// #             }
+/// #
+/// #             if let Some(parent_index) = parent_index {
+/// #                 let parent = entities[parent_index.0 % entities.len()];
+/// #                 entity.insert(ChildOf(parent));
+/// #             }
+/// #
+/// #             entities.push(entity.id());
+/// #
+/// #             entities
+/// #         },
+/// #     );
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(
+/// #     world: &mut World,
+/// # ) -> Vec<(
+/// #     Entity,
+/// #     Option<&Temperature>,
+/// #     Option<&ChildOf>,
+/// #     Option<&Frozen>,
+/// # )> {
+/// #     let mut query = world.query::<(
+/// #         Entity,
+/// #         Option<&Temperature>,
+/// #         Option<&ChildOf>,
+/// #         Option<&Frozen>,
+/// #     )>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<(Option<Temperature>, Option<ParentIndex>)>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, freeze_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table);
+/// #         impure_app.add_systems(Update, freeze_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - A single component is used in this example, but the inserted value is a `Bundle`, so it can
+/// be a `Bundle` struct or tuple of components.
+/// - A parent/child relationship is used in this example, but any other `RelationshipTarget` would
+/// work.
+#[doc = include_str!("defer_command_note.md")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Reflect)]
+pub struct EntityCommandInsertRecursive<RT, B>
+where
+    RT: RelationshipTarget,
+    B: Bundle + Clone,
+{
+    /// The entity that is inserted to recursively.
+    pub entity: Entity,
+    /// The bundle being inserted on relationship targets.
+    pub bundle: B,
+    relationship_target: PhantomData<RT>,
+}
+
+impl<RT, B> EntityCommandInsertRecursive<RT, B>
+where
+    B: Bundle + Clone,
+    RT: RelationshipTarget,
+{
+    /// Construct a new [`EntityCommandInsertRecursive`].
+    pub fn new(entity: Entity, bundle: B) -> Self {
+        Self {
+            entity,
+            bundle,
+            relationship_target: PhantomData,
+        }
+    }
+}
+
+/// Construct a new [`EntityCommandInsertRecursive`] [`Effect`].
+pub fn entity_command_insert_recursive<RT, B>(
+    entity: Entity,
+    bundle: B,
+) -> EntityCommandInsertRecursive<RT, B>
+where
+    RT: RelationshipTarget,
+    B: Bundle + Clone,
+{
+    EntityCommandInsertRecursive::new(entity, bundle)
+}
+
+impl<RT, B> Effect for EntityCommandInsertRecursive<RT, B>
+where
+    RT: RelationshipTarget,
+    B: Bundle + Clone,
+{
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param
+            .entity(self.entity)
+            .insert_recursive::<RT>(self.bundle);
+    }
+}
+
+impl<RT, B> WorldEffect for EntityCommandInsertRecursive<RT, B>
+where
+    RT: RelationshipTarget,
+    B: Bundle + Clone,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<RT, B> RecordEffect for EntityCommandInsertRecursive<RT, B>
+where
+    RT: RelationshipTarget + Debug,
+    B: Bundle + Clone + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that removes a component/bundle recursively from an entity and its relationships.
+///
+/// Can be constructed with [`entity_command_remove_recursive`].
+///
+/// # Example
+/// In this example, a system is written that removes the `Curse` from the `BlessedEntity` and its
+/// children.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Debug, Copy, Clone, PartialEq, Resource)]
+/// struct BlessedEntity(Entity);
+///
+/// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Curse;
+///
+/// /// Pure system using effects.
+/// fn bless_pure(blessed_entity: Res<BlessedEntity>) -> EntityCommandRemoveRecursive<Children, Curse> {
+///     entity_command_remove_recursive(blessed_entity.0)
+/// }
+///
+/// /// Equivalent impure system.
+/// fn bless_impure(blessed_entity: Res<BlessedEntity>, mut commands: Commands) {
+///     commands
+///         .entity(blessed_entity.0)
+///         .remove_recursive::<Children, Curse>();
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # #[derive(Debug, Copy, Clone, PartialEq, Eq, proptest_derive::Arbitrary)]
+/// # struct ParentIndex(usize);
+/// #
+/// # fn app_setup(
+/// #     component_table: Vec<(Option<Curse>, Option<ParentIndex>)>,
+/// #     cured_index: usize,
+/// # ) -> App {
+/// #     let mut app = App::new();
+/// #
+/// #     let entities = component_table.into_iter().fold(
+/// #         vec![app.world_mut().spawn_empty().id()],
+/// #         |mut entities, (curse, parent_index)| {
+/// #             let mut entity = app.world_mut().spawn_empty();
+/// #
+/// #             if let Some(curse) = curse {
+/// #                 entity.insert(curse);
+/// #             }
+/// #
+/// #             if let Some(parent_index) = parent_index {
+/// #                 let parent = entities[parent_index.0 % entities.len()];
+/// #                 entity.insert(ChildOf(parent));
+/// #             }
+/// #
+/// #             entities.push(entity.id());
+/// #
+/// #             entities
+/// #         },
+/// #     );
+/// #
+/// #     let cured_entity = entities[cured_index % entities.len()];
+/// #
+/// #     app.insert_resource(BlessedEntity(cured_entity));
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Curse>, Option<&ChildOf>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Curse>, Option<&ChildOf>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<(Option<Curse>, Option<ParentIndex>)>, cured_index: usize) {
+/// #         let mut pure_app = app_setup(component_table.clone(), cured_index);
+/// #         pure_app.add_systems(Update, bless_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table, cured_index);
+/// #         impure_app.add_systems(Update, bless_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - A single component is used in this example, but the removed type is a `Bundle`, so it can be
+/// a `Bundle` struct or tuple of components.
+/// - A parent/child relationship is used in this example, but any other `RelationshipTarget` would
+/// work.
+#[doc = include_str!("defer_command_note.md")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Reflect)]
+pub struct EntityCommandRemoveRecursive<RT, B>
+where
+    RT: RelationshipTarget,
+    B: Bundle,
+{
+    /// The entity that the bundle is removed from recursively.
+    pub entity: Entity,
+    bundle: PhantomData<B>,
+    relationship_target: PhantomData<RT>,
+}
+
+impl<RT, B> EntityCommandRemoveRecursive<RT, B>
+where
+    B: Bundle,
+    RT: RelationshipTarget,
+{
+    /// Construct a new [`EntityCommandRemoveRecursive`].
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            bundle: PhantomData,
+            relationship_target: PhantomData,
+        }
+    }
+}
+
+/// Construct a new [`EntityCommandRemoveRecursive`] [`Effect`].
+pub fn entity_command_remove_recursive<RT, B>(entity: Entity) -> EntityCommandRemoveRecursive<RT, B>
+where
+    RT: RelationshipTarget,
+    B: Bundle,
+{
+    EntityCommandRemoveRecursive::new(entity)
+}
+
+impl<RT, B> Effect for EntityCommandRemoveRecursive<RT, B>
+where
+    RT: RelationshipTarget,
+    B: Bundle,
+{
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.entity(self.entity).remove_recursive::<RT, B>();
+    }
+}
+
+impl<RT, B> WorldEffect for EntityCommandRemoveRecursive<RT, B>
+where
+    RT: RelationshipTarget,
+    B: Bundle,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<RT, B> RecordEffect for EntityCommandRemoveRecursive<RT, B>
+where
+    RT: RelationshipTarget + Debug,
+    B: Bundle + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that queues a command for triggering an `EntityEvent` targeting the given entity.
+///
+/// This is the effect equivalent of `EntityCommands::trigger`. The event should target the given
+/// entity, since observers are run for the event's own `event_target`. See [`CommandTrigger`] for
+/// events that aren't targeting an entity.
+///
+/// Can be constructed with [`entity_command_trigger`].
+///
+/// # Example
+/// In this example, a system is written that triggers an `Explode` event on bombs whose fuse has
+/// run out.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Fuse {
+///     # #[proptest(strategy = "0..3u8")]
+///     remaining: u8,
+/// }
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, EntityEvent)]
+/// struct Explode {
+///     entity: Entity,
+/// }
+///
+/// /// Pure system using effects.
+/// fn detonate_pure(query: Query<(Entity, &Fuse)>) -> Vec<EntityCommandTrigger<Explode>> {
+///     query
+///         .iter()
+///         .filter(|(_, fuse)| fuse.remaining == 0)
+///         .map(|(entity, _)| entity_command_trigger(entity, Explode { entity }))
+///         .collect()
+/// }
+///
+/// /// Equivalent impure system.
+/// fn detonate_impure(query: Query<(Entity, &Fuse)>, mut commands: Commands) {
+///     for (entity, fuse) in query.iter() {
+///         if fuse.remaining == 0 {
+///             commands.entity(entity).trigger(|entity| Explode { entity });
+///         }
+///     }
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn explode(explode: On<Explode>) -> EntityCommandDespawn {
+/// #     entity_command_despawn(explode.entity)
+/// # }
+/// #
+/// # fn app_setup(component_table: Vec<Option<Fuse>>) -> App {
+/// #     let mut app = App::new();
+/// #     app.world_mut().add_observer(explode.pipe(affect));
+/// #     component_table.into_iter().for_each(|fuse| {
+/// #         let mut entity = app.world_mut().spawn_empty();
+/// #         if let Some(fuse) = fuse {
+/// #             entity.insert(fuse);
+/// #         }
+/// #     });
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Fuse>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Fuse>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<Option<Fuse>>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, detonate_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table);
+/// #         impure_app.add_systems(Update, detonate_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// [`CommandTrigger`]: crate::effects::command::CommandTrigger
+#[doc = include_str!("defer_command_note.md")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EntityCommandTrigger<E>
+where
+    E: EntityEvent,
+    for<'a> E::Trigger<'a>: Default,
+{
+    /// The entity targeted by the event.
+    pub entity: Entity,
+    /// The event being triggered.
+    pub event: E,
+}
+
+/// Construct a new [`EntityCommandTrigger`] [`Effect`].
+pub fn entity_command_trigger<E>(entity: Entity, event: E) -> EntityCommandTrigger<E>
+where
+    E: EntityEvent,
+    for<'a> E::Trigger<'a>: Default,
+{
+    EntityCommandTrigger { entity, event }
+}
+
+impl<E> Effect for EntityCommandTrigger<E>
+where
+    E: EntityEvent,
+    for<'a> E::Trigger<'a>: Default,
+{
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.entity(self.entity).trigger(move |_| self.event);
+    }
+}
+
+impl<E> WorldEffect for EntityCommandTrigger<E>
+where
+    E: EntityEvent,
+    for<'a> E::Trigger<'a>: Default,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<E> RecordEffect for EntityCommandTrigger<E>
+where
+    E: EntityEvent + Debug,
+    for<'a> E::Trigger<'a>: Default,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that queues a command for attaching an `Observer` to an entity, watching for
+/// `EntityEvent`s targeting it.
+///
+/// This is the effect equivalent of `EntityCommands::observe`. See [`command_add_observer`] for
+/// observers that aren't attached to an entity.
+///
+/// Can be constructed with [`entity_command_observe`].
+///
+/// # Example
+/// In this example, a system is written that makes new `Bomb`s despawn when they `Explode`.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Bomb;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, EntityEvent)]
+/// struct Explode {
+///     entity: Entity,
+/// }
+///
+/// fn explode(explode: On<Explode>) -> EntityCommandDespawn {
+///     entity_command_despawn(explode.entity)
+/// }
+///
+/// /// Pure system using effects.
+/// fn arm_bombs_pure(bombs: Query<Entity, Added<Bomb>>) -> Vec<EntityCommandObserve> {
+///     bombs
+///         .iter()
+///         .map(|bomb| entity_command_observe(bomb, explode.pipe(affect)))
+///         .collect()
+/// }
+///
+/// /// Equivalent impure system.
+/// fn arm_bombs_impure(bombs: Query<Entity, Added<Bomb>>, mut commands: Commands) {
+///     for bomb in &bombs {
+///         commands.entity(bomb).observe(explode.pipe(affect));
+///     }
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn detonate(bombs: Query<Entity, With<Bomb>>, mut commands: Commands) {
+/// #     for bomb in &bombs {
+/// #         commands.trigger(Explode { entity: bomb });
+/// #     }
+/// # }
+/// #
+/// # fn app_setup(component_table: Vec<Option<Bomb>>) -> App {
+/// #     let mut app = App::new();
+/// #     app.add_systems(PostUpdate, detonate);
+/// #     component_table.into_iter().for_each(|bomb| {
+/// #         let mut entity = app.world_mut().spawn_empty();
+/// #         if let Some(bomb) = bomb {
+/// #             entity.insert(bomb);
+/// #         }
+/// #     });
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Bomb>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Bomb>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<Option<Bomb>>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, arm_bombs_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table);
+/// #         impure_app.add_systems(Update, arm_bombs_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// [`command_add_observer`]: crate::effects::command::command_add_observer
+#[doc = include_str!("defer_command_note.md")]
+#[derive(derive_more::Debug)]
+pub struct EntityCommandObserve {
+    /// The entity to observe.
+    pub entity: Entity,
+    /// The observer to attach to the entity.
+    #[debug("Observer")]
+    pub observer: Observer,
+}
+
+/// Construct a new [`EntityCommandObserve`] [`Effect`].
+pub fn entity_command_observe<E, B, M>(
+    entity: Entity,
+    observer: impl IntoObserverSystem<E, B, M>,
+) -> EntityCommandObserve
+where
+    E: EntityEvent,
+    B: Bundle,
+{
+    EntityCommandObserve {
+        entity,
+        observer: Observer::new(observer),
+    }
+}
+
+impl Effect for EntityCommandObserve {
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        let observer = self.observer.with_entity(self.entity);
+
+        param
+            .entity(self.entity)
+            .queue(move |entity: EntityWorldMut| {
+                entity.into_world_mut().spawn(observer);
+            });
+    }
+}
+
+impl WorldEffect for EntityCommandObserve {
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl RecordEffect for EntityCommandObserve {
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that supplies an existing entity to the provided effect-producing function to
+/// cause another effect, if the entity exists.
+///
+/// This is the existing-entity equivalent of [`CommandSpawnAnd`]. A single entity command is
+/// queued, which checks that the entity exists when it is applied, and then calls the function and
+/// performs the produced effect with exclusive `World` access (see [`WorldEffect`] for caveats).
+/// So, the produced effect is performed in full, in order, before any command queued after this
+/// one. If the entity doesn't exist by then, the function isn't called and an error is produced
+/// (handled by `bevy`'s `FallbackErrorHandler`).
+///
+/// Can be constructed with [`entity_command_and`].
+///
+/// # Example
+/// In this example, a system is written that gives the `TopPlayer` a `Crown`, and spawns a
+/// `Jewel` as a child of the top player.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Debug, Copy, Clone, PartialEq, Eq, Resource)]
+/// struct TopPlayer(Entity);
+///
+/// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
+/// struct Crown;
+///
+/// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
+/// struct Jewel;
+///
+/// /// Pure system using effects.
+/// fn crown_top_player_pure(
+///     top_player: Res<TopPlayer>,
+/// ) -> EntityCommandAnd<(EntityCommandInsert<Crown>, CommandSpawn<(Jewel, ChildOf)>)> {
+///     entity_command_and(top_player.0, |player| {
+///         (
+///             entity_command_insert(player, Crown),
+///             command_spawn((Jewel, ChildOf(player))),
+///         )
+///     })
+/// }
+///
+/// /// Equivalent impure system.
+/// fn crown_top_player_impure(top_player: Res<TopPlayer>, mut commands: Commands) {
+///     commands
+///         .entity(top_player.0)
+///         .insert(Crown)
+///         .with_child(Jewel);
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(entity_count: u8, top_player_index: usize) -> App {
+/// #     let mut app = App::new();
+/// #
+/// #     let once_entity = app.world_mut().spawn_empty().id();
+/// #
+/// #     let entities = (0..entity_count)
+/// #         .map(|_| app.world_mut().spawn_empty().id())
+/// #         .chain(std::iter::once(once_entity))
+/// #         .collect::<Vec<_>>();
+/// #
+/// #     let top_player = entities[top_player_index % entities.len()];
+/// #
+/// #     app.world_mut().insert_resource(TopPlayer(top_player));
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(
+/// #     world: &mut World,
+/// # ) -> Vec<(Entity, Option<&Crown>, Option<&Jewel>, Option<&ChildOf>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Crown>, Option<&Jewel>, Option<&ChildOf>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(entity_count: u8, player_index: usize) {
+/// #         let mut pure_app = app_setup(entity_count, player_index);
+/// #         pure_app.add_systems(Update, crown_top_player_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(entity_count, player_index);
+/// #         impure_app.add_systems(Update, crown_top_player_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - In this example, a tuple of effects is produced, but any other effect could be produced.
+///
+/// [`CommandSpawnAnd`]: crate::effects::command::CommandSpawnAnd
+#[doc = include_str!("defer_command_note.md")]
+#[derive(derive_more::Debug)]
+pub struct EntityCommandAnd<E>
+where
+    E: WorldEffect + Send + 'static,
+{
+    /// The entity to supply to the function.
+    pub entity: Entity,
+    /// The `Entity -> Effect` function that may cause another effect.
+    #[debug("Entity -> {}", type_name::<E>())]
+    pub f: Box<dyn FnOnce(Entity) -> E + Send>,
+}
+
+/// Construct a new [`EntityCommandAnd`] [`Effect`], with an extra effect using the `Entity`.
+pub fn entity_command_and<F, E>(entity: Entity, f: F) -> EntityCommandAnd<E>
+where
+    F: FnOnce(Entity) -> E + Send + 'static,
+    E: WorldEffect + Send + 'static,
+{
+    EntityCommandAnd {
+        entity,
+        f: Box::new(f),
+    }
+}
+
+impl<E> Effect for EntityCommandAnd<E>
+where
+    E: WorldEffect + Send + 'static,
+{
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        let f = self.f;
+
+        param
+            .entity(self.entity)
+            .queue(move |entity: EntityWorldMut| {
+                let id = entity.id();
+
+                f(id).affect_world(entity.into_world_mut());
+            });
+    }
+}
+
+impl<E> WorldEffect for EntityCommandAnd<E>
+where
+    E: WorldEffect + Send + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<E> RecordEffect for EntityCommandAnd<E>
+where
+    E: RecordEffect + WorldEffect + Send + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        let record = EffectRecord::new(&self);
+
+        recorder.push_and(record, |recorder| (self.f)(self.entity).record(recorder));
+    }
+}
+
+/// Component filter used by [`EntityCommandCloneAnd`] to decide which components are cloned.
+///
+/// Implemented by [`CloneAll`], [`CloneAllow`] and [`CloneDeny`].
+pub trait CloneFilter: Send + Sync + 'static {
+    /// Queue a command for cloning the entity onto a newly spawned entity using this filter.
+    ///
+    /// Returns the `Entity` of the clone.
+    fn clone_and_spawn(entity_commands: &mut EntityCommands) -> Entity;
+}
+
+/// [`CloneFilter`] that clones all components of the entity.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CloneAll;
+
+impl CloneFilter for CloneAll {
+    fn clone_and_spawn(entity_commands: &mut EntityCommands) -> Entity {
+        entity_commands.clone_and_spawn().id()
+    }
+}
+
+/// [`CloneFilter`] that only clones the components of the `Bundle` `B`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CloneAllow<B>(PhantomData<fn() -> B>)
+where
+    B: Bundle;
+
+impl<B> CloneFilter for CloneAllow<B>
+where
+    B: Bundle,
+{
+    fn clone_and_spawn(entity_commands: &mut EntityCommands) -> Entity {
+        entity_commands
+            .clone_and_spawn_with_opt_in(|builder| {
+                builder.allow::<B>();
+            })
+            .id()
+    }
+}
+
+/// [`CloneFilter`] that clones all components except those of the `Bundle` `B`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CloneDeny<B>(PhantomData<fn() -> B>)
+where
+    B: Bundle;
+
+impl<B> CloneFilter for CloneDeny<B>
+where
+    B: Bundle,
+{
+    fn clone_and_spawn(entity_commands: &mut EntityCommands) -> Entity {
+        entity_commands
+            .clone_and_spawn_with_opt_out(|builder| {
+                builder.deny::<B>();
+            })
+            .id()
+    }
+}
+
+/// [`Effect`] that queues a command for cloning an entity onto a newly spawned entity, then
+/// supplies the clone's entity id to the provided effect-producing function to cause another
+/// effect.
+///
+/// The cloned components can be filtered with the `Filter` generic (see [`CloneFilter`]).
+///
+/// Can be constructed with [`entity_command_clone_and`].
+///
+/// # Example
+/// In this example, a system is written that duplicates the `Selected` entity, without cloning
+/// its `Selected` marker, and marks the clone as a `Duplicate`.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// struct Selected;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// struct Duplicate;
+///
+/// /// Pure system using effects.
+/// fn duplicate_selected_pure(
+///     selected: Single<Entity, With<Selected>>,
+/// ) -> EntityCommandCloneAnd<EntityCommandInsert<Duplicate>, CloneDeny<Selected>> {
+///     entity_command_clone_and(*selected, |clone| entity_command_insert(clone, Duplicate))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn duplicate_selected_impure(selected: Single<Entity, With<Selected>>, mut commands: Commands) {
+///     commands
+///         .entity(*selected)
+///         .clone_and_spawn_with_opt_out(|builder| {
+///             builder.deny::<Selected>();
+///         })
+///         .insert(Duplicate);
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component, proptest_derive::Arbitrary)]
+/// # struct Prefab(u8);
+/// #
+/// # fn app_setup(component_table: Vec<Option<Prefab>>, selected_index: usize) -> App {
+/// #     let mut app = App::new();
+/// #
+/// #     let once_entity = app.world_mut().spawn_empty().id();
+/// #
+/// #     let entities = component_table
+/// #         .into_iter()
+/// #         .map(|prefab| {
+/// #             let mut entity = app.world_mut().spawn_empty();
+/// #             if let Some(prefab) = prefab {
+/// #                 entity.insert(prefab);
 /// #             }
 /// #
-/// #             entities.push(entity.id());
+/// #             entity.id()
+/// #         })
+/// #         .chain(std::iter::once(once_entity))
+/// #         .collect::<Vec<_>>();
 /// #
-/// #             entities
-/// #         },
-/// #     );
+/// #     let selected = entities[selected_index % entities.len()];
+/// #
+/// #     app.world_mut().entity_mut(selected).insert(Selected);
 /// #
 /// #     app
 /// # }
 /// #
 /// # fn test_state(
 /// #     world: &mut World,
-/// # ) -> Vec<(
-/// #     Entity,
-/// #     Option<&Temperature>,
-/// #     Option<&ChildOf>,
-/// #     Option<&Frozen>,
-/// # )> {
-/// #     let mut query = world.query::<(
-/// #         Entity,
-/// #         Option<&Temperature>,
-/// #         Option<&ChildOf>,
-/// #         Option<&Frozen>,
-/// #     )>();
+/// # ) -> Vec<(Entity, Option<&Prefab>, Option<&Selected>, Option<&Duplicate>)> {
+/// #     let mut query =
+/// #         world.query::<(Entity, Option<&Prefab>, Option<&Selected>, Option<&Duplicate>)>();
 /// #     query.iter(world).collect()
 /// # }
 /// #
 /// # proptest! {
-/// #     fn main(component_table: Vec<(Option<Temperature>, Option<ParentIndex>)>) {
-/// #         let mut pure_app = app_setup(component_table.clone());
-/// #         pure_app.add_systems(Update, freeze_pure.pipe(affect));
+/// #     fn main(component_table: Vec<Option<Prefab>>, selected_index: usize) {
+/// #         let mut pure_app = app_setup(component_table.clone(), selected_index);
+/// #         pure_app.add_systems(Update, duplicate_selected_pure.pipe(affect));
 /// #
-/// #         let mut impure_app = app_setup(component_table);
-/// #         impure_app.add_systems(Update, freeze_impure);
+/// #         let mut impure_app = app_setup(component_table.clone(), selected_index);
+/// #         impure_app.add_systems(Update, duplicate_selected_impure);
 /// #
 /// #         for _ in 0..3 {
 /// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
@@ -553,144 +2255,170 @@ impl Effect for EntityCommandDespawn {
 /// ```
 ///
 /// Not shown...
-/// - A single component is used in this example, but the inserted value is a `Bundle`, so it can
-/// be a `Bundle` struct or tuple of components.
-/// - A parent/child relationship is used in this example, but any other `RelationshipTarget` would
-/// work.
+/// - the `Filter` parameter can be omitted to clone all components
+/// - [`CloneAllow`] can be used to only clone specific components
 #[doc = include_str!("defer_command_note.md")]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Reflect)]
-pub struct EntityCommandInsertRecursive<RT, B>
+#[derive(derive_more::Debug)]
+pub struct EntityCommandCloneAnd<E, Filter = CloneAll>
 where
-    RT: RelationshipTarget,
-    B: Bundle + Clone,
+    E: Effect,
+    Filter: CloneFilter,
 {
-    /// The entity that is inserted to recursively.
+    /// The entity to clone.
     pub entity: Entity,
-    /// The bundle being inserted on relationship targets.
-    pub bundle: B,
-    relationship_target: PhantomData<RT>,
+    /// The `Entity -> Effect` function that may cause another effect using the clone.
+    #[debug("Entity -> {}", type_name::<E>())]
+    pub f: Box<dyn FnOnce(Entity) -> E>,
+    /// The [`CloneFilter`] deciding which components are cloned.
+    #[debug("{}", type_name::<Filter>())]
+    pub filter: PhantomData<Filter>,
 }
 
-impl<RT, B> EntityCommandInsertRecursive<RT, B>
+/// Construct a new [`EntityCommandCloneAnd`] [`Effect`], with an extra effect using the clone's
+/// `Entity`.
+pub fn entity_command_clone_and<E, Filter, F>(
+    entity: Entity,
+    f: F,
+) -> EntityCommandCloneAnd<E, Filter>
 where
-    B: Bundle + Clone,
-    RT: RelationshipTarget,
+    E: Effect,
+    Filter: CloneFilter,
+    F: FnOnce(Entity) -> E + 'static,
 {
-    /// Construct a new [`EntityCommandInsertRecursive`].
-    pub fn new(entity: Entity, bundle: B) -> Self {
-        Self {
-            entity,
-            bundle,
-            relationship_target: PhantomData,
-        }
+    EntityCommandCloneAnd {
+        entity,
+        f: Box::new(f),
+        filter: PhantomData,
     }
 }
 
-/// Construct a new [`EntityCommandInsertRecursive`] [`Effect`].
-pub fn entity_command_insert_recursive<RT, B>(
-    entity: Entity,
-    bundle: B,
-) -> EntityCommandInsertRecursive<RT, B>
+impl<E, Filter> Effect for EntityCommandCloneAnd<E, Filter>
 where
-    RT: RelationshipTarget,
-    B: Bundle + Clone,
+    E: Effect,
+    Filter: CloneFilter,
 {
-    EntityCommandInsertRecursive::new(entity, bundle)
+    type MutParam = (Commands<'static, 'static>, E::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        let clone = Filter::clone_and_spawn(&mut param.0.entity(self.entity));
+
+        (self.f)(clone).affect(&mut param.1);
+    }
 }
 
-impl<RT, B> Effect for EntityCommandInsertRecursive<RT, B>
+impl<E, Filter> WorldEffect for EntityCommandCloneAnd<E, Filter>
 where
-    RT: RelationshipTarget,
-    B: Bundle + Clone,
+    E: Effect,
+    E::MutParam: 'static,
+    Filter: CloneFilter,
 {
-    type MutParam = Commands<'static, 'static>;
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
 
-    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
-        param
-            .entity(self.entity)
-            .insert_recursive::<RT>(self.bundle);
+impl<E, Filter> RecordEffect for EntityCommandCloneAnd<E, Filter>
+where
+    E: RecordEffect,
+    Filter: CloneFilter,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        let record = EffectRecord::new(&self);
+        let clone = recorder.placeholder_entity();
+
+        recorder.push_and(record, |recorder| (self.f)(clone).record(recorder));
     }
 }
 
-/// [`Effect`] that removes a component/bundle recursively from an entity and its relationships.
+/// [`Effect`] that queues a command for cloning the components of the `Bundle` from one `Entity`
+/// onto another.
 ///
-/// Can be constructed with [`entity_command_remove_recursive`].
+/// Can be constructed with [`entity_command_clone_components`].
 ///
 /// # Example
-/// In this example, a system is written that removes the `Curse` from the `BlessedEntity` and its
-/// children.
+/// In this example, a system is written that copies the `Appearance` of the `Template` entity
+/// onto all `Npc`s.
 /// ```
 /// use bevy::prelude::*;
 /// use bevy_pipe_affect::prelude::*;
 ///
-/// #[derive(Debug, Copy, Clone, PartialEq, Resource)]
-/// struct BlessedEntity(Entity);
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// struct Template;
 ///
-/// #[derive(Debug, Default, Copy, Clone, PartialEq, Component)]
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
 /// # #[derive(proptest_derive::Arbitrary)]
-/// struct Curse;
+/// struct Npc;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Appearance(u8);
 ///
 /// /// Pure system using effects.
-/// fn bless_pure(blessed_entity: Res<BlessedEntity>) -> EntityCommandRemoveRecursive<Children, Curse> {
-///     entity_command_remove_recursive(blessed_entity.0)
+/// fn copy_appearance_pure(
+///     template: Single<Entity, With<Template>>,
+///     npcs: Query<Entity, (With<Npc>, Without<Template>)>,
+/// ) -> Vec<EntityCommandCloneComponents<Appearance>> {
+///     npcs.iter()
+///         .map(|npc| entity_command_clone_components::<Appearance>(*template, npc))
+///         .collect()
 /// }
 ///
 /// /// Equivalent impure system.
-/// fn bless_impure(blessed_entity: Res<BlessedEntity>, mut commands: Commands) {
-///     commands
-///         .entity(blessed_entity.0)
-///         .remove_recursive::<Children, Curse>();
+/// fn copy_appearance_impure(
+///     template: Single<Entity, With<Template>>,
+///     npcs: Query<Entity, (With<Npc>, Without<Template>)>,
+///     mut commands: Commands,
+/// ) {
+///     for npc in npcs.iter() {
+///         commands.entity(*template).clone_components::<Appearance>(npc);
+///     }
 /// }
 /// # use proptest::prelude::*;
 /// #
-/// # #[derive(Debug, Copy, Clone, PartialEq, Eq, proptest_derive::Arbitrary)]
-/// # struct ParentIndex(usize);
-/// #
-/// # fn app_setup(
-/// #     component_table: Vec<(Option<Curse>, Option<ParentIndex>)>,
-/// #     cured_index: usize,
-/// # ) -> App {
+/// # fn app_setup(component_table: Vec<(Option<Npc>, Option<Appearance>)>, template_index: usize) -> App {
 /// #     let mut app = App::new();
 /// #
-/// #     let entities = component_table.into_iter().fold(
-/// #         vec![app.world_mut().spawn_empty().id()],
-/// #         |mut entities, (curse, parent_index)| {
-/// #             let mut entity = app.world_mut().spawn_empty();
+/// #     let once_entity = app.world_mut().spawn(Appearance(0)).id();
 /// #
-/// #             if let Some(curse) = curse {
-/// #                 entity.insert(curse);
+/// #     let entities = component_table
+/// #         .into_iter()
+/// #         .map(|(npc, appearance)| {
+/// #             let mut entity = app.world_mut().spawn_empty();
+/// #             if let Some(npc) = npc {
+/// #                 entity.insert(npc);
 /// #             }
 /// #
-/// #             if let Some(parent_index) = parent_index {
-/// #                 let parent = entities[parent_index.0 % entities.len()];
-/// #                 entity.insert(ChildOf(parent));
+/// #             if let Some(appearance) = appearance {
+/// #                 entity.insert(appearance);
 /// #             }
 /// #
-/// #             entities.push(entity.id());
-/// #
-/// #             entities
-/// #         },
-/// #     );
+/// #             entity.id()
+/// #         })
+/// #         .chain(std::iter::once(once_entity))
+/// #         .collect::<Vec<_>>();
 /// #
-/// #     let cured_entity = entities[cured_index % entities.len()];
+/// #     let template = entities[template_index % entities.len()];
 /// #
-/// #     app.insert_resource(BlessedEntity(cured_entity));
+/// #     app.world_mut().entity_mut(template).insert(Template);
 /// #
 /// #     app
 /// # }
 /// #
-/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Curse>, Option<&ChildOf>)> {
-/// #     let mut query = world.query::<(Entity, Option<&Curse>, Option<&ChildOf>)>();
+/// # fn test_state(
+/// #     world: &mut World,
+/// # ) -> Vec<(Entity, Option<&Template>, Option<&Npc>, Option<&Appearance>)> {
+/// #     let mut query =
+/// #         world.query::<(Entity, Option<&Template>, Option<&Npc>, Option<&Appearance>)>();
 /// #     query.iter(world).collect()
 /// # }
 /// #
 /// # proptest! {
-/// #     fn main(component_table: Vec<(Option<Curse>, Option<ParentIndex>)>, cured_index: usize) {
-/// #         let mut pure_app = app_setup(component_table.clone(), cured_index);
-/// #         pure_app.add_systems(Update, bless_pure.pipe(affect));
+/// #     fn main(component_table: Vec<(Option<Npc>, Option<Appearance>)>, template_index: usize) {
+/// #         let mut pure_app = app_setup(component_table.clone(), template_index);
+/// #         pure_app.add_systems(Update, copy_appearance_pure.pipe(affect));
 /// #
-/// #         let mut impure_app = app_setup(component_table, cured_index);
-/// #         impure_app.add_systems(Update, bless_impure);
+/// #         let mut impure_app = app_setup(component_table.clone(), template_index);
+/// #         impure_app.add_systems(Update, copy_appearance_impure);
 /// #
 /// #         for _ in 0..3 {
 /// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
@@ -702,67 +2430,79 @@ where
 /// ```
 ///
 /// Not shown...
-/// - A single component is used in this example, but the removed type is a `Bundle`, so it can be
-/// a `Bundle` struct or tuple of components.
-/// - A parent/child relationship is used in this example, but any other `RelationshipTarget` would
-/// work.
+/// - A single component is used in this example, but the cloned components are a `Bundle`, so it
+/// can be a `Bundle` struct or tuple of components.
 #[doc = include_str!("defer_command_note.md")]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Reflect)]
-pub struct EntityCommandRemoveRecursive<RT, B>
+#[derive(Copy, Clone, PartialEq, Eq, derive_more::Debug)]
+pub struct EntityCommandCloneComponents<B>
 where
-    RT: RelationshipTarget,
     B: Bundle,
 {
-    /// The entity that the bundle is removed from recursively.
-    pub entity: Entity,
-    bundle: PhantomData<B>,
-    relationship_target: PhantomData<RT>,
+    /// The entity to clone the components from.
+    pub source: Entity,
+    /// The entity to clone the components onto.
+    pub target: Entity,
+    /// The `Bundle` of components to clone.
+    #[debug("{}", type_name::<B>())]
+    pub bundle: PhantomData<fn() -> B>,
 }
 
-impl<RT, B> EntityCommandRemoveRecursive<RT, B>
+/// Construct a new [`EntityCommandCloneComponents`] [`Effect`].
+pub fn entity_command_clone_components<B>(
+    source: Entity,
+    target: Entity,
+) -> EntityCommandCloneComponents<B>
 where
     B: Bundle,
-    RT: RelationshipTarget,
 {
-    /// Construct a new [`EntityCommandRemoveRecursive`].
-    pub fn new(entity: Entity) -> Self {
-        Self {
-            entity,
-            bundle: PhantomData,
-            relationship_target: PhantomData,
-        }
+    EntityCommandCloneComponents {
+        source,
+        target,
+        bundle: PhantomData,
     }
 }
 
-/// Construct a new [`EntityCommandRemoveRecursive`] [`Effect`].
-pub fn entity_command_remove_recursive<RT, B>(entity: Entity) -> EntityCommandRemoveRecursive<RT, B>
+impl<B> Effect for EntityCommandCloneComponents<B>
 where
-    RT: RelationshipTarget,
     B: Bundle,
 {
-    EntityCommandRemoveRecursive::new(entity)
+    type MutParam = Commands<'static, 'static>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.entity(self.source).clone_components::<B>(self.target);
+    }
 }
 
-impl<RT, B> Effect for EntityCommandRemoveRecursive<RT, B>
+impl<B> WorldEffect for EntityCommandCloneComponents<B>
 where
-    RT: RelationshipTarget,
     B: Bundle,
 {
-    type MutParam = Commands<'static, 'static>;
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
 
-    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
-        param.entity(self.entity).remove_recursive::<RT, B>();
+impl<B> RecordEffect for EntityCommandCloneComponents<B>
+where
+    B: Bundle,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use bevy::ecs::error::{ErrorContext, FallbackErrorHandler};
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::ecs::system::entity_command::EntityCommandError;
     use proptest::prelude::*;
 
     use super::*;
-    use crate::effects::command::{command_insert_resource, command_spawn_and};
+    use crate::effects::command::{command_insert_resource, command_spawn, command_spawn_and};
     use crate::effects::number_data::NumberComponent;
-    use crate::prelude::affect;
+    use crate::prelude::{affect, affect_undoable, redo, undo};
+    use crate::undo::UndoStack;
 
     proptest! {
         #[test]
@@ -918,6 +2658,85 @@ mod tests {
             assert_eq!(app.world().entity(child_entity_b).get::<NumberComponent<0>>(), None);
             assert_eq!(app.world().entity(child_entity_b).get::<NumberComponent<1>>(), None);
         }
+
+        #[test]
+        fn entity_command_insert_can_be_undone_and_redone(initial in any::<Option<NumberComponent<0>>>(), component in any::<NumberComponent<0>>()) {
+            let mut app = App::new();
+
+            let entity = app.world_mut().spawn(()).id();
+            if let Some(initial) = initial {
+                app.world_mut().entity_mut(entity).insert(initial);
+            }
+
+            app.init_resource::<UndoStack<EntityCommandRestore<NumberComponent<0>>>>();
+
+            app.world_mut()
+                .run_system_once((move || entity_command_insert(entity, component)).pipe(affect_undoable))
+                .unwrap();
+
+            prop_assert_eq!(app.world().entity(entity).get::<NumberComponent<0>>(), Some(&component));
+
+            app.world_mut()
+                .run_system_once(undo::<EntityCommandRestore<NumberComponent<0>>>)
+                .unwrap();
+
+            prop_assert_eq!(app.world().entity(entity).get::<NumberComponent<0>>(), initial.as_ref());
+
+            app.world_mut()
+                .run_system_once(redo::<EntityCommandRestore<NumberComponent<0>>>)
+                .unwrap();
+
+            prop_assert_eq!(app.world().entity(entity).get::<NumberComponent<0>>(), Some(&component));
+        }
+
+        #[test]
+        fn entity_command_insert_and_remove_leave_partially_present_bundle_through_undo_and_redo(initial: NumberComponent<0>, bundle: (NumberComponent<0>, NumberComponent<1>)) {
+            type PartialBundle = (NumberComponent<0>, NumberComponent<1>);
+
+            static HANDLED: Mutex<Vec<PartialBundleError>> = Mutex::new(Vec::new());
+
+            fn record_partial_bundle(error: BevyError, _: ErrorContext) {
+                if let Some(EntityCommandError::CommandFailed(error)) =
+                    error.downcast_ref::<EntityCommandError<PartialBundleError>>()
+                {
+                    HANDLED.lock().unwrap().push(*error);
+                }
+            }
+
+            HANDLED.lock().unwrap().clear();
+
+            let mut app = App::new();
+            app.insert_resource(FallbackErrorHandler(record_partial_bundle))
+                .init_resource::<UndoStack<EntityCommandRestore<PartialBundle>>>();
+
+            let entity = app.world_mut().spawn(initial).id();
+
+            app.world_mut()
+                .run_system_once((move || entity_command_insert(entity, bundle)).pipe(affect_undoable))
+                .unwrap();
+            app.world_mut()
+                .run_system_once((move || entity_command_remove::<PartialBundle>(entity)).pipe(affect_undoable))
+                .unwrap();
+
+            for _ in 0..2 {
+                app.world_mut()
+                    .run_system_once(undo::<EntityCommandRestore<PartialBundle>>)
+                    .unwrap();
+            }
+
+            for _ in 0..2 {
+                app.world_mut()
+                    .run_system_once(redo::<EntityCommandRestore<PartialBundle>>)
+                    .unwrap();
+            }
+
+            prop_assert_eq!(app.world().entity(entity).get::<NumberComponent<0>>(), Some(&initial));
+            prop_assert_eq!(app.world().entity(entity).get::<NumberComponent<1>>(), None);
+            prop_assert_eq!(
+                HANDLED.lock().unwrap().clone(),
+                vec![PartialBundleError::new::<PartialBundle>(entity); 2]
+            );
+        }
     }
 
     #[test]
@@ -950,4 +2769,176 @@ mod tests {
 
         assert!(app.world().get_entity(entity).is_err());
     }
+
+    #[test]
+    fn try_entity_commands_ignore_despawned_entities() {
+        let mut app = App::new();
+
+        let entity = app.world_mut().spawn(NumberComponent::<0>(0)).id();
+
+        app.add_systems(
+            Update,
+            (
+                (move || entity_command_despawn(entity)).pipe(affect),
+                (move || {
+                    (
+                        entity_command_try_insert(entity, NumberComponent::<1>(1)),
+                        entity_command_insert_if_new(entity, NumberComponent::<0>(2)),
+                        entity_command_try_despawn(entity),
+                    )
+                })
+                .pipe(affect),
+            )
+                .chain(),
+        );
+
+        app.update();
+
+        assert!(app.world().get_entity(entity).is_err());
+    }
+
+    #[test]
+    fn entity_command_and_chains_effects_on_existing_entity() {
+        let mut app = App::new();
+
+        let entity = app.world_mut().spawn(NumberComponent::<0>(0)).id();
+
+        app.world_mut()
+            .run_system_once(
+                (move || {
+                    entity_command_and(entity, |entity| {
+                        (
+                            entity_command_insert(entity, NumberComponent::<0>(1)),
+                            entity_command_insert_if_new(entity, NumberComponent::<0>(2)),
+                            entity_command_insert_if_new(entity, NumberComponent::<1>(3)),
+                        )
+                    })
+                })
+                .pipe(affect),
+            )
+            .unwrap();
+
+        assert_eq!(
+            app.world().get::<NumberComponent<0>>(entity),
+            Some(&NumberComponent(1))
+        );
+        assert_eq!(
+            app.world().get::<NumberComponent<1>>(entity),
+            Some(&NumberComponent(3))
+        );
+    }
+
+    /// The entity is despawned by a command queued before the `entity_command_and`, so the
+    /// continuation shouldn't run when commands are applied, even though the entity still exists
+    /// when the system runs.
+    #[test]
+    fn entity_command_and_checks_entity_exists_when_applied() {
+        let mut app = App::new();
+        app.insert_resource(FallbackErrorHandler(bevy::ecs::error::ignore));
+
+        let entity = app.world_mut().spawn(NumberComponent::<0>(0)).id();
+
+        app.world_mut()
+            .run_system_once(
+                (move || {
+                    (
+                        entity_command_despawn(entity),
+                        entity_command_and(entity, |_| command_spawn(NumberComponent::<1>(1))),
+                    )
+                })
+                .pipe(affect),
+            )
+            .unwrap();
+
+        assert!(app.world().get_entity(entity).is_err());
+        assert_eq!(
+            app.world_mut()
+                .query::<&NumberComponent<1>>()
+                .iter(app.world())
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn entity_command_clone_and_only_clones_allowed_components() {
+        #[derive(Resource)]
+        struct CloneHolder(Entity);
+
+        let mut app = App::new();
+
+        let entity = app
+            .world_mut()
+            .spawn((NumberComponent::<0>(0), NumberComponent::<1>(1)))
+            .id();
+
+        app.world_mut()
+            .run_system_once(
+                (move || -> EntityCommandCloneAnd<_, CloneAllow<NumberComponent<0>>> {
+                    entity_command_clone_and(entity, |clone| {
+                        command_insert_resource(CloneHolder(clone))
+                    })
+                })
+                .pipe(affect),
+            )
+            .unwrap();
+
+        let clone = app.world().resource::<CloneHolder>().0;
+
+        assert_ne!(clone, entity);
+        assert_eq!(
+            app.world().get::<NumberComponent<0>>(clone),
+            Some(&NumberComponent(0))
+        );
+        assert_eq!(app.world().get::<NumberComponent<1>>(clone), None);
+        assert_eq!(
+            app.world().get::<NumberComponent<1>>(entity),
+            Some(&NumberComponent(1))
+        );
+    }
+
+    /// Only `observed` has an observer, which inserts `NumberComponent::<0>(1)` when poked. After
+    /// both entities are poked, `observed` should have the component and `unobserved` should not.
+    #[test]
+    fn entity_command_trigger_only_runs_observers_of_target_entity() {
+        #[derive(EntityEvent)]
+        struct Poke {
+            entity: Entity,
+        }
+
+        let mut app = App::new();
+
+        let observed = app.world_mut().spawn_empty().id();
+        let unobserved = app.world_mut().spawn_empty().id();
+
+        app.add_systems(
+            Startup,
+            (move || {
+                entity_command_observe(
+                    observed,
+                    (|poke: On<Poke>| entity_command_insert(poke.entity, NumberComponent::<0>(1)))
+                        .pipe(affect),
+                )
+            })
+            .pipe(affect),
+        )
+        .add_systems(
+            Update,
+            (move || {
+                (
+                    entity_command_trigger(observed, Poke { entity: observed }),
+                    entity_command_trigger(unobserved, Poke { entity: unobserved }),
+                )
+            })
+            .pipe(affect),
+        );
+
+        app.update();
+
+        assert_eq!(
+            app.world().get::<NumberComponent<0>>(observed),
+            Some(&NumberComponent(1))
+        );
+        assert_eq!(app.world().get::<NumberComponent<0>>(unobserved), None);
+    }
 }
@@ -2,11 +2,19 @@
 //!
 //! On top of the types shown here, this implements [`Effect`] for...
 //! - `Result<T, E>` where `T: Effect` and `E: Into<BevyError>`
+//!
+//! `Result`s are also [`WorldEffect`]s when their `Ok` effects are.
 use bevy::ecs::error::{ErrorContext, FallbackErrorHandler};
 use bevy::ecs::system::{SystemChangeTick, SystemName};
+use std::any::type_name;
+
 use bevy::prelude::*;
 
-use crate::Effect;
+use crate::dry_run::{DryRun, DryRunEffect};
+use crate::effect_record::{EffectRecord, EffectRecorder, RecordEffect};
+use crate::undo::InvertibleEffect;
+use crate::world_effect::affect_world_error_context;
+use crate::{Effect, WorldEffect};
 
 /// [`Effect`] that causes the `Ok` effect, or handles the `Err` with a custom handler.
 ///
@@ -117,6 +125,218 @@ where
     }
 }
 
+/// Handles the `Err` case of a result performed as a [`WorldEffect`].
+///
+/// The error is attributed to the exclusive `affect_world` system performing the effect.
+fn handle_err_in_world<Er>(er: Er, handler: impl FnOnce(BevyError, ErrorContext), world: &World)
+where
+    Er: Into<BevyError>,
+{
+    handler(er.into(), affect_world_error_context(world));
+}
+
+impl<Ef, Er> WorldEffect for AffectOrHandle<Ef, Er>
+where
+    Ef: Effect + WorldEffect,
+    Er: Into<BevyError>,
+{
+    fn affect_world(self, world: &mut World) {
+        match self.result {
+            Ok(ef) => ef.affect_world(world),
+            Err(er) => handle_err_in_world(er, self.handler, world),
+        }
+    }
+}
+
+/// Records the `Err` case of a result as the error, since the error handler is never run.
+fn record_err<Er>(er: Er, recorder: &mut EffectRecorder)
+where
+    Er: Into<BevyError>,
+{
+    let bevy_error: BevyError = er.into();
+
+    recorder.push(EffectRecord {
+        type_name: type_name::<Er>(),
+        debug: format!("Err({bevy_error})"),
+        continuation: Vec::new(),
+    });
+}
+
+impl<Ef, Er> RecordEffect for AffectOrHandle<Ef, Er>
+where
+    Ef: RecordEffect,
+    Er: Into<BevyError>,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        match self.result {
+            Ok(ef) => ef.record(recorder),
+            Err(er) => record_err(er, recorder),
+        }
+    }
+}
+
+impl<Ef, Er> DryRunEffect for AffectOrHandle<Ef, Er>
+where
+    Ef: DryRunEffect,
+    Er: Into<BevyError>,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        if let Ok(ef) = self.result {
+            ef.dry_run(dry_run);
+        }
+    }
+}
+
+/// [`Effect`] that causes the `Ok` effect, or handles the `Err` with a custom handler, without
+/// boxing the handler.
+///
+/// This is the unboxed alternative to [`AffectOrHandle`]. It is generic over the handler type
+/// instead, so constructing it doesn't allocate and performing it doesn't use dynamic dispatch.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// fn zero_red_clear_color_srgba(clear_color: Res<ClearColor>) -> impl Effect + use<> {
+///     let result = match clear_color.0 {
+///         Color::Srgba(srgba) => {
+///             let color = Color::Srgba(Srgba { red: 0., ..srgba });
+///             Ok(ResSet {
+///                 value: ClearColor(color),
+///             })
+///         }
+///         _ => Err("color is not srgba"),
+///     };
+///
+///     affect_or_handle_fn(result, bevy::ecs::error::warn)
+/// }
+///
+/// bevy::ecs::system::assert_is_system(zero_red_clear_color_srgba.pipe(affect))
+/// ```
+///
+/// Can be constructed with [`affect_or_handle_fn`].
+#[derive(derive_more::Debug)]
+pub struct AffectOrHandleFn<Handler, Ef, Er>
+where
+    Handler: FnOnce(BevyError, ErrorContext),
+    Ef: Effect,
+    Er: Into<BevyError>,
+{
+    /// The result to be affected or handled.
+    pub result: Result<Ef, Er>,
+    /// The handler to use in the `Err` case.
+    #[debug("BevyError, ErrorContext -> ()")]
+    pub handler: Handler,
+}
+
+impl<Handler, Ef, Er> AffectOrHandleFn<Handler, Ef, Er>
+where
+    Handler: FnOnce(BevyError, ErrorContext),
+    Ef: Effect,
+    Er: Into<BevyError>,
+{
+    /// Maps a `AffectOrHandleFn<H, T, E>` to a `AffectOrHandleFn<H, U, F>` by applying a function
+    /// to the `result` value.
+    pub fn map_result<EfO, ErO>(
+        self,
+        f: impl FnOnce(Result<Ef, Er>) -> Result<EfO, ErO>,
+    ) -> AffectOrHandleFn<Handler, EfO, ErO>
+    where
+        EfO: Effect,
+        ErO: Into<BevyError>,
+    {
+        AffectOrHandleFn {
+            result: f(self.result),
+            handler: self.handler,
+        }
+    }
+
+    /// Maps a `AffectOrHandleFn<H, T, E>` to a `AffectOrHandleFn<H, U, E>` by applying a function
+    /// to the contained `Ok` value.
+    pub fn map<EO>(self, f: impl FnOnce(Ef) -> EO) -> AffectOrHandleFn<Handler, EO, Er>
+    where
+        EO: Effect,
+    {
+        self.map_result(|result| result.map(f))
+    }
+}
+
+/// Construct a new [`AffectOrHandleFn`] [`Effect`].
+pub fn affect_or_handle_fn<Handler, Ef, Er>(
+    result: Result<Ef, Er>,
+    handler: Handler,
+) -> AffectOrHandleFn<Handler, Ef, Er>
+where
+    Handler: FnOnce(BevyError, ErrorContext),
+    Ef: Effect,
+    Er: Into<BevyError>,
+{
+    AffectOrHandleFn { result, handler }
+}
+
+impl<Handler, Ef, Er> Effect for AffectOrHandleFn<Handler, Ef, Er>
+where
+    Handler: FnOnce(BevyError, ErrorContext),
+    Ef: Effect,
+    Er: Into<BevyError>,
+{
+    type MutParam = (Ef::MutParam, SystemName, SystemChangeTick);
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        match self.result {
+            Ok(ef) => ef.affect(&mut param.0),
+            Err(er) => (self.handler)(
+                er.into(),
+                ErrorContext::System {
+                    name: param.1.name(),
+                    last_run: param.2.last_run(),
+                },
+            ),
+        }
+    }
+}
+
+impl<Handler, Ef, Er> WorldEffect for AffectOrHandleFn<Handler, Ef, Er>
+where
+    Handler: FnOnce(BevyError, ErrorContext),
+    Ef: Effect + WorldEffect,
+    Er: Into<BevyError>,
+{
+    fn affect_world(self, world: &mut World) {
+        match self.result {
+            Ok(ef) => ef.affect_world(world),
+            Err(er) => handle_err_in_world(er, self.handler, world),
+        }
+    }
+}
+
+impl<Handler, Ef, Er> RecordEffect for AffectOrHandleFn<Handler, Ef, Er>
+where
+    Handler: FnOnce(BevyError, ErrorContext),
+    Ef: RecordEffect,
+    Er: Into<BevyError>,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        match self.result {
+            Ok(ef) => ef.record(recorder),
+            Err(er) => record_err(er, recorder),
+        }
+    }
+}
+
+impl<Handler, Ef, Er> DryRunEffect for AffectOrHandleFn<Handler, Ef, Er>
+where
+    Handler: FnOnce(BevyError, ErrorContext),
+    Ef: DryRunEffect,
+    Er: Into<BevyError>,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        if let Ok(ef) = self.result {
+            ef.dry_run(dry_run);
+        }
+    }
+}
+
 impl<Ef, Er> Effect for Result<Ef, Er>
 where
     Ef: Effect,
@@ -143,6 +363,70 @@ where
     }
 }
 
+impl<Ef, Er> RecordEffect for Result<Ef, Er>
+where
+    Ef: RecordEffect,
+    Er: Into<BevyError>,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        match self {
+            Ok(ef) => ef.record(recorder),
+            Err(er) => record_err(er, recorder),
+        }
+    }
+}
+
+impl<Ef, Er> DryRunEffect for Result<Ef, Er>
+where
+    Ef: DryRunEffect,
+    Er: Into<BevyError>,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        if let Ok(ef) = self {
+            ef.dry_run(dry_run);
+        }
+    }
+}
+
+impl<Ef, Er> WorldEffect for Result<Ef, Er>
+where
+    Ef: WorldEffect,
+    Er: Into<BevyError>,
+{
+    fn affect_world(self, world: &mut World) {
+        match self {
+            Ok(ef) => ef.affect_world(world),
+            Err(er) => {
+                let default_error_handler = world
+                    .get_resource::<FallbackErrorHandler>()
+                    .copied()
+                    .unwrap_or_default();
+
+                handle_err_in_world(er, default_error_handler.0, world);
+            }
+        }
+    }
+}
+
+impl<Ef, Er> InvertibleEffect for Result<Ef, Er>
+where
+    Ef: InvertibleEffect,
+    Er: Into<BevyError>,
+{
+    type Inverse = Ef::Inverse;
+
+    fn affect_invertible(
+        self,
+        param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>,
+        inverses: &mut Vec<Self::Inverse>,
+    ) {
+        match self {
+            Ok(ef) => ef.affect_invertible(&mut param.1.0, inverses),
+            er => er.affect(param),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::{Arc, Mutex};
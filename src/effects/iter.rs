@@ -3,7 +3,14 @@
 //! On top of the types shown here, this implements [`Effect`] for...
 //! - `Vec<T>` where `T: Effect`
 //! - `Option<T>` where `T: Effect`
-use crate::Effect;
+//!
+//! These are also [`WorldEffect`]s when their inner effects are.
+use bevy::prelude::*;
+
+use crate::dry_run::{DryRun, DryRunEffect};
+use crate::effect_record::{EffectRecorder, RecordEffect};
+use crate::undo::InvertibleEffect;
+use crate::{Effect, WorldEffect};
 
 /// [`Effect`] that causes all effects in the provided iterator.
 ///
@@ -88,6 +95,60 @@ where
     }
 }
 
+impl<I> RecordEffect for AffectMany<I>
+where
+    I: IntoIterator,
+    I::Item: RecordEffect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        self.iter.into_iter().for_each(|e| {
+            e.record(recorder);
+        });
+    }
+}
+
+impl<I> DryRunEffect for AffectMany<I>
+where
+    I: IntoIterator,
+    I::Item: DryRunEffect,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        self.iter.into_iter().for_each(|e| {
+            e.dry_run(dry_run);
+        });
+    }
+}
+
+impl<I> InvertibleEffect for AffectMany<I>
+where
+    I: IntoIterator,
+    I::Item: InvertibleEffect,
+{
+    type Inverse = <I::Item as InvertibleEffect>::Inverse;
+
+    fn affect_invertible(
+        self,
+        param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>,
+        inverses: &mut Vec<Self::Inverse>,
+    ) {
+        self.iter.into_iter().for_each(|e| {
+            e.affect_invertible(param, inverses);
+        });
+    }
+}
+
+impl<I> WorldEffect for AffectMany<I>
+where
+    I: IntoIterator,
+    I::Item: Effect + WorldEffect,
+{
+    fn affect_world(self, world: &mut World) {
+        self.iter.into_iter().for_each(|e| {
+            e.affect_world(world);
+        });
+    }
+}
+
 impl<I> IntoIterator for AffectMany<I>
 where
     I: IntoIterator,
@@ -122,6 +183,50 @@ where
     }
 }
 
+impl<E> RecordEffect for Vec<E>
+where
+    E: RecordEffect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        AffectMany { iter: self }.record(recorder);
+    }
+}
+
+impl<E> DryRunEffect for Vec<E>
+where
+    E: DryRunEffect,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        AffectMany { iter: self }.dry_run(dry_run);
+    }
+}
+
+impl<E> WorldEffect for Vec<E>
+where
+    E: WorldEffect,
+{
+    fn affect_world(self, world: &mut World) {
+        self.into_iter().for_each(|e| {
+            e.affect_world(world);
+        });
+    }
+}
+
+impl<E> InvertibleEffect for Vec<E>
+where
+    E: InvertibleEffect,
+{
+    type Inverse = E::Inverse;
+
+    fn affect_invertible(
+        self,
+        param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>,
+        inverses: &mut Vec<Self::Inverse>,
+    ) {
+        AffectMany { iter: self }.affect_invertible(param, inverses);
+    }
+}
+
 impl<E> Effect for Option<E>
 where
     E: Effect,
@@ -133,6 +238,50 @@ where
     }
 }
 
+impl<E> RecordEffect for Option<E>
+where
+    E: RecordEffect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        AffectMany { iter: self }.record(recorder);
+    }
+}
+
+impl<E> DryRunEffect for Option<E>
+where
+    E: DryRunEffect,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        AffectMany { iter: self }.dry_run(dry_run);
+    }
+}
+
+impl<E> WorldEffect for Option<E>
+where
+    E: WorldEffect,
+{
+    fn affect_world(self, world: &mut World) {
+        if let Some(e) = self {
+            e.affect_world(world);
+        }
+    }
+}
+
+impl<E> InvertibleEffect for Option<E>
+where
+    E: InvertibleEffect,
+{
+    type Inverse = E::Inverse;
+
+    fn affect_invertible(
+        self,
+        param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>,
+        inverses: &mut Vec<Self::Inverse>,
+    ) {
+        AffectMany { iter: self }.affect_invertible(param, inverses);
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -1,7 +1,13 @@
 //! [`Effect`]s that modify `Local` parameters.
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
+use crate::effect_record::{EffectRecorder, RecordEffect};
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
 use crate::{Effect, EffectOut};
 
 /// Type alias for the transforming function used in [`LocalSetAnd`].
@@ -125,6 +131,664 @@ where
     }
 }
 
+impl<T, E> WorldEffect for LocalSetAnd<T, E>
+where
+    T: FromWorld + Send + 'static,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<T, E> RecordEffect for LocalSetAnd<T, E>
+where
+    T: FromWorld + Send + 'static,
+    E: Effect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that transforms a `Local<T>` parameter with the provided function, and can
+/// potentially produce another effect `E`, without boxing the function.
+///
+/// This is the unboxed alternative to [`LocalSetAnd`]. It is generic over the function type `F`
+/// instead, so constructing it doesn't allocate and performing it doesn't use dynamic dispatch.
+/// The function type can be hidden in the system's return type with `impl FnOnce`.
+///
+/// The same note about the locality of the parameter applies as for [`LocalSetAnd`].
+///
+/// Can be constructed with [`local_set_and_fn`].
+///
+/// # Example
+/// In this example, a system is written that updates a resource to the next fibonacci value,
+/// relying on a local parameter to store the previous fibonacci value.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct FibonacciNumber(u32);
+///
+/// /// Pure system using effects.
+/// fn next_fib_pure(
+///     current_fib: Res<FibonacciNumber>,
+/// ) -> LocalSetAndFn<
+///     impl FnOnce(&u32) -> EffectOut<ResSet<FibonacciNumber>, u32> + use<>,
+///     u32,
+///     ResSet<FibonacciNumber>,
+/// > {
+///     let current_fib = current_fib.0;
+///     local_set_and_fn(move |last_fib: &u32| {
+///         let next_fib = FibonacciNumber(current_fib.saturating_add(*last_fib));
+///         effect_out(res_set(next_fib), current_fib)
+///     })
+/// }
+///
+/// /// Equivalent impure system.
+/// fn next_fib_impure(mut current_fib: ResMut<FibonacciNumber>, mut last_fib: Local<u32>) {
+///     let next_fib = current_fib.0.saturating_add(*last_fib);
+///     *last_fib = current_fib.0;
+///     current_fib.0 = next_fib;
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(fib_number: FibonacciNumber) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(fib_number);
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &FibonacciNumber {
+/// #     world.get_resource::<FibonacciNumber>().unwrap()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(fib_number: FibonacciNumber) {
+/// #         let mut pure_app = app_setup(fib_number);
+/// #         pure_app.add_systems(Update, next_fib_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(fib_number);
+/// #         impure_app.add_systems(Update, next_fib_impure);
+/// #
+/// #         for _ in 0..20 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - Other [`Effect`]s can be used for the `E` parameter.
+#[derive(derive_more::Debug)]
+pub struct LocalSetAndFn<F, T, E>
+where
+    F: FnOnce(&T) -> EffectOut<E, T>,
+    T: FromWorld + Send + 'static,
+    E: Effect,
+{
+    /// The function taking the current value of the parameter and returning its new value and
+    /// another effect `E`.
+    #[debug("{0} -> {1}", std::any::type_name::<&T>(), std::any::type_name::<EffectOut<E, T>>())]
+    pub f: F,
+    #[debug(skip)]
+    phantom: PhantomData<fn() -> (T, E)>,
+}
+
+/// Construct a new [`LocalSetAndFn`] [`Effect`].
+pub fn local_set_and_fn<F, T, E>(f: F) -> LocalSetAndFn<F, T, E>
+where
+    F: FnOnce(&T) -> EffectOut<E, T>,
+    T: FromWorld + Send + 'static,
+    E: Effect,
+{
+    LocalSetAndFn {
+        f,
+        phantom: PhantomData,
+    }
+}
+
+impl<F, T, E> Effect for LocalSetAndFn<F, T, E>
+where
+    F: FnOnce(&T) -> EffectOut<E, T>,
+    T: FromWorld + Send + 'static,
+    E: Effect,
+{
+    type MutParam = (Local<'static, T>, <E as Effect>::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        let EffectOut {
+            effect,
+            out: new_local,
+        } = (self.f)(&param.0);
+
+        *param.0 = new_local;
+
+        effect.affect(&mut param.1);
+    }
+}
+
+impl<F, T, E> WorldEffect for LocalSetAndFn<F, T, E>
+where
+    F: FnOnce(&T) -> EffectOut<E, T>,
+    T: FromWorld + Send + 'static,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<F, T, E> RecordEffect for LocalSetAndFn<F, T, E>
+where
+    F: FnOnce(&T) -> EffectOut<E, T>,
+    T: FromWorld + Send + 'static,
+    E: Effect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that sets a `Local<T>` parameter to the provided value.
+///
+/// The same note about the locality of the parameter applies as for [`LocalSetAnd`], so no other
+/// effect or system can read the value it sets. When the value needs to be used, see
+/// [`LocalSetAnd`], [`LocalPush`], [`LocalCounter`] or [`LocalOnce`] instead.
+///
+/// Can be constructed with [`local_set`].
+///
+/// # Example
+/// ```
+/// use bevy::ecs::system::assert_is_system;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// fn reset_pure() -> LocalSet<u32> {
+///     local_set(0)
+/// }
+///
+/// assert_is_system(reset_pure.pipe(affect));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LocalSet<T>
+where
+    T: FromWorld + Send + 'static,
+{
+    /// The value that the parameter will be set to.
+    pub value: T,
+}
+
+/// Construct a new [`LocalSet`] [`Effect`].
+pub fn local_set<T>(value: T) -> LocalSet<T>
+where
+    T: FromWorld + Send + 'static,
+{
+    LocalSet { value }
+}
+
+impl<T> Effect for LocalSet<T>
+where
+    T: FromWorld + Send + 'static,
+{
+    type MutParam = Local<'static, T>;
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        **param = self.value;
+    }
+}
+
+impl<T> WorldEffect for LocalSet<T>
+where
+    T: FromWorld + Send + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<T> RecordEffect for LocalSet<T>
+where
+    T: FromWorld + Send + Debug + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that transforms a `Local<T>` parameter with the provided `&T -> T` function.
+///
+/// The same note about the locality of the parameter applies as for [`LocalSetAnd`], so no other
+/// effect or system can read the value it sets. When the value needs to be used, see
+/// [`LocalSetAnd`] instead.
+///
+/// Can be constructed with [`local_set_with`].
+///
+/// # Example
+/// ```
+/// use bevy::ecs::system::assert_is_system;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// fn increment_pure() -> LocalSetWith<u32> {
+///     local_set_with(|n| n + 1)
+/// }
+///
+/// assert_is_system(increment_pure.pipe(affect));
+/// ```
+#[derive(derive_more::Debug)]
+pub struct LocalSetWith<T>
+where
+    T: FromWorld + Send + 'static,
+{
+    /// The function taking the current value of the parameter and returning its new value.
+    #[debug("{0} -> {1}", std::any::type_name::<&T>(), std::any::type_name::<T>())]
+    pub f: Box<dyn FnOnce(&T) -> T>,
+}
+
+/// Construct a new [`LocalSetWith`] [`Effect`].
+pub fn local_set_with<T, F>(f: F) -> LocalSetWith<T>
+where
+    T: FromWorld + Send + 'static,
+    F: FnOnce(&T) -> T + 'static,
+{
+    LocalSetWith { f: Box::new(f) }
+}
+
+impl<T> Effect for LocalSetWith<T>
+where
+    T: FromWorld + Send + 'static,
+{
+    type MutParam = Local<'static, T>;
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        **param = (self.f)(param);
+    }
+}
+
+impl<T> WorldEffect for LocalSetWith<T>
+where
+    T: FromWorld + Send + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<T> RecordEffect for LocalSetWith<T>
+where
+    T: FromWorld + Send + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// Type alias for the function used in [`LocalPush`].
+pub type BoxedLocalPushFn<T, E> = Box<dyn FnOnce(&VecDeque<T>) -> E>;
+
+/// [`Effect`] that pushes a value to a ring buffer stored in a `Local` parameter, then supplies
+/// the buffer to the provided effect-producing function to cause another effect.
+///
+/// The buffer holds the values of (at most) the last `capacity` times this effect was performed,
+/// oldest first. This is useful for things like moving averages over the last N frames.
+///
+/// The same note about the locality of the parameter applies as for [`LocalSetAnd`].
+///
+/// Can be constructed with [`local_push`].
+///
+/// # Example
+/// In this example, a system is written that sets the `AverageScore` to the average `Score` of the
+/// last 3 frames.
+/// ```
+/// use std::collections::VecDeque;
+///
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u32);
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// struct AverageScore(u32);
+///
+/// fn average(scores: &VecDeque<u32>) -> AverageScore {
+///     AverageScore((scores.iter().map(|&s| s as u64).sum::<u64>() / scores.len() as u64) as u32)
+/// }
+///
+/// /// Pure system using effects.
+/// fn average_score_pure(score: Res<Score>) -> LocalPush<u32, ResSet<AverageScore>> {
+///     local_push(score.0, 3, |scores| res_set(average(scores)))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn average_score_impure(
+///     score: Res<Score>,
+///     mut scores: Local<VecDeque<u32>>,
+///     mut average_score: ResMut<AverageScore>,
+/// ) {
+///     scores.push_back(score.0);
+///     while scores.len() > 3 {
+///         scores.pop_front();
+///     }
+///     *average_score = average(&scores);
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup() -> App {
+/// #     let mut app = App::new();
+/// #     app.init_resource::<Score>().init_resource::<AverageScore>();
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &AverageScore {
+/// #     world.resource::<AverageScore>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(scores: Vec<Score>) {
+/// #         let mut pure_app = app_setup();
+/// #         pure_app.add_systems(Update, average_score_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup();
+/// #         impure_app.add_systems(Update, average_score_impure);
+/// #
+/// #         for score in scores {
+/// #              pure_app.insert_resource(score);
+/// #              impure_app.insert_resource(score);
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - Other [`Effect`]s can be used for the `E` parameter.
+#[derive(derive_more::Debug)]
+pub struct LocalPush<T, E>
+where
+    T: Send + 'static,
+    E: Effect,
+{
+    /// The value to push to the buffer.
+    pub value: T,
+    /// The maximum number of values kept in the buffer.
+    pub capacity: usize,
+    /// The function taking the buffer and returning another effect `E`.
+    #[debug("{0} -> {1}", std::any::type_name::<&VecDeque<T>>(), std::any::type_name::<E>())]
+    pub f: BoxedLocalPushFn<T, E>,
+}
+
+/// Construct a new [`LocalPush`] [`Effect`].
+pub fn local_push<T, E, F>(value: T, capacity: usize, f: F) -> LocalPush<T, E>
+where
+    T: Send + 'static,
+    E: Effect,
+    F: FnOnce(&VecDeque<T>) -> E + 'static,
+{
+    LocalPush {
+        value,
+        capacity,
+        f: Box::new(f),
+    }
+}
+
+impl<T, E> Effect for LocalPush<T, E>
+where
+    T: Send + 'static,
+    E: Effect,
+{
+    type MutParam = (Local<'static, VecDeque<T>>, <E as Effect>::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        let buffer = &mut *param.0;
+
+        buffer.push_back(self.value);
+        while buffer.len() > self.capacity {
+            buffer.pop_front();
+        }
+
+        (self.f)(buffer).affect(&mut param.1);
+    }
+}
+
+impl<T, E> WorldEffect for LocalPush<T, E>
+where
+    T: Send + 'static,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<T, E> RecordEffect for LocalPush<T, E>
+where
+    T: Send + Debug + 'static,
+    E: Effect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that increments a counter stored in a `Local` parameter, then supplies the new count
+/// to the provided effect-producing function to cause another effect.
+///
+/// The count starts at 1 the first time this effect is performed.
+///
+/// The same note about the locality of the parameter applies as for [`LocalSetAnd`].
+///
+/// Can be constructed with [`local_counter`].
+///
+/// # Example
+/// In this example, a system is written that spawns an `Enemy` every third frame.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Component)]
+/// struct Enemy;
+///
+/// /// Pure system using effects.
+/// fn spawn_enemies_pure() -> LocalCounter<Option<CommandSpawn<Enemy>>> {
+///     local_counter(|count| (count % 3 == 0).then_some(command_spawn(Enemy)))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn spawn_enemies_impure(mut count: Local<u64>, mut commands: Commands) {
+///     *count += 1;
+///     if *count % 3 == 0 {
+///         commands.spawn(Enemy);
+///     }
+/// }
+/// #
+/// # fn test_state(world: &mut World) -> usize {
+/// #     world.query::<&Enemy>().iter(world).count()
+/// # }
+/// #
+/// # fn main() {
+/// #     let mut pure_app = App::new();
+/// #     pure_app.add_systems(Update, spawn_enemies_pure.pipe(affect));
+/// #
+/// #     let mut impure_app = App::new();
+/// #     impure_app.add_systems(Update, spawn_enemies_impure);
+/// #
+/// #     for _ in 0..10 {
+/// #         assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #         pure_app.update();
+/// #         impure_app.update();
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - Other [`Effect`]s can be used for the `E` parameter.
+#[derive(derive_more::Debug)]
+pub struct LocalCounter<E>
+where
+    E: Effect,
+{
+    /// The function taking the new count and returning another effect `E`.
+    #[debug("u64 -> {0}", std::any::type_name::<E>())]
+    pub f: Box<dyn FnOnce(u64) -> E>,
+}
+
+/// Construct a new [`LocalCounter`] [`Effect`].
+pub fn local_counter<E, F>(f: F) -> LocalCounter<E>
+where
+    E: Effect,
+    F: FnOnce(u64) -> E + 'static,
+{
+    LocalCounter { f: Box::new(f) }
+}
+
+impl<E> Effect for LocalCounter<E>
+where
+    E: Effect,
+{
+    type MutParam = (Local<'static, u64>, <E as Effect>::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        *param.0 += 1;
+
+        (self.f)(*param.0).affect(&mut param.1);
+    }
+}
+
+impl<E> WorldEffect for LocalCounter<E>
+where
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<E> RecordEffect for LocalCounter<E>
+where
+    E: Effect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that performs the provided effect only the first time it is performed by a system.
+///
+/// Whether it has been performed is stored in a `Local` parameter, so this fires exactly once per
+/// system instance. The same note about the locality of the parameter applies as for
+/// [`LocalSetAnd`].
+///
+/// Can be constructed with [`local_once`].
+///
+/// # Example
+/// In this example, a system is written that spawns the `Player` once.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Component)]
+/// struct Player;
+///
+/// /// Pure system using effects.
+/// fn spawn_player_pure() -> LocalOnce<CommandSpawn<Player>> {
+///     local_once(command_spawn(Player))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn spawn_player_impure(mut spawned: Local<bool>, mut commands: Commands) {
+///     if !*spawned {
+///         *spawned = true;
+///         commands.spawn(Player);
+///     }
+/// }
+/// #
+/// # fn test_state(world: &mut World) -> usize {
+/// #     world.query::<&Player>().iter(world).count()
+/// # }
+/// #
+/// # fn main() {
+/// #     let mut pure_app = App::new();
+/// #     pure_app.add_systems(Update, spawn_player_pure.pipe(affect));
+/// #
+/// #     let mut impure_app = App::new();
+/// #     impure_app.add_systems(Update, spawn_player_impure);
+/// #
+/// #     for _ in 0..3 {
+/// #         assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #         pure_app.update();
+/// #         impure_app.update();
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - Other [`Effect`]s can be used for the `E` parameter.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LocalOnce<E>
+where
+    E: Effect,
+{
+    /// The effect to perform the first time.
+    pub effect: E,
+}
+
+/// Construct a new [`LocalOnce`] [`Effect`].
+pub fn local_once<E>(effect: E) -> LocalOnce<E>
+where
+    E: Effect,
+{
+    LocalOnce { effect }
+}
+
+impl<E> Effect for LocalOnce<E>
+where
+    E: Effect,
+{
+    type MutParam = (Local<'static, bool>, <E as Effect>::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        if !*param.0 {
+            *param.0 = true;
+            self.effect.affect(&mut param.1);
+        }
+    }
+}
+
+impl<E> WorldEffect for LocalOnce<E>
+where
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<E> RecordEffect for LocalOnce<E>
+where
+    E: Effect + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::prelude::*;
@@ -133,9 +797,12 @@ mod tests {
     use crate::effect_out;
     use crate::effects::number_data::NumberResource;
     use crate::effects::one_way_fn::OneWayFn;
-    use crate::effects::resource::res_set;
+    use crate::effects::resource::{res_set, res_set_with};
     use crate::prelude::affect;
 
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Resource)]
+    struct Buffer(Vec<u128>);
+
     proptest! {
         #[test]
         fn local_set_and_sets_value_and_produces_effect(one_way_fn: OneWayFn) {
@@ -170,5 +837,68 @@ mod tests {
                 );
             }
         }
+
+        #[test]
+        fn local_push_supplies_last_values_up_to_capacity(values: Vec<u128>, capacity in 1..5usize) {
+            let mut app = App::new();
+
+            app.init_resource::<NumberResource>()
+                .init_resource::<Buffer>()
+                .add_systems(
+                    Update,
+                    (move |n: Res<NumberResource>| {
+                        local_push(n.0, capacity, |buffer: &VecDeque<u128>| {
+                            res_set(Buffer(buffer.iter().copied().collect()))
+                        })
+                    })
+                    .pipe(affect),
+                );
+
+            let mut expected = VecDeque::new();
+
+            for value in values {
+                expected.push_back(value);
+                if expected.len() > capacity {
+                    expected.pop_front();
+                }
+
+                app.insert_resource(NumberResource(value));
+                app.update();
+
+                prop_assert_eq!(&app.world().resource::<Buffer>().0, &Vec::from(expected.clone()));
+            }
+        }
+
+        #[test]
+        fn local_counter_counts_performances(updates in 0..10u128) {
+            let mut app = App::new();
+
+            app.init_resource::<NumberResource>().add_systems(
+                Update,
+                (|| local_counter(|count| res_set(NumberResource(count as u128)))).pipe(affect),
+            );
+
+            for _ in 0..updates {
+                app.update();
+            }
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &NumberResource(updates));
+        }
+
+        #[test]
+        fn local_once_performs_effect_once(initial: NumberResource, f: OneWayFn) {
+            let mut app = App::new();
+
+            app.insert_resource(initial).add_systems(
+                Update,
+                (move || local_once(res_set_with(move |&NumberResource(n)| NumberResource(f.call(n))))).pipe(affect),
+            );
+
+            for _ in 0..3 {
+                app.update();
+            }
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &NumberResource(f.call(initial.0)));
+        }
     }
 }
@@ -1,7 +1,12 @@
 //! [`Effect`]s that modify `MessageReader`s and `MessageWriter`s.
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
 use bevy::prelude::*;
 
 use crate::Effect;
+use crate::effect_record::{EffectRecorder, RecordEffect};
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
 
 /// [`Effect`] that sends a message `M` to the corresponding `MessageWriter`.
 ///
@@ -101,6 +106,278 @@ where
     }
 }
 
+impl<M> WorldEffect for MessageWrite<M>
+where
+    M: Message,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<M> RecordEffect for MessageWrite<M>
+where
+    M: Message + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that sends a batch of messages `M` to the corresponding `MessageWriter`.
+///
+/// The messages are written with a single `MessageWriter::write_batch` call, rather than one write
+/// per message like a `Vec` of [`MessageWrite`]s.
+///
+/// Can be constructed with [`message_write_batch`].
+///
+/// # Example
+/// In this example, a system is written that writes a `Winner` message for every entity with a
+/// score above 100.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u8);
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Message)]
+/// struct Winner(Entity);
+///
+/// /// Pure system using effects.
+/// fn declare_winners_pure(query: Query<(Entity, &Score)>) -> MessageWriteBatch<Winner> {
+///     message_write_batch(
+///         query
+///             .iter()
+///             .filter(|(_, score)| score.0 >= 100)
+///             .map(|(entity, _)| Winner(entity)),
+///     )
+/// }
+///
+/// /// Equivalent impure system.
+/// fn declare_winners_impure(query: Query<(Entity, &Score)>, mut writer: MessageWriter<Winner>) {
+///     writer.write_batch(
+///         query
+///             .iter()
+///             .filter(|(_, score)| score.0 >= 100)
+///             .map(|(entity, _)| Winner(entity)),
+///     );
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(component_table: Vec<Option<Score>>) -> App {
+/// #     let mut app = App::new();
+/// #     app.add_message::<Winner>();
+/// #     component_table.into_iter().for_each(|score| {
+/// #         let mut entity = app.world_mut().spawn_empty();
+/// #         if let Some(score) = score {
+/// #             entity.insert(score);
+/// #         }
+/// #     });
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &World) -> Vec<&Winner> {
+/// #     world
+/// #         .resource::<Messages<Winner>>()
+/// #         .iter_current_update_messages()
+/// #         .collect::<Vec<_>>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<Option<Score>>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, declare_winners_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table.clone());
+/// #         impure_app.add_systems(Update, declare_winners_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MessageWriteBatch<M>
+where
+    M: Message,
+{
+    /// The messages that will be written to the `MessageWriter`, in order.
+    pub messages: Vec<M>,
+}
+
+/// Construct a new [`MessageWriteBatch`] [`Effect`].
+pub fn message_write_batch<M>(messages: impl IntoIterator<Item = M>) -> MessageWriteBatch<M>
+where
+    M: Message,
+{
+    MessageWriteBatch {
+        messages: messages.into_iter().collect(),
+    }
+}
+
+impl<M> Effect for MessageWriteBatch<M>
+where
+    M: Message,
+{
+    type MutParam = MessageWriter<'static, M>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.write_batch(self.messages);
+    }
+}
+
+impl<M> WorldEffect for MessageWriteBatch<M>
+where
+    M: Message,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<M> RecordEffect for MessageWriteBatch<M>
+where
+    M: Message + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that discards all pending messages `M`.
+///
+/// After this, no `MessageReader` will see the messages written before it, including those that
+/// haven't been read yet. This is useful for flushing input messages, e.g. when a modal dialog
+/// opens.
+///
+/// Since this mutates the `Messages<M>` resource, it conflicts with `MessageReader<M>` and
+/// `MessageWriter<M>` parameters, and can't be combined with effects using them in a single
+/// effect.
+///
+/// Can be constructed with [`messages_clear`].
+///
+/// # Example
+/// In this example, a system is written that discards all pending `Click` messages while a
+/// `Modal` is open.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
+/// struct Modal;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Message)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Click(u8);
+///
+/// /// Pure system using effects.
+/// fn flush_clicks_pure(modal: Option<Res<Modal>>) -> Option<MessagesClear<Click>> {
+///     modal.map(|_| messages_clear::<Click>())
+/// }
+///
+/// /// Equivalent impure system.
+/// fn flush_clicks_impure(modal: Option<Res<Modal>>, mut clicks: ResMut<Messages<Click>>) {
+///     if modal.is_some() {
+///         clicks.clear();
+///     }
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(modal: bool, clicks: Vec<Click>) -> App {
+/// #     let mut app = App::new();
+/// #     app.add_message::<Click>();
+/// #     if modal {
+/// #         app.insert_resource(Modal);
+/// #     }
+/// #     app.world_mut().write_message_batch(clicks);
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &World) -> usize {
+/// #     world.resource::<Messages<Click>>().len()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(modal: bool, clicks: Vec<Click>) {
+/// #         let mut pure_app = app_setup(modal, clicks.clone());
+/// #         pure_app.add_systems(Update, flush_clicks_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(modal, clicks.clone());
+/// #         impure_app.add_systems(Update, flush_clicks_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Copy, Clone, Default, PartialEq, Eq, derive_more::Debug)]
+pub struct MessagesClear<M>
+where
+    M: Message,
+{
+    #[debug("{}", std::any::type_name::<M>())]
+    messages: PhantomData<fn() -> M>,
+}
+
+impl<M> MessagesClear<M>
+where
+    M: Message,
+{
+    /// Construct a new [`MessagesClear`]
+    pub fn new() -> Self {
+        MessagesClear {
+            messages: PhantomData,
+        }
+    }
+}
+
+/// Construct a new [`MessagesClear`] [`Effect`].
+pub fn messages_clear<M>() -> MessagesClear<M>
+where
+    M: Message,
+{
+    MessagesClear::new()
+}
+
+impl<M> Effect for MessagesClear<M>
+where
+    M: Message,
+{
+    type MutParam = ResMut<'static, Messages<M>>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.clear();
+    }
+}
+
+impl<M> WorldEffect for MessagesClear<M>
+where
+    M: Message,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<M> RecordEffect for MessagesClear<M>
+where
+    M: Message,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
 /// [`Effect`] that reads all messages in a `MessageReader`, supplying them to the provided
 /// effect-producing function to cause another effect.
 ///
@@ -254,78 +531,559 @@ where
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use proptest::prelude::*;
-
-    use super::*;
-    use crate::effects::number_data::{NumberMessage, NumberResource};
-    use crate::effects::resource::res_set_with;
-    use crate::prelude::affect;
-
-    proptest! {
-        #[test]
-        fn message_write_produces_messages(messages in prop::collection::vec(any::<NumberMessage>(), 1..10)) {
-            let mut app = App::new();
-
-            let mut messages_clone = messages.clone();
-            app.add_message::<NumberMessage>()
-                .add_systems(Update, (move || message_write(messages_clone.remove(0))).pipe(affect));
-
-            for expected in messages {
-                app.update();
-
-                let messages_in_update = app.world().resource::<Messages<NumberMessage>>().iter_current_update_messages().collect::<Vec<_>>();
-
-                prop_assert_eq!(messages_in_update.len(), 1);
-
-                let message_sent = messages_in_update.first().unwrap();
-
-                prop_assert_eq!(message_sent, &&expected);
-            }
-        }
-
-        #[test]
-        fn messages_read_and_reads_all_available_and_produces_effects(message_bundles in prop::collection::vec(prop::collection::vec(any::<NumberMessage>(), 0..10), 0..10)) {
-            let mut app = App::new();
-
-            let mut message_bundles_write_clone = message_bundles.clone();
-
-            app.add_message::<NumberMessage>()
-                .init_resource::<NumberResource>()
-                .add_systems(
-                    Update,
-                    (
-                        // An effect that writes the messages, one bundle at a time
-                        (move || {
-                            message_bundles_write_clone
-                                .remove(0)
-                                .into_iter()
-                                .map(message_write)
-                                .collect::<Vec<_>>()
-                        })
-                        .pipe(affect),
-                        // An effect that reads the messages and sums their values into a resource
-                        (|| {
-                            messages_read_and(move |m: &NumberMessage| {
-                                let m = m.0;
-                                res_set_with(move |n: &NumberResource| NumberResource(n.0.wrapping_add(m)))
-                            })
-                        })
-                        .pipe(affect),
-                    )
-                        // Chained to make sure the writing system comes before the reading system
-                        .chain(),
-                );
-
-            assert_eq!(app.world().resource::<NumberResource>().0, 0);
+impl<M, E> WorldEffect for MessagesReadAnd<M, E>
+where
+    M: Message,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
 
-            for i in 0..message_bundles.len() {
-                app.update();
+impl<M, E> RecordEffect for MessagesReadAnd<M, E>
+where
+    M: Message,
+    E: Effect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
 
-                let expected_written_so_far = message_bundles
-                    .iter()
-                    // i + 1 updates have occurred, so i + 1 bundles have been written.
+/// [`Effect`] that reads all messages in a `MessageReader`, supplying them to the provided
+/// effect-producing function to cause another effect, without boxing the function.
+///
+/// This is the unboxed alternative to [`MessagesReadAnd`]. It is generic over the function type
+/// `F` instead, so constructing it doesn't allocate and performing it doesn't use dynamic
+/// dispatch. The function type can be hidden in the system's return type with `impl FnMut`.
+///
+/// The cursor of the message reader is updated.
+///
+/// Can be constructed with [`messages_read_and_fn`].
+///
+/// # Example
+/// In this example, a system is written that inserts a `Crown` component to entities declared a
+/// winner by the `Winner` message.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// struct Crown;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Message)]
+/// struct Winner(Entity);
+///
+/// /// Pure system using effects.
+/// fn crown_winners_pure() -> MessagesReadAndFn<
+///     impl FnMut(&Winner) -> EntityCommandInsert<Crown>,
+///     Winner,
+///     EntityCommandInsert<Crown>,
+/// > {
+///     messages_read_and_fn(|&Winner(entity): &Winner| entity_command_insert(entity, Crown))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn crown_winners_impure(mut reader: MessageReader<Winner>, mut commands: Commands) {
+///     reader.read().for_each(|Winner(entity)| {
+///         commands.entity(*entity).insert(Crown);
+///     });
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(entity_count: usize, mut winner_indices_per_update: Vec<Vec<usize>>) -> App {
+/// #     let mut app = App::new();
+/// #     app.add_message::<Winner>();
+/// #
+/// #     let entities = (0..entity_count)
+/// #         .map(|_| app.world_mut().spawn_empty().id())
+/// #         .collect::<Vec<_>>();
+/// #
+/// #     app.add_systems(
+/// #         PreUpdate,
+/// #         (move || {
+/// #             winner_indices_per_update.pop().map(|winner_indices| {
+/// #                 winner_indices
+/// #                     .into_iter()
+/// #                     .map(|winner_index| message_write(Winner(entities[winner_index % entities.len()])))
+/// #                     .collect::<Vec<_>>()
+/// #             })
+/// #         })
+/// #         .pipe(affect),
+/// #     );
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<(Entity, Option<&Crown>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Crown>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(entity_count in 1..64usize, winner_indices: Vec<Vec<usize>>) {
+/// #         let mut pure_app = app_setup(entity_count, winner_indices.clone());
+/// #         pure_app.add_systems(Update, crown_winners_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(entity_count, winner_indices.clone());
+/// #         impure_app.add_systems(Update, crown_winners_impure);
+/// #
+/// #         for _ in 0..winner_indices.len() + 1 {
+/// #             prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - in this example, [`EntityCommandInsert`] is used as the [`Effect`], but any other effect
+///   could be produced.
+///
+/// [`EntityCommandInsert`]: crate::prelude::EntityCommandInsert
+#[derive(derive_more::Debug)]
+pub struct MessagesReadAndFn<F, M, E>
+where
+    F: FnMut(&M) -> E,
+    M: Message,
+    E: Effect,
+{
+    /// The `&Message -> Effect` function that may cause another effect.
+    #[debug("{0} -> {1}", std::any::type_name::<&M>(), std::any::type_name::<E>())]
+    pub f: F,
+    #[debug(skip)]
+    phantom: PhantomData<fn() -> (M, E)>,
+}
+
+/// Construct a new [`MessagesReadAndFn`] [`Effect`].
+pub fn messages_read_and_fn<F, M, E>(f: F) -> MessagesReadAndFn<F, M, E>
+where
+    F: FnMut(&M) -> E,
+    M: Message,
+    E: Effect,
+{
+    MessagesReadAndFn {
+        f,
+        phantom: PhantomData,
+    }
+}
+
+impl<F, M, E> Effect for MessagesReadAndFn<F, M, E>
+where
+    F: FnMut(&M) -> E,
+    M: Message,
+    E: Effect,
+{
+    type MutParam = (MessageReader<'static, 'static, M>, E::MutParam);
+
+    fn affect(
+        mut self,
+        param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>,
+    ) {
+        param
+            .0
+            .read()
+            .for_each(|message| (self.f)(message).affect(&mut param.1))
+    }
+}
+
+impl<F, M, E> WorldEffect for MessagesReadAndFn<F, M, E>
+where
+    F: FnMut(&M) -> E,
+    M: Message,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<F, M, E> RecordEffect for MessagesReadAndFn<F, M, E>
+where
+    F: FnMut(&M) -> E,
+    M: Message,
+    E: Effect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// Type alias for the folding function used in [`MessagesFoldAnd`].
+pub type BoxedMessagesFoldFn<M, A> = Box<dyn FnMut(A, &M) -> A>;
+
+/// [`Effect`] that folds all messages in a `MessageReader` into an accumulator, then supplies the
+/// accumulator to the provided effect-producing function to cause another effect.
+///
+/// Unlike [`MessagesReadAnd`], which produces an effect per message, this aggregates all of them
+/// into one effect. The effect-producing function is called even if there are no messages, with
+/// the initial accumulator.
+///
+/// The cursor of the message reader is updated, so every message is seen exactly once.
+///
+/// Can be constructed with [`messages_fold_and`].
+///
+/// # Example
+/// In this example, a system is written that subtracts the total of all `Damage` messages from the
+/// `Health` resource.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
+/// struct Health(u32);
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Message)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Damage(u32);
+///
+/// /// Pure system using effects.
+/// fn take_damage_pure() -> MessagesFoldAnd<Damage, u32, ResSetWith<Health>> {
+///     messages_fold_and(
+///         0,
+///         |total, &Damage(damage)| total.saturating_add(damage),
+///         |total| res_set_with(move |&Health(health)| Health(health.saturating_sub(total))),
+///     )
+/// }
+///
+/// /// Equivalent impure system.
+/// fn take_damage_impure(mut reader: MessageReader<Damage>, mut health: ResMut<Health>) {
+///     let total = reader
+///         .read()
+///         .fold(0u32, |total, &Damage(damage)| total.saturating_add(damage));
+///     health.0 = health.0.saturating_sub(total);
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(mut damages_per_update: Vec<Vec<Damage>>) -> App {
+/// #     let mut app = App::new();
+/// #     app.add_message::<Damage>()
+/// #         .insert_resource(Health(u32::MAX / 2))
+/// #         .add_systems(
+/// #             PreUpdate,
+/// #             (move || {
+/// #                 damages_per_update
+/// #                     .pop()
+/// #                     .map(|damages| damages.into_iter().map(message_write).collect::<Vec<_>>())
+/// #             })
+/// #             .pipe(affect),
+/// #         );
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &Health {
+/// #     world.resource::<Health>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(damages_per_update: Vec<Vec<Damage>>) {
+/// #         let mut pure_app = app_setup(damages_per_update.clone());
+/// #         pure_app.add_systems(Update, take_damage_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(damages_per_update.clone());
+/// #         impure_app.add_systems(Update, take_damage_impure);
+/// #
+/// #         for _ in 0..damages_per_update.len() + 1 {
+/// #             prop_assert_eq!(resource_state(pure_app.world()), resource_state(impure_app.world()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - in this example, a `ResSetWith` is used as the additional [`Effect`], but any other effect
+///   could be produced.
+#[derive(derive_more::Debug)]
+pub struct MessagesFoldAnd<M, A, E>
+where
+    M: Message,
+    E: Effect,
+{
+    /// The initial value of the accumulator.
+    pub init: A,
+    /// The `Accumulator, &Message -> Accumulator` function folding each message.
+    #[debug("{0}, {1} -> {0}", std::any::type_name::<A>(), std::any::type_name::<&M>())]
+    pub fold: BoxedMessagesFoldFn<M, A>,
+    /// The `Accumulator -> Effect` function that may cause another effect.
+    #[debug("{0} -> {1}", std::any::type_name::<A>(), std::any::type_name::<E>())]
+    pub f: Box<dyn FnOnce(A) -> E>,
+}
+
+/// Construct a new [`MessagesFoldAnd`] [`Effect`].
+pub fn messages_fold_and<M, A, E, Fold, F>(init: A, fold: Fold, f: F) -> MessagesFoldAnd<M, A, E>
+where
+    M: Message,
+    E: Effect,
+    Fold: FnMut(A, &M) -> A + 'static,
+    F: FnOnce(A) -> E + 'static,
+{
+    MessagesFoldAnd {
+        init,
+        fold: Box::new(fold),
+        f: Box::new(f),
+    }
+}
+
+impl<M, A, E> Effect for MessagesFoldAnd<M, A, E>
+where
+    M: Message,
+    E: Effect,
+{
+    type MutParam = (MessageReader<'static, 'static, M>, E::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        let accumulator = param.0.read().fold(self.init, self.fold);
+
+        (self.f)(accumulator).affect(&mut param.1);
+    }
+}
+
+impl<M, A, E> WorldEffect for MessagesFoldAnd<M, A, E>
+where
+    M: Message,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<M, A, E> RecordEffect for MessagesFoldAnd<M, A, E>
+where
+    M: Message,
+    A: Debug,
+    E: Effect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// Type alias for the effect-producing function used in [`MessagesCollectAnd`].
+pub type BoxedMessagesCollectFn<M, E> = Box<dyn FnOnce(Vec<&M>) -> E>;
+
+/// [`Effect`] that collects all messages in a `MessageReader`, then supplies them to the provided
+/// effect-producing function to cause another effect.
+///
+/// Unlike [`MessagesReadAnd`], which produces an effect per message, this supplies all of them to
+/// one function, in the order they were written. The function is called even if there are no
+/// messages, with an empty `Vec`.
+///
+/// The cursor of the message reader is updated, so every message is seen exactly once.
+///
+/// Can be constructed with [`messages_collect_and`].
+///
+/// # Example
+/// In this example, a system is written that sets the `WindowSize` to the last `Resize` message.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// struct WindowSize(u32);
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Message)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Resize(u32);
+///
+/// /// Pure system using effects.
+/// fn resize_pure() -> MessagesCollectAnd<Resize, Option<ResSet<WindowSize>>> {
+///     messages_collect_and(|resizes: Vec<&Resize>| {
+///         resizes.last().map(|&&Resize(size)| res_set(WindowSize(size)))
+///     })
+/// }
+///
+/// /// Equivalent impure system.
+/// fn resize_impure(mut reader: MessageReader<Resize>, mut window_size: ResMut<WindowSize>) {
+///     if let Some(&Resize(size)) = reader.read().last() {
+///         window_size.0 = size;
+///     }
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(mut resizes_per_update: Vec<Vec<Resize>>) -> App {
+/// #     let mut app = App::new();
+/// #     app.add_message::<Resize>()
+/// #         .init_resource::<WindowSize>()
+/// #         .add_systems(
+/// #             PreUpdate,
+/// #             (move || {
+/// #                 resizes_per_update
+/// #                     .pop()
+/// #                     .map(|resizes| resizes.into_iter().map(message_write).collect::<Vec<_>>())
+/// #             })
+/// #             .pipe(affect),
+/// #         );
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &WindowSize {
+/// #     world.resource::<WindowSize>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(resizes_per_update: Vec<Vec<Resize>>) {
+/// #         let mut pure_app = app_setup(resizes_per_update.clone());
+/// #         pure_app.add_systems(Update, resize_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(resizes_per_update.clone());
+/// #         impure_app.add_systems(Update, resize_impure);
+/// #
+/// #         for _ in 0..resizes_per_update.len() + 1 {
+/// #             prop_assert_eq!(resource_state(pure_app.world()), resource_state(impure_app.world()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - in this example, a `ResSet` is used as the additional [`Effect`], but any other effect could
+///   be produced.
+#[derive(derive_more::Debug)]
+pub struct MessagesCollectAnd<M, E>
+where
+    M: Message,
+    E: Effect,
+{
+    /// The `Vec<&Message> -> Effect` function that may cause another effect.
+    #[debug("{0} -> {1}", std::any::type_name::<Vec<&M>>(), std::any::type_name::<E>())]
+    pub f: BoxedMessagesCollectFn<M, E>,
+}
+
+/// Construct a new [`MessagesCollectAnd`] [`Effect`].
+pub fn messages_collect_and<M, E, F>(f: F) -> MessagesCollectAnd<M, E>
+where
+    M: Message,
+    E: Effect,
+    F: FnOnce(Vec<&M>) -> E + 'static,
+{
+    MessagesCollectAnd { f: Box::new(f) }
+}
+
+impl<M, E> Effect for MessagesCollectAnd<M, E>
+where
+    M: Message,
+    E: Effect,
+{
+    type MutParam = (MessageReader<'static, 'static, M>, E::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        let messages = param.0.read().collect();
+
+        (self.f)(messages).affect(&mut param.1);
+    }
+}
+
+impl<M, E> WorldEffect for MessagesCollectAnd<M, E>
+where
+    M: Message,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<M, E> RecordEffect for MessagesCollectAnd<M, E>
+where
+    M: Message,
+    E: Effect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::effects::number_data::{NumberMessage, NumberResource};
+    use crate::effects::resource::{res_set, res_set_with};
+    use crate::prelude::affect;
+
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Resource)]
+    struct CollectedMessages(Vec<NumberMessage>);
+
+    proptest! {
+        #[test]
+        fn message_write_produces_messages(messages in prop::collection::vec(any::<NumberMessage>(), 1..10)) {
+            let mut app = App::new();
+
+            let mut messages_clone = messages.clone();
+            app.add_message::<NumberMessage>()
+                .add_systems(Update, (move || message_write(messages_clone.remove(0))).pipe(affect));
+
+            for expected in messages {
+                app.update();
+
+                let messages_in_update = app.world().resource::<Messages<NumberMessage>>().iter_current_update_messages().collect::<Vec<_>>();
+
+                prop_assert_eq!(messages_in_update.len(), 1);
+
+                let message_sent = messages_in_update.first().unwrap();
+
+                prop_assert_eq!(message_sent, &&expected);
+            }
+        }
+
+        #[test]
+        fn messages_read_and_reads_all_available_and_produces_effects(message_bundles in prop::collection::vec(prop::collection::vec(any::<NumberMessage>(), 0..10), 0..10)) {
+            let mut app = App::new();
+
+            let mut message_bundles_write_clone = message_bundles.clone();
+
+            app.add_message::<NumberMessage>()
+                .init_resource::<NumberResource>()
+                .add_systems(
+                    Update,
+                    (
+                        // An effect that writes the messages, one bundle at a time
+                        (move || {
+                            message_bundles_write_clone
+                                .remove(0)
+                                .into_iter()
+                                .map(message_write)
+                                .collect::<Vec<_>>()
+                        })
+                        .pipe(affect),
+                        // An effect that reads the messages and sums their values into a resource
+                        (|| {
+                            messages_read_and(move |m: &NumberMessage| {
+                                let m = m.0;
+                                res_set_with(move |n: &NumberResource| NumberResource(n.0.wrapping_add(m)))
+                            })
+                        })
+                        .pipe(affect),
+                    )
+                        // Chained to make sure the writing system comes before the reading system
+                        .chain(),
+                );
+
+            assert_eq!(app.world().resource::<NumberResource>().0, 0);
+
+            for i in 0..message_bundles.len() {
+                app.update();
+
+                let expected_written_so_far = message_bundles
+                    .iter()
+                    // i + 1 updates have occurred, so i + 1 bundles have been written.
                     .take(i + 1)
                     .flatten()
                     .map(|m| m.0)
@@ -338,5 +1096,130 @@ mod tests {
                 );
             }
         }
+
+        #[test]
+        fn messages_fold_and_folds_each_message_once(message_bundles in prop::collection::vec(prop::collection::vec(any::<NumberMessage>(), 0..10), 0..10)) {
+            let mut app = App::new();
+
+            let mut message_bundles_write_clone = message_bundles.clone();
+
+            app.add_message::<NumberMessage>()
+                .init_resource::<NumberResource>()
+                .add_systems(
+                    Update,
+                    (
+                        (move || {
+                            message_bundles_write_clone
+                                .remove(0)
+                                .into_iter()
+                                .map(message_write)
+                                .collect::<Vec<_>>()
+                        })
+                        .pipe(affect),
+                        (|| {
+                            messages_fold_and(
+                                0,
+                                |total: u128, m: &NumberMessage| total.wrapping_add(m.0),
+                                |total| res_set_with(move |n: &NumberResource| NumberResource(n.0.wrapping_add(total))),
+                            )
+                        })
+                        .pipe(affect),
+                    )
+                        .chain(),
+                );
+
+            for i in 0..message_bundles.len() {
+                app.update();
+
+                let expected_written_so_far = message_bundles
+                    .iter()
+                    .take(i + 1)
+                    .flatten()
+                    .map(|m| m.0)
+                    .fold(0, u128::wrapping_add);
+
+                prop_assert_eq!(app.world().resource::<NumberResource>().0, expected_written_so_far);
+            }
+        }
+
+        #[test]
+        fn messages_collect_and_collects_messages_in_order(message_bundles in prop::collection::vec(prop::collection::vec(any::<NumberMessage>(), 0..10), 0..10)) {
+            let mut app = App::new();
+
+            let mut message_bundles_write_clone = message_bundles.clone();
+
+            app.add_message::<NumberMessage>()
+                .init_resource::<CollectedMessages>()
+                .add_systems(
+                    Update,
+                    (
+                        (move || {
+                            message_bundles_write_clone
+                                .remove(0)
+                                .into_iter()
+                                .map(message_write)
+                                .collect::<Vec<_>>()
+                        })
+                        .pipe(affect),
+                        (|| {
+                            messages_collect_and(|messages: Vec<&NumberMessage>| {
+                                res_set(CollectedMessages(messages.into_iter().copied().collect()))
+                            })
+                        })
+                        .pipe(affect),
+                    )
+                        .chain(),
+                );
+
+            for bundle in message_bundles {
+                app.update();
+
+                prop_assert_eq!(&app.world().resource::<CollectedMessages>().0, &bundle);
+            }
+        }
+
+        #[test]
+        fn message_write_batch_produces_messages_in_order(message_bundles in prop::collection::vec(prop::collection::vec(any::<NumberMessage>(), 0..10), 1..10)) {
+            let mut app = App::new();
+
+            let mut message_bundles_clone = message_bundles.clone();
+            app.add_message::<NumberMessage>()
+                .add_systems(Update, (move || message_write_batch(message_bundles_clone.remove(0))).pipe(affect));
+
+            for expected in message_bundles {
+                app.update();
+
+                let messages_in_update = app.world().resource::<Messages<NumberMessage>>().iter_current_update_messages().copied().collect::<Vec<_>>();
+
+                prop_assert_eq!(messages_in_update, expected);
+            }
+        }
+
+        #[test]
+        fn messages_clear_discards_pending_messages(messages in prop::collection::vec(any::<NumberMessage>(), 0..10)) {
+            let mut app = App::new();
+
+            app.add_message::<NumberMessage>()
+                .add_systems(
+                    Update,
+                    (
+                        (move || message_write_batch(messages.clone())).pipe(affect),
+                        (|| messages_clear::<NumberMessage>()).pipe(affect),
+                        (|| {
+                            messages_collect_and(|messages: Vec<&NumberMessage>| {
+                                res_set(CollectedMessages(messages.into_iter().copied().collect()))
+                            })
+                        })
+                        .pipe(affect),
+                    )
+                        .chain(),
+                );
+
+            app.world_mut().insert_resource(CollectedMessages(vec![NumberMessage(0)]));
+            app.update();
+
+            prop_assert!(app.world().resource::<Messages<NumberMessage>>().is_empty());
+            prop_assert_eq!(&app.world().resource::<CollectedMessages>().0, &vec![]);
+        }
     }
 }
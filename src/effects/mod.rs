@@ -1,4 +1,4 @@
-//! [`Effect`] implementors and their constructors.
+//! [`Effect`] and [`WorldEffect`] implementors and their constructors.
 //!
 //! Exploring this module is an easy way to discover what effects are available!
 //! The effects are roughly organized by the system parameter they mutate.
@@ -7,9 +7,12 @@
 //! to an equivalent impure system.
 //!
 //! [`Effect`]: crate::Effect
+//! [`WorldEffect`]: crate::WorldEffect
 
 pub mod resource;
 
+pub mod non_send;
+
 pub mod local;
 
 pub mod message;
@@ -22,9 +25,24 @@ pub mod query;
 
 pub mod query_entity;
 
+pub mod query_par;
+
 #[cfg(feature = "asset")]
 pub mod asset;
 
+#[cfg(feature = "state")]
+pub mod state;
+
+pub mod time;
+
+pub mod world;
+
+pub mod boxed;
+
+pub mod delayed;
+
+pub mod queue;
+
 pub mod algebra;
 
 pub mod iter;
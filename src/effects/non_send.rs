@@ -0,0 +1,358 @@
+//! [`Effect`]s that modify non-send resources.
+//!
+//! Non-send resources can only be accessed on the main thread. The `MutParam` of these effects is a
+//! `NonSendMut`, so any system they are piped into (like [`affect`]) is pinned to the main thread,
+//! just like an impure system with a `NonSendMut` parameter. Since piping creates a single system,
+//! this includes the pure system producing the effect. To keep the rest of the schedule parallel,
+//! prefer small systems dedicated to non-send effects over combining them with other effects.
+//!
+//! [`affect`]: crate::system_combinators::affect
+use std::any::type_name;
+use std::fmt::Debug;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::effect::Effect;
+use crate::effect_record::{EffectRecorder, RecordEffect};
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
+
+/// [`Effect`] that sets a non-send resource to the provided value.
+///
+/// Pins the system it is piped into to the main thread, see the [module-level
+/// documentation](crate::effects::non_send).
+///
+/// Can be constructed by [`non_send_set`].
+///
+/// # Example
+/// In this example, a system is written that mutes a main-thread-only `NativeVolume` handle.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// /// Main-thread-only handle to the native volume.
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct NativeVolume(u8);
+///
+/// /// Pure system using effects.
+/// fn mute_pure() -> NonSendSet<NativeVolume> {
+///     non_send_set(NativeVolume(0))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn mute_impure(mut volume: NonSendMut<NativeVolume>) {
+///     volume.0 = 0;
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(volume: NativeVolume) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_non_send(volume);
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &NativeVolume {
+/// #     world.non_send::<NativeVolume>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(volume: NativeVolume) {
+/// #         let mut pure_app = app_setup(volume);
+/// #         pure_app.add_systems(Update, mute_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(volume);
+/// #         impure_app.add_systems(Update, mute_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NonSendSet<R>
+where
+    R: 'static,
+{
+    /// The value that the non-send resource will be set to.
+    pub value: R,
+}
+
+/// Construct a new [`NonSendSet`] [`Effect`].
+pub fn non_send_set<R>(value: R) -> NonSendSet<R>
+where
+    R: 'static,
+{
+    NonSendSet { value }
+}
+
+impl<R> Effect for NonSendSet<R>
+where
+    R: 'static,
+{
+    type MutParam = NonSendMut<'static, R>;
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        **param = self.value;
+    }
+}
+
+impl<R> WorldEffect for NonSendSet<R>
+where
+    R: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<R> RecordEffect for NonSendSet<R>
+where
+    R: Debug + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`Effect`] that transforms a non-send resource with the provided `R -> R` function.
+///
+/// Pins the system it is piped into to the main thread, see the [module-level
+/// documentation](crate::effects::non_send).
+///
+/// Can be constructed by [`non_send_set_with`].
+///
+/// # Example
+/// In this example, a system is written that turns up a main-thread-only `NativeVolume` handle.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// /// Main-thread-only handle to the native volume.
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct NativeVolume(u8);
+///
+/// /// Pure system using effects.
+/// fn volume_up_pure() -> NonSendSetWith<NativeVolume> {
+///     non_send_set_with(|NativeVolume(volume)| NativeVolume(volume.saturating_add(1)))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn volume_up_impure(mut volume: NonSendMut<NativeVolume>) {
+///     volume.0 = volume.0.saturating_add(1);
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(volume: NativeVolume) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_non_send(volume);
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &NativeVolume {
+/// #     world.non_send::<NativeVolume>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(volume: NativeVolume) {
+/// #         let mut pure_app = app_setup(volume);
+/// #         pure_app.add_systems(Update, volume_up_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(volume);
+/// #         impure_app.add_systems(Update, volume_up_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(derive_more::Debug)]
+pub struct NonSendSetWith<R>
+where
+    R: 'static,
+{
+    /// The function that maps the non-send resource to its new value.
+    #[debug("{} -> {}", type_name::<&R>(), type_name::<R>())]
+    pub f: Box<dyn FnOnce(&R) -> R>,
+}
+
+/// Construct a new [`NonSendSetWith`] [`Effect`].
+pub fn non_send_set_with<F, R>(f: F) -> NonSendSetWith<R>
+where
+    F: FnOnce(&R) -> R + 'static,
+    R: 'static,
+{
+    NonSendSetWith { f: Box::new(f) }
+}
+
+impl<R> Effect for NonSendSetWith<R>
+where
+    R: 'static,
+{
+    type MutParam = NonSendMut<'static, R>;
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        **param = (self.f)(param);
+    }
+}
+
+impl<R> WorldEffect for NonSendSetWith<R>
+where
+    R: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<R> RecordEffect for NonSendSetWith<R>
+where
+    R: 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// [`WorldEffect`] that inserts a non-send resource immediately, replacing it if it already
+/// exists.
+///
+/// Since the resource may not exist yet, this can't use a `NonSendMut` parameter. It is performed
+/// with exclusive `World` access instead, which also runs on the main thread.
+///
+/// Can be constructed by [`non_send_insert`].
+///
+/// # Example
+/// In this example, a system is written that opens a main-thread-only `NativeVolume` handle.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// /// Main-thread-only handle to the native volume.
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct NativeVolume(u8);
+///
+/// /// Pure system using effects.
+/// fn open_volume_pure() -> NonSendInsert<NativeVolume> {
+///     non_send_insert(NativeVolume(50))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn open_volume_impure(world: &mut World) {
+///     world.insert_non_send(NativeVolume(50));
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(volume: Option<NativeVolume>) -> App {
+/// #     let mut app = App::new();
+/// #     if let Some(volume) = volume {
+/// #         app.insert_non_send(volume);
+/// #     }
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> Option<&NativeVolume> {
+/// #     world.get_non_send::<NativeVolume>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(volume: Option<NativeVolume>) {
+/// #         let mut pure_app = app_setup(volume);
+/// #         pure_app.add_systems(Update, open_volume_pure.pipe(affect_world));
+/// #
+/// #         let mut impure_app = app_setup(volume);
+/// #         impure_app.add_systems(Update, open_volume_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NonSendInsert<R>
+where
+    R: 'static,
+{
+    /// The value of the inserted non-send resource.
+    pub value: R,
+}
+
+/// Construct a new [`NonSendInsert`] [`WorldEffect`].
+pub fn non_send_insert<R>(value: R) -> NonSendInsert<R>
+where
+    R: 'static,
+{
+    NonSendInsert { value }
+}
+
+impl<R> WorldEffect for NonSendInsert<R>
+where
+    R: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        world.insert_non_send(self.value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::effects::one_way_fn::OneWayFn;
+    use crate::system_combinators::{affect, affect_world};
+
+    /// Test non-send resource storing a number.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    struct NumberNonSend(Rc<u128>);
+
+    proptest! {
+        #[test]
+        fn non_send_set_with_correctly_executes_one_way_function(initial: u128, f: OneWayFn) {
+            let expected = NumberNonSend(Rc::new(f.call(initial)));
+
+            let mut app = App::new();
+
+            app.insert_non_send(NumberNonSend(Rc::new(initial))).add_systems(
+                Update,
+                (move || non_send_set_with(move |NumberNonSend(n)| NumberNonSend(Rc::new(f.call(**n)))))
+                    .pipe(affect),
+            );
+
+            app.update();
+
+            prop_assert_eq!(app.world().non_send::<NumberNonSend>(), &expected);
+        }
+
+        #[test]
+        fn non_send_insert_is_visible_to_following_effects(put: u128, set: u128) {
+            let mut app = App::new();
+
+            app.add_systems(
+                Update,
+                (move || (non_send_insert(put), non_send_set(set))).pipe(affect_world),
+            );
+
+            app.update();
+
+            prop_assert_eq!(app.world().non_send::<u128>(), &set);
+        }
+    }
+}
@@ -1,10 +1,14 @@
 //! [`Effect`]s that modify `Query` data.
+use std::fmt::Debug;
 use std::marker::PhantomData;
 
 use bevy::ecs::query::{QueryData, QueryFilter, ReadOnlyQueryData};
 use bevy::prelude::*;
 
+use crate::dry_run::{DryRun, DryRunEffect, DryRunQueryDataEffect};
+use crate::effect_record::{EffectRecorder, RecordEffect};
 use crate::query_data_effect::QueryDataEffect;
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
 use crate::{Effect, EffectOut, effect_out};
 
 /// [`Effect`] that applies a [`QueryDataEffect`] to all entities in a query.
@@ -81,7 +85,7 @@ use crate::{Effect, EffectOut, effect_out};
 /// Not shown...
 /// - other [`QueryDataEffect`]s are available
 /// - the `Filter` parameter can be omitted
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, PartialEq, Eq, derive_more::Debug)]
 pub struct QueryAffect<QueryDataE, Filter = ()>
 where
     QueryDataE: QueryDataEffect,
@@ -143,6 +147,44 @@ where
     }
 }
 
+impl<QueryDataE, Filter> WorldEffect for QueryAffect<QueryDataE, Filter>
+where
+    QueryDataE: QueryDataEffect + Clone,
+    QueryDataE::MutQueryData: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<QueryDataE, Filter> RecordEffect for QueryAffect<QueryDataE, Filter>
+where
+    QueryDataE: QueryDataEffect + Clone + Debug,
+    QueryDataE::MutQueryData: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<QueryDataE, Filter> DryRunEffect for QueryAffect<QueryDataE, Filter>
+where
+    QueryDataE: DryRunQueryDataEffect + Clone,
+    QueryDataE::MutQueryData: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        dry_run
+            .matching_entities::<QueryDataE::MutQueryData, Filter>()
+            .into_iter()
+            .for_each(|entity| {
+                self.query_data_effect.clone().dry_run(entity, dry_run);
+            });
+    }
+}
+
 /// Type alias for the mapping function in [`QueryMap`] and [`QueryEntityMap`].
 ///
 /// [`QueryEntityMap`]: crate::prelude::QueryEntityMap
@@ -311,6 +353,280 @@ where
     }
 }
 
+impl<QueryDataIn, QueryDataE, Filter> WorldEffect for QueryMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<QueryDataIn, QueryDataE, Filter> RecordEffect for QueryMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<QueryDataIn, QueryDataE, Filter> DryRunEffect for QueryMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: DryRunQueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        let world = dry_run.world();
+
+        let query_data_effects = world
+            .try_query_filtered::<(Entity, QueryDataIn), (QueryDataE::Filter, Filter)>()
+            .map(|state| {
+                state
+                    .iter_manual(world)
+                    .map(|(entity, data_in)| (entity, (self.f)(data_in)))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        query_data_effects
+            .into_iter()
+            .for_each(|(entity, query_data_effect)| {
+                if dry_run.matches::<QueryDataE::MutQueryData, Filter>(entity) {
+                    query_data_effect.dry_run(entity, dry_run);
+                }
+            });
+    }
+}
+
+/// Type alias for the generics of [`QueryMapFn`] that are only used by its function's bounds.
+type QueryMapFnPhantom<QueryDataIn, QueryDataE, Filter> =
+    PhantomData<fn() -> (QueryDataIn, QueryDataE, Filter)>;
+
+/// [`Effect`] that applies a mapping of `QueryData` to [`QueryDataEffect`] to all entities in a
+/// query, without boxing the mapping function.
+///
+/// This is the unboxed alternative to [`QueryMap`]. It is generic over the function type `F`
+/// instead, so constructing it doesn't allocate and calling the function doesn't use dynamic
+/// dispatch. The function type can be hidden in the system's return type with `impl Fn`.
+///
+/// This query can be filtered with the `Filter` generic.
+///
+/// Can be constructed with [`query_map_fn`].
+///
+/// # Example
+/// In this example, a system is written that updates all entities' `Speed` component according to
+/// their `Acceleration` component.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Default, Debug, PartialEq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Acceleration(f32);
+///
+/// #[derive(Copy, Clone, Default, Debug, PartialEq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Speed(f32);
+///
+/// /// Pure system using effects.
+/// fn accelerate_pure() -> QueryMapFn<
+///     impl for<'a> Fn((&'a Acceleration, &'a Speed)) -> ComponentSet<Speed>,
+///     (&'static Acceleration, &'static Speed),
+///     ComponentSet<Speed>,
+/// > {
+///     query_map_fn(|(acceleration, speed): (&Acceleration, &Speed)| {
+///         component_set(Speed(speed.0 + acceleration.0))
+///     })
+/// }
+///
+/// /// Equivalent impure system.
+/// fn accelerate_impure(mut query: Query<(&Acceleration, &mut Speed)>) {
+///     for (acceleration, mut speed) in query.iter_mut() {
+///         speed.0 += acceleration.0
+///     }
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(component_table: Vec<(Option<Acceleration>, Option<Speed>)>) -> App {
+/// #     let mut app = App::new();
+/// #     component_table.into_iter().for_each(|(acceleration, speed)| {
+/// #         let mut entity = app.world_mut().spawn_empty();
+/// #         if let Some(acceleration) = acceleration {
+/// #             entity.insert(acceleration);
+/// #         }
+/// #         if let Some(speed) = speed {
+/// #             entity.insert(speed);
+/// #         }
+/// #     });
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn query_state(world: &mut World) -> Vec<(Entity, Option<&Acceleration>, Option<&Speed>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Acceleration>, Option<&Speed>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<(Option<Acceleration>, Option<Speed>)>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, accelerate_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table.clone());
+/// #         impure_app.add_systems(Update, accelerate_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             assert_eq!(query_state(pure_app.world_mut()), query_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - other [`QueryDataEffect`]s are available
+/// - any `QueryData` (including single components) can be input to the map function
+/// - a filter can be applied using the `Filter` generic parameter.
+#[derive(derive_more::Debug)]
+pub struct QueryMapFn<F, QueryDataIn, QueryDataE, Filter = ()>
+where
+    F: for<'w, 's> Fn(QueryDataIn::Item<'w, 's>) -> QueryDataE,
+    QueryDataIn: ReadOnlyQueryData,
+    QueryDataE: QueryDataEffect,
+    Filter: QueryFilter,
+{
+    /// The `QueryData -> QueryDataEffect` function that is applied to all entities in the query.
+    #[debug("{0} -> {1}", std::any::type_name::<QueryDataIn>(), std::any::type_name::<QueryDataE>())]
+    pub f: F,
+    #[debug(skip)]
+    phantom: QueryMapFnPhantom<QueryDataIn, QueryDataE, Filter>,
+}
+
+/// Construct a new [`QueryMapFn`] [`Effect`].
+pub fn query_map_fn<F, QueryDataIn, QueryDataE, Filter>(
+    f: F,
+) -> QueryMapFn<F, QueryDataIn, QueryDataE, Filter>
+where
+    F: for<'w, 's> Fn(QueryDataIn::Item<'w, 's>) -> QueryDataE,
+    QueryDataIn: ReadOnlyQueryData,
+    QueryDataE: QueryDataEffect,
+    Filter: QueryFilter,
+{
+    QueryMapFn {
+        f,
+        phantom: PhantomData,
+    }
+}
+
+impl<F, QueryDataIn, QueryDataE, Filter> Effect for QueryMapFn<F, QueryDataIn, QueryDataE, Filter>
+where
+    F: for<'w, 's> Fn(QueryDataIn::Item<'w, 's>) -> QueryDataE,
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    type MutParam = ParamSet<
+        'static,
+        'static,
+        (
+            Query<'static, 'static, (Entity, QueryDataIn), (QueryDataE::Filter, Filter)>,
+            Query<'static, 'static, QueryDataE::MutQueryData, Filter>,
+        ),
+    >;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        let query_data_effects = param
+            .p0()
+            .iter()
+            .map(|(entity, data_in)| (entity, (self.f)(data_in)))
+            .collect::<Vec<_>>();
+
+        query_data_effects
+            .into_iter()
+            .for_each(|(entity, query_data_effect)| {
+                query_data_effect.affect(&mut param.p1().get_mut(entity).expect("The entities in the first query are guaranteed to be a subset of the entities in the second query due to filters"));
+            })
+    }
+}
+
+impl<F, QueryDataIn, QueryDataE, Filter> WorldEffect
+    for QueryMapFn<F, QueryDataIn, QueryDataE, Filter>
+where
+    F: for<'w, 's> Fn(QueryDataIn::Item<'w, 's>) -> QueryDataE,
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<F, QueryDataIn, QueryDataE, Filter> RecordEffect
+    for QueryMapFn<F, QueryDataIn, QueryDataE, Filter>
+where
+    F: for<'w, 's> Fn(QueryDataIn::Item<'w, 's>) -> QueryDataE,
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<F, QueryDataIn, QueryDataE, Filter> DryRunEffect
+    for QueryMapFn<F, QueryDataIn, QueryDataE, Filter>
+where
+    F: for<'w, 's> Fn(QueryDataIn::Item<'w, 's>) -> QueryDataE,
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: DryRunQueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        let world = dry_run.world();
+
+        let query_data_effects = world
+            .try_query_filtered::<(Entity, QueryDataIn), (QueryDataE::Filter, Filter)>()
+            .map(|state| {
+                state
+                    .iter_manual(world)
+                    .map(|(entity, data_in)| (entity, (self.f)(data_in)))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        query_data_effects
+            .into_iter()
+            .for_each(|(entity, query_data_effect)| {
+                if dry_run.matches::<QueryDataE::MutQueryData, Filter>(entity) {
+                    query_data_effect.dry_run(entity, dry_run);
+                }
+            });
+    }
+}
+
 /// Type alias for the mapping function in [`QueryMapAnd`] and [`QueryEntityMapAnd`].
 ///
 /// [`QueryEntityMapAnd`]: crate::prelude::QueryEntityMapAnd
@@ -540,3 +856,281 @@ where
         effects.affect(&mut param.p2())
     }
 }
+
+impl<QueryDataIn, E, QueryDataE, Filter> WorldEffect
+    for QueryMapAnd<QueryDataIn, E, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    E: Effect,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<QueryDataIn, E, QueryDataE, Filter> RecordEffect
+    for QueryMapAnd<QueryDataIn, E, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    E: Effect,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// Type alias for the generics of [`QueryMapAndFn`] that are only used by its function's bounds.
+type QueryMapAndFnPhantom<QueryDataIn, E, QueryDataE, Filter> =
+    PhantomData<fn() -> (QueryDataIn, E, QueryDataE, Filter)>;
+
+/// [`Effect`] that applies a mapping of `QueryData` to [`EffectOut`] (containing an [`Effect`] and
+/// a [`QueryDataEffect`]) to all entities in a query, without boxing the mapping function.
+///
+/// This is the unboxed alternative to [`QueryMapAnd`]. It is generic over the function type `F`
+/// instead, so constructing it doesn't allocate and calling the function doesn't use dynamic
+/// dispatch. The function type can be hidden in the system's return type with `impl Fn`.
+///
+/// The query can be filtered with the `Filter` generic.
+///
+/// Can be constructed by [`query_map_and_fn`].
+///
+/// # Example
+/// In this example, a system is written that lowers all entities' `Health` by their `DamageTaken`
+/// (if they do not have an `Invincible` component). It also despawns the entities if their health
+/// is 0.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct DamageTaken(u32);
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Health(u32);
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Invincible;
+///
+/// /// Pure system using effects.
+/// fn health_and_death_pure() -> QueryMapAndFn<
+///     impl for<'a> Fn(
+///         (Entity, &'a DamageTaken, &'a Health),
+///     ) -> EffectOut<Option<EntityCommandDespawn>, ComponentSet<Health>>,
+///     (Entity, &'static DamageTaken, &'static Health),
+///     Option<EntityCommandDespawn>,
+///     ComponentSet<Health>,
+///     Without<Invincible>,
+/// > {
+///     query_map_and_fn(
+///         |(entity, damage_taken, health): (Entity, &DamageTaken, &Health)| {
+///             let new_health = health.0.saturating_sub(damage_taken.0);
+///
+///             let despawn_command = (new_health == 0).then_some(entity_command_despawn(entity));
+///
+///             effect_out(despawn_command, component_set(Health(new_health)))
+///         },
+///     )
+/// }
+///
+/// /// Equivalent impure system
+/// fn health_and_death_impure(
+///     mut query: Query<(Entity, &DamageTaken, &mut Health), Without<Invincible>>,
+///     mut commands: Commands,
+/// ) {
+///     for (entity, damage_taken, mut health) in query.iter_mut() {
+///         let new_health = health.0.saturating_sub(damage_taken.0);
+///
+///         health.0 = new_health;
+///
+///         if new_health == 0 {
+///             commands.entity(entity).despawn();
+///         }
+///     }
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(
+/// #     component_table: Vec<(Option<DamageTaken>, Option<Health>, Option<Invincible>)>,
+/// # ) -> App {
+/// #     let mut app = App::new();
+/// #     component_table
+/// #         .into_iter()
+/// #         .for_each(|(damage_taken, health, invincible)| {
+/// #             let mut entity = app.world_mut().spawn_empty();
+/// #             if let Some(damage_taken) = damage_taken {
+/// #                 entity.insert(damage_taken);
+/// #             }
+/// #             if let Some(health) = health {
+/// #                 entity.insert(health);
+/// #             }
+/// #             if let Some(invincible) = invincible {
+/// #                 entity.insert(invincible);
+/// #             }
+/// #         });
+/// #
+/// #     app
+/// # }
+/// # fn query_state(
+/// #     world: &mut World,
+/// # ) -> Vec<(
+/// #     Entity,
+/// #     Option<&DamageTaken>,
+/// #     Option<&Health>,
+/// #     Option<&Invincible>,
+/// # )> {
+/// #     let mut query = world.query::<(
+/// #         Entity,
+/// #         Option<&DamageTaken>,
+/// #         Option<&Health>,
+/// #         Option<&Invincible>,
+/// #     )>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<(Option<DamageTaken>, Option<Health>, Option<Invincible>)>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, health_and_death_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table.clone());
+/// #         impure_app.add_systems(Update, health_and_death_impure);
+/// #
+/// #         for _ in 0..20 {
+/// #             assert_eq!(query_state(pure_app.world_mut()), query_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - other [`QueryDataEffect`]s are available
+/// - any `QueryData` (including single components) can be input to the map function
+/// - other [`Effect`]s are available
+/// - the `Filter` generic can be omitted
+#[derive(derive_more::Debug)]
+pub struct QueryMapAndFn<F, QueryDataIn, E, QueryDataE, Filter = ()>
+where
+    F: for<'w, 's> Fn(QueryDataIn::Item<'w, 's>) -> EffectOut<E, QueryDataE>,
+    QueryDataIn: ReadOnlyQueryData,
+    E: Effect,
+    QueryDataE: QueryDataEffect,
+    Filter: QueryFilter,
+{
+    /// The `QueryData -> EffectOut<Effect, QueryDataEffect>` function that applies to all entities
+    /// in the query.
+    #[debug("{0} -> {1}", std::any::type_name::<QueryDataIn>(), std::any::type_name::<EffectOut<E, QueryDataE>>())]
+    pub f: F,
+    #[debug(skip)]
+    phantom: QueryMapAndFnPhantom<QueryDataIn, E, QueryDataE, Filter>,
+}
+
+/// Construct a new [`QueryMapAndFn`] [`Effect`].
+pub fn query_map_and_fn<F, QueryDataIn, E, QueryDataE, Filter>(
+    f: F,
+) -> QueryMapAndFn<F, QueryDataIn, E, QueryDataE, Filter>
+where
+    F: for<'w, 's> Fn(QueryDataIn::Item<'w, 's>) -> EffectOut<E, QueryDataE>,
+    QueryDataIn: ReadOnlyQueryData,
+    E: Effect,
+    QueryDataE: QueryDataEffect,
+    Filter: QueryFilter,
+{
+    QueryMapAndFn {
+        f,
+        phantom: PhantomData,
+    }
+}
+
+impl<F, QueryDataIn, E, QueryDataE, Filter> Effect
+    for QueryMapAndFn<F, QueryDataIn, E, QueryDataE, Filter>
+where
+    F: for<'w, 's> Fn(QueryDataIn::Item<'w, 's>) -> EffectOut<E, QueryDataE>,
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    E: Effect,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    type MutParam = ParamSet<
+        'static,
+        'static,
+        (
+            Query<'static, 'static, (Entity, QueryDataIn), (QueryDataE::Filter, Filter)>,
+            Query<'static, 'static, QueryDataE::MutQueryData, Filter>,
+            <Vec<E> as Effect>::MutParam,
+        ),
+    >;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        let EffectOut {
+            effect: effects,
+            out: query_data_effects,
+        }: EffectOut<Vec<E>, Vec<(Entity, QueryDataE)>> = param
+            .p0()
+            .iter()
+            .map(|(entity, data_in)| {
+                let EffectOut {
+                    effect,
+                    out: query_data_effect,
+                } = (self.f)(data_in);
+                effect_out(effect, (entity, query_data_effect))
+            })
+            .collect();
+
+        query_data_effects
+            .into_iter()
+            .for_each(|(entity, query_data_effect)| {
+                query_data_effect.affect(&mut param.p1().get_mut(entity).expect("The entities in the first query are guaranteed to be a subset of the entities in the second query due to filters"));
+            });
+
+        effects.affect(&mut param.p2())
+    }
+}
+
+impl<F, QueryDataIn, E, QueryDataE, Filter> WorldEffect
+    for QueryMapAndFn<F, QueryDataIn, E, QueryDataE, Filter>
+where
+    F: for<'w, 's> Fn(QueryDataIn::Item<'w, 's>) -> EffectOut<E, QueryDataE>,
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    E: Effect,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<F, QueryDataIn, E, QueryDataE, Filter> RecordEffect
+    for QueryMapAndFn<F, QueryDataIn, E, QueryDataE, Filter>
+where
+    F: for<'w, 's> Fn(QueryDataIn::Item<'w, 's>) -> EffectOut<E, QueryDataE>,
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    E: Effect,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
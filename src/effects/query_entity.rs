@@ -1,11 +1,19 @@
 //! [`Effect`]s that modify `Query` data for particular entities.
+use std::fmt::Debug;
 use std::marker::PhantomData;
 
+use bevy::ecs::component::Mutable;
 use bevy::ecs::query::{QueryFilter, ReadOnlyQueryData};
 use bevy::prelude::*;
 
+use crate::coalesce::{Coalesce, retain_last_by_key};
+use crate::dry_run::{DryRun, DryRunEffect, DryRunQueryDataEffect};
+use crate::effect_record::{EffectRecorder, RecordEffect};
 use crate::effects::query::{BoxedQueryMapAndFn, BoxedQueryMapFn};
 use crate::query_data_effect::QueryDataEffect;
+use crate::query_data_effects::ComponentSet;
+use crate::undo::{InvertibleEffect, InvertibleQueryDataEffect};
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
 use crate::{Effect, EffectOut};
 
 /// [`Effect`] that applies the given [`QueryDataEffect`] to the given entity.
@@ -95,7 +103,7 @@ use crate::{Effect, EffectOut};
 /// Not shown...
 /// - Other [`QueryDataEffect`]s are available
 /// - a filter can be applied using the `Filter` generic parameter.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, PartialEq, Eq, derive_more::Debug)]
 pub struct QueryEntityAffect<QueryDataE, Filter = ()>
 where
     QueryDataE: QueryDataEffect,
@@ -159,6 +167,78 @@ where
     }
 }
 
+impl<QueryDataE, Filter> WorldEffect for QueryEntityAffect<QueryDataE, Filter>
+where
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<QueryDataE, Filter> RecordEffect for QueryEntityAffect<QueryDataE, Filter>
+where
+    QueryDataE: QueryDataEffect + Debug,
+    QueryDataE::MutQueryData: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<QueryDataE, Filter> DryRunEffect for QueryEntityAffect<QueryDataE, Filter>
+where
+    QueryDataE: DryRunQueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        if dry_run.matches::<QueryDataE::MutQueryData, Filter>(self.entity) {
+            self.query_data_effect.dry_run(self.entity, dry_run);
+        }
+    }
+}
+
+impl<QueryDataE, Filter> InvertibleEffect for QueryEntityAffect<QueryDataE, Filter>
+where
+    QueryDataE: InvertibleQueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    Filter: QueryFilter + 'static,
+{
+    type Inverse = QueryEntityAffect<QueryDataE::Inverse, Filter>;
+
+    fn affect_invertible(
+        self,
+        param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>,
+        inverses: &mut Vec<Self::Inverse>,
+    ) {
+        let mut query_data = match param.0.get_mut(self.entity) {
+            Ok(d) => d,
+            Err(e) => {
+                Err::<(), _>(e).affect(&mut param.1);
+                return;
+            }
+        };
+
+        let inverse = self.query_data_effect.affect_invertible(&mut query_data);
+
+        inverses.push(query_entity_affect(self.entity, inverse));
+    }
+}
+
+impl<C, Filter> Coalesce for QueryEntityAffect<ComponentSet<C>, Filter>
+where
+    C: Component<Mutability = Mutable>,
+    Filter: QueryFilter + 'static,
+{
+    fn coalesce(effects: &mut Vec<Self>) {
+        retain_last_by_key(effects, |effect| effect.entity);
+    }
+}
+
 /// [`Effect`] that applies the given mapping of `QueryData` to [`QueryDataEffect`] to the given
 /// entity, and applies the [`QueryDataEffect`].
 ///
@@ -341,6 +421,59 @@ where
     }
 }
 
+impl<QueryDataIn, QueryDataE, Filter> WorldEffect
+    for QueryEntityMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<QueryDataIn, QueryDataE, Filter> RecordEffect
+    for QueryEntityMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<QueryDataIn, QueryDataE, Filter> DryRunEffect
+    for QueryEntityMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: DryRunQueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        let world = dry_run.world();
+
+        let Some(query_data_effect) = world
+            .try_query_filtered::<QueryDataIn, (QueryDataE::Filter, Filter)>()
+            .and_then(|state| state.get_manual(world, self.entity).ok().map(&self.f))
+        else {
+            return;
+        };
+
+        if dry_run.matches::<QueryDataE::MutQueryData, Filter>(self.entity) {
+            query_data_effect.dry_run(self.entity, dry_run);
+        }
+    }
+}
+
 /// [`Effect`] that applies the given mapping of `QueryData` to [`QueryDataEffect`] + [`Effect`]
 /// (as an `EffectOut<Effect, QueryDataEffect>` to the given entity, and applies the
 /// [`QueryDataEffect`] + [`Effect`].
@@ -541,3 +674,34 @@ where
         effect.affect(&mut param.p2())
     }
 }
+
+impl<QueryDataIn, E, QueryDataE, Filter> WorldEffect
+    for QueryEntityMapAnd<QueryDataIn, E, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    E: Effect,
+    E::MutParam: 'static,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<QueryDataIn, E, QueryDataE, Filter> RecordEffect
+    for QueryEntityMapAnd<QueryDataIn, E, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    E: Effect,
+    QueryDataE: QueryDataEffect,
+    QueryDataE::MutQueryData: 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
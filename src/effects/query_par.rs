@@ -0,0 +1,642 @@
+//! [`Effect`]s that modify `Query` data in parallel.
+//!
+//! These are parallel counterparts of the effects in the [`query`] module. They produce the same
+//! results, but iterate the query with `Query::par_iter` and `Query::par_iter_mut`, which can be
+//! faster for queries with many entities or expensive mapping functions.
+//!
+//! Mapping effects like [`QueryParMap`] iterate in parallel twice: once to call the mapping function,
+//! and once more to apply the resulting [`QueryDataEffect`]s to their entities' mutable query data.
+//!
+//! Queries are only iterated in parallel if `bevy`'s `multi_threaded` feature is enabled, and the
+//! `ComputeTaskPool` has more than one thread. Otherwise, they are iterated sequentially.
+//!
+//! [`query`]: crate::effects::query
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::{Mutex, PoisonError};
+
+use bevy::ecs::batching::BatchingStrategy;
+use bevy::ecs::entity::EntityHashMap;
+use bevy::ecs::query::{IterQueryData, QueryData, QueryFilter, ReadOnlyQueryData};
+use bevy::prelude::*;
+
+use crate::Effect;
+use crate::dry_run::{DryRun, DryRunEffect, DryRunQueryDataEffect};
+use crate::effect_record::{EffectRecorder, RecordEffect};
+use crate::query_data_effect::QueryDataEffect;
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
+
+/// Buffer for the items of one batch of a parallel query iteration.
+///
+/// The items are moved to the shared buffer when the batch is finished, so the shared buffer is
+/// locked once per batch rather than once per item.
+struct BatchBuffer<'a, T> {
+    buffer: Vec<T>,
+    shared: &'a Mutex<Vec<T>>,
+}
+
+impl<'a, T> BatchBuffer<'a, T> {
+    fn new(shared: &'a Mutex<Vec<T>>) -> Self {
+        BatchBuffer {
+            buffer: Vec::new(),
+            shared,
+        }
+    }
+}
+
+impl<T> Drop for BatchBuffer<'_, T> {
+    fn drop(&mut self) {
+        self.shared
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .append(&mut self.buffer);
+    }
+}
+
+/// [`Effect`] that applies a [`QueryDataEffect`] to all entities in a query, in parallel.
+///
+/// This is the parallel counterpart of [`QueryAffect`], with the same results. The batch sizes of
+/// the parallel iteration can be configured with [`QueryParAffect::batching_strategy`].
+///
+/// The query can be filtered with the `Filter` generic.
+///
+/// Can be constructed with [`query_par_affect`].
+///
+/// # Example
+/// In this example, a system is written that sets all entities' `Speed` to 0 if they have a
+/// `Brake` component.
+/// ```
+/// use bevy::ecs::batching::BatchingStrategy;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Speed(f32);
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Brake;
+///
+/// /// Pure system using effects.
+/// fn stop_all_pure() -> QueryParAffect<ComponentSet<Speed>, With<Brake>> {
+///     query_par_affect(component_set(Speed(0.0)))
+///         .batching_strategy(BatchingStrategy::new().min_batch_size(32))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn stop_all_impure(mut query: Query<&mut Speed, With<Brake>>) {
+///     query
+///         .par_iter_mut()
+///         .batching_strategy(BatchingStrategy::new().min_batch_size(32))
+///         .for_each(|mut speed| *speed = Speed(0.0));
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(component_table: Vec<(Option<Speed>, Option<Brake>)>) -> App {
+/// #     let mut app = App::new();
+/// #     component_table.into_iter().for_each(|(speed, brake)| {
+/// #         let mut entity = app.world_mut().spawn_empty();
+/// #         if let Some(speed) = speed {
+/// #             entity.insert(speed);
+/// #         }
+/// #         if let Some(brake) = brake {
+/// #             entity.insert(brake);
+/// #         }
+/// #     });
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn query_state(world: &mut World) -> Vec<(Entity, Option<&Speed>, Option<&Brake>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Speed>, Option<&Brake>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<(Option<Speed>, Option<Brake>)>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, stop_all_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table.clone());
+/// #         impure_app.add_systems(Update, stop_all_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             assert_eq!(query_state(pure_app.world_mut()), query_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - other [`QueryDataEffect`]s are available
+/// - the `Filter` parameter can be omitted
+/// - the batching strategy can be omitted
+///
+/// [`QueryAffect`]: crate::effects::query::QueryAffect
+#[derive(Clone, derive_more::Debug)]
+pub struct QueryParAffect<QueryDataE, Filter = ()>
+where
+    QueryDataE: QueryDataEffect,
+    Filter: QueryFilter,
+{
+    /// The [`QueryDataEffect`] that is applied to all entities in the query.
+    pub query_data_effect: QueryDataE,
+    /// The batching strategy of the parallel iteration.
+    pub batching_strategy: BatchingStrategy,
+    filter: PhantomData<Filter>,
+}
+
+impl<QueryDataE, Filter> QueryParAffect<QueryDataE, Filter>
+where
+    QueryDataE: QueryDataEffect,
+    Filter: QueryFilter,
+{
+    /// Construct a new [`QueryParAffect`] with the default batching strategy.
+    pub fn new(query_data_effect: QueryDataE) -> Self {
+        QueryParAffect {
+            query_data_effect,
+            batching_strategy: BatchingStrategy::default(),
+            filter: PhantomData,
+        }
+    }
+
+    /// Change the batching strategy of the parallel iteration.
+    pub fn batching_strategy(mut self, batching_strategy: BatchingStrategy) -> Self {
+        self.batching_strategy = batching_strategy;
+        self
+    }
+}
+
+/// Construct a new [`QueryParAffect`] [`Effect`].
+pub fn query_par_affect<QueryDataE, Filter>(
+    query_data_effect: QueryDataE,
+) -> QueryParAffect<QueryDataE, Filter>
+where
+    QueryDataE: QueryDataEffect,
+    Filter: QueryFilter,
+{
+    QueryParAffect::new(query_data_effect)
+}
+
+impl<QueryDataE, Filter> Default for QueryParAffect<QueryDataE, Filter>
+where
+    QueryDataE: QueryDataEffect + Default,
+    Filter: QueryFilter,
+{
+    fn default() -> Self {
+        query_par_affect(default())
+    }
+}
+
+impl<QueryDataE, Filter> Effect for QueryParAffect<QueryDataE, Filter>
+where
+    QueryDataE: QueryDataEffect + Clone + Send + Sync,
+    QueryDataE::MutQueryData: IterQueryData + 'static,
+    Filter: QueryFilter + 'static,
+{
+    type MutParam = Query<'static, 'static, QueryDataE::MutQueryData, Filter>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        let query_data_effect = &self.query_data_effect;
+
+        param
+            .par_iter_mut()
+            .batching_strategy(self.batching_strategy)
+            .for_each(|mut query_data| query_data_effect.clone().affect(&mut query_data));
+    }
+}
+
+impl<QueryDataE, Filter> WorldEffect for QueryParAffect<QueryDataE, Filter>
+where
+    QueryDataE: QueryDataEffect + Clone + Send + Sync,
+    QueryDataE::MutQueryData: IterQueryData + 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<QueryDataE, Filter> RecordEffect for QueryParAffect<QueryDataE, Filter>
+where
+    QueryDataE: QueryDataEffect + Clone + Send + Sync + Debug,
+    QueryDataE::MutQueryData: IterQueryData + 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<QueryDataE, Filter> DryRunEffect for QueryParAffect<QueryDataE, Filter>
+where
+    QueryDataE: DryRunQueryDataEffect + Clone + Send + Sync,
+    QueryDataE::MutQueryData: IterQueryData + 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        dry_run
+            .matching_entities::<QueryDataE::MutQueryData, Filter>()
+            .into_iter()
+            .for_each(|entity| {
+                self.query_data_effect.clone().dry_run(entity, dry_run);
+            });
+    }
+}
+
+/// Type alias for the mapping function in [`QueryParMap`].
+pub type BoxedQueryParMapFn<QueryDataIn, QueryDataE> =
+    Box<dyn for<'w, 's> Fn(<QueryDataIn as QueryData>::Item<'w, 's>) -> QueryDataE + Send + Sync>;
+
+/// [`Effect`] that applies a mapping of `QueryData` to [`QueryDataEffect`] to all entities in a
+/// query, in parallel.
+///
+/// This is the parallel counterpart of [`QueryMap`], with the same results. The mapping function
+/// is called in parallel, and the resulting [`QueryDataEffect`]s are then applied in parallel too.
+/// The batch sizes of both parallel iterations can be configured with
+/// [`QueryParMap::batching_strategy`].
+///
+/// The mapping function needs to be `Send + Sync`, so it can be called from multiple threads.
+///
+/// This query can be filtered with the `Filter` generic.
+///
+/// Can be constructed with [`query_par_map`].
+///
+/// # Example
+/// In this example, a system is written that updates all entities' `Speed` component according to
+/// their `Acceleration` component.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Acceleration(u32);
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Component)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Speed(u32);
+///
+/// /// Pure system using effects.
+/// fn accelerate_pure()
+/// -> QueryParMap<(&'static Acceleration, &'static Speed), ComponentSet<Speed>> {
+///     query_par_map(|(acceleration, speed): (&Acceleration, &Speed)| {
+///         component_set(Speed(speed.0.saturating_add(acceleration.0)))
+///     })
+/// }
+///
+/// /// Equivalent impure system.
+/// fn accelerate_impure(mut query: Query<(&Acceleration, &mut Speed)>) {
+///     query.par_iter_mut().for_each(|(acceleration, mut speed)| {
+///         speed.0 = speed.0.saturating_add(acceleration.0);
+///     });
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(component_table: Vec<(Option<Acceleration>, Option<Speed>)>) -> App {
+/// #     let mut app = App::new();
+/// #     component_table.into_iter().for_each(|(acceleration, speed)| {
+/// #         let mut entity = app.world_mut().spawn_empty();
+/// #         if let Some(acceleration) = acceleration {
+/// #             entity.insert(acceleration);
+/// #         }
+/// #         if let Some(speed) = speed {
+/// #             entity.insert(speed);
+/// #         }
+/// #     });
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn query_state(world: &mut World) -> Vec<(Entity, Option<&Acceleration>, Option<&Speed>)> {
+/// #     let mut query = world.query::<(Entity, Option<&Acceleration>, Option<&Speed>)>();
+/// #     query.iter(world).collect()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(component_table: Vec<(Option<Acceleration>, Option<Speed>)>) {
+/// #         let mut pure_app = app_setup(component_table.clone());
+/// #         pure_app.add_systems(Update, accelerate_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(component_table.clone());
+/// #         impure_app.add_systems(Update, accelerate_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             assert_eq!(query_state(pure_app.world_mut()), query_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - other [`QueryDataEffect`]s are available
+/// - any `QueryData` (including single components) can be input to the map function
+/// - a filter can be applied using the `Filter` generic parameter
+/// - the batching strategy can be changed with [`QueryParMap::batching_strategy`]
+///
+/// [`QueryMap`]: crate::effects::query::QueryMap
+#[derive(derive_more::Debug)]
+pub struct QueryParMap<QueryDataIn, QueryDataE, Filter = ()>
+where
+    QueryDataIn: ReadOnlyQueryData,
+    QueryDataE: QueryDataEffect,
+    Filter: QueryFilter,
+{
+    /// The `QueryData -> QueryDataEffect` function that is applied to all entities in the query.
+    #[debug("{0} -> {1}", std::any::type_name::<QueryDataIn>(), std::any::type_name::<QueryDataE>())]
+    pub f: BoxedQueryParMapFn<QueryDataIn, QueryDataE>,
+    /// The batching strategy of the parallel iteration.
+    pub batching_strategy: BatchingStrategy,
+    filter: PhantomData<Filter>,
+}
+
+impl<QueryDataIn, QueryDataE, Filter> QueryParMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData,
+    QueryDataE: QueryDataEffect,
+    Filter: QueryFilter,
+{
+    /// Construct a new [`QueryParMap`] with the default batching strategy.
+    pub fn new(f: BoxedQueryParMapFn<QueryDataIn, QueryDataE>) -> Self {
+        QueryParMap {
+            f,
+            batching_strategy: BatchingStrategy::default(),
+            filter: PhantomData,
+        }
+    }
+
+    /// Change the batching strategy of the parallel iteration.
+    pub fn batching_strategy(mut self, batching_strategy: BatchingStrategy) -> Self {
+        self.batching_strategy = batching_strategy;
+        self
+    }
+}
+
+/// Construct a new [`QueryParMap`] [`Effect`].
+pub fn query_par_map<QueryDataIn, QueryDataE, Filter, F>(
+    f: F,
+) -> QueryParMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData,
+    QueryDataE: QueryDataEffect,
+    Filter: QueryFilter,
+    F: for<'w, 's> Fn(QueryDataIn::Item<'w, 's>) -> QueryDataE + Send + Sync + 'static,
+{
+    QueryParMap::new(Box::new(f))
+}
+
+impl<QueryDataIn, QueryDataE, Filter> Default for QueryParMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData,
+    QueryDataE: QueryDataEffect + Default,
+    Filter: QueryFilter,
+{
+    fn default() -> Self {
+        query_par_map(|_| default())
+    }
+}
+
+impl<QueryDataIn, QueryDataE, Filter> Effect for QueryParMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: QueryDataEffect + Send,
+    QueryDataE::MutQueryData: IterQueryData + 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    type MutParam = ParamSet<
+        'static,
+        'static,
+        (
+            Query<'static, 'static, (Entity, QueryDataIn), (QueryDataE::Filter, Filter)>,
+            Query<'static, 'static, (Entity, QueryDataE::MutQueryData), Filter>,
+        ),
+    >;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        let f = &self.f;
+        let query_data_effects = Mutex::new(Vec::new());
+
+        param
+            .p0()
+            .par_iter()
+            .batching_strategy(self.batching_strategy.clone())
+            .for_each_init(
+                || BatchBuffer::new(&query_data_effects),
+                |batch, (entity, data_in)| {
+                    batch.buffer.push((entity, Mutex::new(Some(f(data_in)))));
+                },
+            );
+
+        // Each effect is only taken once, by the task applying it, so these locks aren't contended.
+        let query_data_effects = query_data_effects
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+            .into_iter()
+            .collect::<EntityHashMap<_>>();
+
+        param
+            .p1()
+            .par_iter_mut()
+            .batching_strategy(self.batching_strategy)
+            .for_each(|(entity, mut query_data)| {
+                let query_data_effect =
+                    query_data_effects
+                        .get(&entity)
+                        .and_then(|query_data_effect| {
+                            query_data_effect
+                                .lock()
+                                .unwrap_or_else(PoisonError::into_inner)
+                                .take()
+                        });
+
+                if let Some(query_data_effect) = query_data_effect {
+                    query_data_effect.affect(&mut query_data);
+                }
+            });
+
+        assert!(
+            query_data_effects
+                .into_iter()
+                .all(|(_, query_data_effect)| {
+                    query_data_effect
+                        .into_inner()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .is_none()
+                }),
+            "The entities in the first query are guaranteed to be a subset of the entities in the second query due to filters"
+        );
+    }
+}
+
+impl<QueryDataIn, QueryDataE, Filter> WorldEffect for QueryParMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: QueryDataEffect + Send,
+    QueryDataE::MutQueryData: IterQueryData + 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<QueryDataIn, QueryDataE, Filter> RecordEffect for QueryParMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: QueryDataEffect + Send,
+    QueryDataE::MutQueryData: IterQueryData + 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<QueryDataIn, QueryDataE, Filter> DryRunEffect for QueryParMap<QueryDataIn, QueryDataE, Filter>
+where
+    QueryDataIn: ReadOnlyQueryData + 'static,
+    QueryDataE: DryRunQueryDataEffect + Send,
+    QueryDataE::MutQueryData: IterQueryData + 'static,
+    QueryDataE::Filter: 'static,
+    Filter: QueryFilter + 'static,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        let world = dry_run.world();
+
+        let query_data_effects = world
+            .try_query_filtered::<(Entity, QueryDataIn), (QueryDataE::Filter, Filter)>()
+            .map(|state| {
+                state
+                    .iter_manual(world)
+                    .map(|(entity, data_in)| (entity, (self.f)(data_in)))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        query_data_effects
+            .into_iter()
+            .for_each(|(entity, query_data_effect)| {
+                if dry_run.matches::<QueryDataE::MutQueryData, Filter>(entity) {
+                    query_data_effect.dry_run(entity, dry_run);
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::effects::number_data::NumberComponent;
+    use crate::effects::one_way_fn::OneWayFn;
+    use crate::effects::query::query_map;
+    use crate::query_data_effects::{ComponentSet, component_set};
+    use crate::system_combinators::affect;
+
+    fn app_setup(
+        component_table: Vec<(Option<NumberComponent<0>>, Option<NumberComponent<1>>)>,
+    ) -> App {
+        let mut app = App::new();
+        component_table.into_iter().for_each(|(n0, n1)| {
+            let mut entity = app.world_mut().spawn_empty();
+            if let Some(n0) = n0 {
+                entity.insert(n0);
+            }
+            if let Some(n1) = n1 {
+                entity.insert(n1);
+            }
+        });
+
+        app
+    }
+
+    fn query_state(
+        world: &mut World,
+    ) -> Vec<(
+        Entity,
+        Option<NumberComponent<0>>,
+        Option<NumberComponent<1>>,
+    )> {
+        world
+            .query::<(
+                Entity,
+                Option<&NumberComponent<0>>,
+                Option<&NumberComponent<1>>,
+            )>()
+            .iter(world)
+            .map(|(entity, n0, n1)| (entity, n0.copied(), n1.copied()))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn query_par_map_is_equivalent_to_query_map(
+            component_table in prop::collection::vec(any::<(Option<NumberComponent<0>>, Option<NumberComponent<1>>)>(), 0..256),
+            f: OneWayFn,
+            batch_size in 1..64usize,
+        ) {
+            let mut par_app = app_setup(component_table.clone());
+            par_app.add_systems(
+                Update,
+                (move || {
+                    query_par_map::<&'static NumberComponent<0>, _, (), _>(move |n0: &NumberComponent<0>| {
+                        component_set(NumberComponent::<1>(f.call(n0.0)))
+                    })
+                    .batching_strategy(BatchingStrategy::fixed(batch_size))
+                })
+                .pipe(affect),
+            );
+
+            let mut sequential_app = app_setup(component_table);
+            sequential_app.add_systems(
+                Update,
+                (move || {
+                    query_map::<&'static NumberComponent<0>, ComponentSet<NumberComponent<1>>, (), _>(move |n0: &NumberComponent<0>| {
+                        component_set(NumberComponent::<1>(f.call(n0.0)))
+                    })
+                })
+                .pipe(affect),
+            );
+
+            par_app.update();
+            sequential_app.update();
+
+            prop_assert_eq!(query_state(par_app.world_mut()), query_state(sequential_app.world_mut()));
+        }
+
+        #[test]
+        fn query_par_affect_is_equivalent_to_sequential_set(
+            component_table in prop::collection::vec(any::<(Option<NumberComponent<0>>, Option<NumberComponent<1>>)>(), 0..256),
+            put: NumberComponent<1>,
+            batch_size in 1..64usize,
+        ) {
+            let mut app = app_setup(component_table.clone());
+            app.add_systems(
+                Update,
+                (move || {
+                    query_par_affect::<_, With<NumberComponent<0>>>(component_set(put))
+                        .batching_strategy(BatchingStrategy::fixed(batch_size))
+                })
+                .pipe(affect),
+            );
+
+            let expected = query_state(app.world_mut())
+                .into_iter()
+                .map(|(entity, n0, n1)| (entity, n0, if n0.is_some() { n1.map(|_| put) } else { n1 }))
+                .collect::<Vec<_>>();
+
+            app.update();
+
+            prop_assert_eq!(query_state(app.world_mut()), expected);
+        }
+    }
+}
@@ -0,0 +1,340 @@
+//! [`Effect`]s that are queued in a typed resource and performed together later.
+//!
+//! Requires an [`EffectQueuePlugin`] to be added to the app for each queued effect type.
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use bevy::ecs::schedule::{InternedScheduleLabel, InternedSystemSet, ScheduleLabel, SystemSet};
+use bevy::prelude::*;
+
+use crate::Effect;
+use crate::effect_record::{EffectRecorder, RecordEffect};
+use crate::system_combinators::affect;
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
+
+/// `Resource` storing effects enqueued by [`EffectEnqueue`] effects, waiting to be performed.
+///
+/// Initialized by the [`EffectQueuePlugin`] for the same effect type.
+#[derive(Debug, Resource)]
+pub struct EffectQueue<E>
+where
+    E: Effect + Send + Sync + 'static,
+{
+    effects: Vec<E>,
+}
+
+impl<E> Default for EffectQueue<E>
+where
+    E: Effect + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        EffectQueue {
+            effects: Vec::new(),
+        }
+    }
+}
+
+impl<E> EffectQueue<E>
+where
+    E: Effect + Send + Sync + 'static,
+{
+    /// The number of effects in the queue.
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    /// Returns true if there are no effects in the queue.
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Iterate over the effects in the queue, in the order they were enqueued.
+    pub fn iter(&self) -> impl Iterator<Item = &E> {
+        self.effects.iter()
+    }
+}
+
+/// `bevy` system that drains the [`EffectQueue`], returning its effects in the order they were
+/// enqueued.
+///
+/// Piped into [`affect`] by the [`EffectQueuePlugin`].
+pub fn effect_dequeue<E>(mut queue: ResMut<EffectQueue<E>>) -> Vec<E>
+where
+    E: Effect + Send + Sync + 'static,
+{
+    std::mem::take(&mut queue.effects)
+}
+
+/// `Plugin` that initializes the [`EffectQueue<E>`] resource and performs its effects at a chosen
+/// schedule point.
+///
+/// All effects in the queue are drained and performed together in a single [`affect`] system,
+/// optionally in a system set. Before being performed, the effects can be reordered, filtered, or
+/// deduplicated with a preprocessor function.
+///
+/// # Example
+/// In this example, many systems may enqueue a new `Level`, and the highest one wins.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// struct Level(u32);
+///
+/// fn level_up() -> EffectEnqueue<ResSet<Level>> {
+///     effect_enqueue(res_set(Level(3)))
+/// }
+///
+/// fn level_down() -> EffectEnqueue<ResSet<Level>> {
+///     effect_enqueue(res_set(Level(1)))
+/// }
+///
+/// let mut app = App::new();
+///
+/// app.init_resource::<Level>()
+///     .add_plugins(
+///         EffectQueuePlugin::<ResSet<Level>>::new(PostUpdate)
+///             .with_preprocessor(|effects| effects.sort_by_key(|effect| effect.value.0)),
+///     )
+///     .add_systems(Update, (level_up.pipe(affect), level_down.pipe(affect)));
+///
+/// app.update();
+///
+/// assert_eq!(app.world().resource::<Level>(), &Level(3));
+/// ```
+///
+/// [`EffectQueue<E>`]: EffectQueue
+#[derive(derive_more::Debug)]
+pub struct EffectQueuePlugin<E>
+where
+    E: Effect + Send + Sync + 'static,
+{
+    schedule: InternedScheduleLabel,
+    set: Option<InternedSystemSet>,
+    #[debug("Option<fn(&mut Vec<E>)>")]
+    preprocessor: Option<fn(&mut Vec<E>)>,
+    #[debug(skip)]
+    phantom: PhantomData<fn() -> E>,
+}
+
+impl<E> EffectQueuePlugin<E>
+where
+    E: Effect + Send + Sync + 'static,
+{
+    /// Construct a new [`EffectQueuePlugin`] that performs the queued effects in the given
+    /// schedule.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        EffectQueuePlugin {
+            schedule: schedule.intern(),
+            set: None,
+            preprocessor: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Perform the queued effects in the given system set.
+    pub fn in_set(mut self, set: impl SystemSet) -> Self {
+        self.set = Some(set.intern());
+        self
+    }
+
+    /// Process the queued effects with the given function before they are performed.
+    pub fn with_preprocessor(mut self, preprocessor: fn(&mut Vec<E>)) -> Self {
+        self.preprocessor = Some(preprocessor);
+        self
+    }
+}
+
+impl<E> Plugin for EffectQueuePlugin<E>
+where
+    E: Effect + Send + Sync + 'static,
+{
+    fn build(&self, app: &mut App) {
+        let preprocessor = self.preprocessor;
+
+        let system = effect_dequeue::<E>
+            .pipe(move |In(mut effects): In<Vec<E>>| {
+                if let Some(preprocessor) = preprocessor {
+                    preprocessor(&mut effects);
+                }
+
+                effects
+            })
+            .pipe(affect);
+
+        app.init_resource::<EffectQueue<E>>();
+
+        match self.set {
+            Some(set) => app.add_systems(self.schedule, system.in_set(set)),
+            None => app.add_systems(self.schedule, system),
+        };
+    }
+}
+
+/// [`Effect`] that pushes an effect to the [`EffectQueue`], to be performed later.
+///
+/// The effect is performed when the [`EffectQueuePlugin`] for its type drains the queue, together
+/// with all other effects in the queue.
+///
+/// Can be constructed with [`effect_enqueue`].
+///
+/// # Example
+/// In this example, a system is written that increments the `Score`, but the increment is applied
+/// in `PostUpdate`.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u32);
+///
+/// /// Pure system using effects.
+/// fn increment_score_pure(score: Res<Score>) -> EffectEnqueue<ResSet<Score>> {
+///     effect_enqueue(res_set(Score(score.0.saturating_add(1))))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn increment_score_impure(mut score: ResMut<Score>) {
+///     score.0 = score.0.saturating_add(1);
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(score: Score) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(score);
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &Score {
+/// #     world.get_resource::<Score>().unwrap()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(score: Score) {
+/// #         let mut pure_app = app_setup(score);
+/// #         pure_app
+/// #             .add_plugins(EffectQueuePlugin::<ResSet<Score>>::new(PostUpdate))
+/// #             .add_systems(Update, increment_score_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(score);
+/// #         impure_app.add_systems(Update, increment_score_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world()), resource_state(impure_app.world()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct EffectEnqueue<E>
+where
+    E: Effect + Send + Sync + 'static,
+{
+    /// The effect to enqueue.
+    pub effect: E,
+}
+
+/// Construct a new [`EffectEnqueue`] [`Effect`].
+pub fn effect_enqueue<E>(effect: E) -> EffectEnqueue<E>
+where
+    E: Effect + Send + Sync + 'static,
+{
+    EffectEnqueue { effect }
+}
+
+impl<E> Effect for EffectEnqueue<E>
+where
+    E: Effect + Send + Sync + 'static,
+{
+    type MutParam = ResMut<'static, EffectQueue<E>>;
+
+    fn affect(self, param: &mut <Self::MutParam as bevy::ecs::system::SystemParam>::Item<'_, '_>) {
+        param.effects.push(self.effect);
+    }
+}
+
+impl<E> WorldEffect for EffectEnqueue<E>
+where
+    E: Effect + Send + Sync + 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<E> RecordEffect for EffectEnqueue<E>
+where
+    E: Effect + Send + Sync + Debug + 'static,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::effects::message::{MessageWrite, message_write};
+    use crate::effects::number_data::NumberMessage;
+
+    proptest! {
+        #[test]
+        fn enqueued_effects_are_preprocessed_and_performed_together(messages in prop::collection::vec(any::<NumberMessage>(), 0..16)) {
+            let mut app = App::new();
+
+            #[derive(Debug, Clone, PartialEq, Eq, Hash, SystemSet)]
+            struct ApplyQueue;
+
+            let messages_to_enqueue = messages.clone();
+
+            app.add_message::<NumberMessage>()
+                .add_plugins(
+                    EffectQueuePlugin::<MessageWrite<NumberMessage>>::new(PostUpdate)
+                        .in_set(ApplyQueue)
+                        .with_preprocessor(|effects| {
+                            effects.sort_by_key(|effect| effect.message.0);
+                            effects.dedup();
+                        }),
+                )
+                .add_systems(
+                    Update,
+                    (move || {
+                        messages_to_enqueue
+                            .iter()
+                            .map(|message| effect_enqueue(message_write(*message)))
+                            .collect::<Vec<_>>()
+                    })
+                    .pipe(affect),
+                )
+                .add_systems(
+                    PostUpdate,
+                    (|queue: Res<EffectQueue<MessageWrite<NumberMessage>>>| {
+                        assert!(queue.is_empty());
+                    })
+                    .after(ApplyQueue),
+                );
+
+            app.update();
+
+            let mut expected = messages;
+            expected.sort_by_key(|message| message.0);
+            expected.dedup();
+
+            let written = app
+                .world()
+                .resource::<Messages<NumberMessage>>()
+                .iter_current_update_messages()
+                .copied()
+                .collect::<Vec<_>>();
+
+            prop_assert_eq!(written, expected);
+            prop_assert!(app.world().resource::<EffectQueue<MessageWrite<NumberMessage>>>().is_empty());
+        }
+    }
+}
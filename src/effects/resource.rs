@@ -1,14 +1,24 @@
 //! [`Effect`]s that modify resources.
 use std::any::type_name;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
 
 use bevy::ecs::component::Mutable;
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
+use crate::coalesce::Coalesce;
+use crate::dry_run::{DryRun, DryRunEffect};
 use crate::effect::Effect;
+use crate::effect_record::{EffectRecorder, RecordEffect};
+use crate::undo::InvertibleEffect;
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
 
 /// [`Effect`] that sets a `Resource` to the provided value.
 ///
+/// The resource must already exist. If it may not, consider [`ResSetOrInsert`] instead.
+///
 /// Can be constructed by [`res_set`].
 ///
 /// # Example
@@ -87,6 +97,57 @@ where
     }
 }
 
+impl<R> WorldEffect for ResSet<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<R> RecordEffect for ResSet<R>
+where
+    R: Resource + Component<Mutability = Mutable> + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<R> DryRunEffect for ResSet<R>
+where
+    R: Resource + Component<Mutability = Mutable> + Debug,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        dry_run.set_resource(self.value);
+    }
+}
+
+impl<R> InvertibleEffect for ResSet<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    type Inverse = ResSet<R>;
+
+    fn affect_invertible(
+        self,
+        param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>,
+        inverses: &mut Vec<Self::Inverse>,
+    ) {
+        inverses.push(res_set(std::mem::replace(&mut **param, self.value)));
+    }
+}
+
+impl<R> Coalesce for ResSet<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    fn coalesce(effects: &mut Vec<Self>) {
+        effects.drain(..effects.len().saturating_sub(1));
+    }
+}
+
 /// [`Effect`] that transforms a `Resource` with the provided `R -> R` function.
 ///
 /// Can be constructed by [`res_set_with`].
@@ -178,44 +239,1435 @@ where
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use proptest::prelude::*;
+impl<R> WorldEffect for ResSetWith<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
 
-    use super::*;
-    use crate::effects::number_data::NumberResource;
-    use crate::effects::one_way_fn::OneWayFn;
-    use crate::system_combinators::affect;
+impl<R> RecordEffect for ResSetWith<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
 
-    proptest! {
-        #[test]
-        fn res_put_overwrites_initial_state(initial: NumberResource, put: NumberResource) {
-            let mut app = App::new();
+impl<R> DryRunEffect for ResSetWith<R>
+where
+    R: Resource + Component<Mutability = Mutable> + Debug,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        if let Some(value) = dry_run.resource::<R>().map(self.f) {
+            dry_run.set_resource(value);
+        }
+    }
+}
 
-            prop_assume!(initial != put);
+/// [`Effect`] that transforms a `Resource` with the provided `R -> R` function, without boxing
+/// it.
+///
+/// This is the unboxed alternative to [`ResSetWith`]. It is generic over the function type `F`
+/// instead, so constructing it doesn't allocate and performing it doesn't use dynamic dispatch.
+/// The function type can be hidden in the system's return type with `impl FnOnce`.
+///
+/// Can be constructed by [`res_set_with_fn`].
+///
+/// # Example
+/// In this example, a system is written that increments the `Updates` by 1.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Updates(u32);
+///
+/// /// Pure system using effects.
+/// fn increment_updates_pure() -> ResSetWithFn<impl FnOnce(&Updates) -> Updates, Updates> {
+///     res_set_with_fn(|Updates(n)| Updates(n.saturating_add(1)))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn increment_updates_impure(mut updates: ResMut<Updates>) {
+///     updates.0 = updates.0.saturating_add(1);
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(updates: Updates) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(updates);
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &Updates {
+/// #     world.get_resource::<Updates>().unwrap()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(updates: Updates) {
+/// #         let mut pure_app = app_setup(updates);
+/// #         pure_app.add_systems(Update, increment_updates_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(updates);
+/// #         impure_app.add_systems(Update, increment_updates_impure);
+/// #
+/// #         for _ in 0..20 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(derive_more::Debug)]
+pub struct ResSetWithFn<F, R>
+where
+    F: FnOnce(&R) -> R,
+    R: Resource + Component<Mutability = Mutable>,
+{
+    /// The function that maps the resource to its new value.
+    #[debug("{} -> {}", type_name::<&R>(), type_name::<R>())]
+    pub f: F,
+    #[debug(skip)]
+    resource: PhantomData<fn() -> R>,
+}
 
-            app.insert_resource(initial)
-                .add_systems(Update, (move || res_set(put)).pipe(affect));
+/// Construct a new [`ResSetWithFn`] [`Effect`].
+pub fn res_set_with_fn<F, R>(f: F) -> ResSetWithFn<F, R>
+where
+    F: FnOnce(&R) -> R,
+    R: Resource + Component<Mutability = Mutable>,
+{
+    ResSetWithFn {
+        f,
+        resource: PhantomData,
+    }
+}
 
-            prop_assert_eq!(app.world().resource::<NumberResource>(), &initial);
+impl<F, R> Effect for ResSetWithFn<F, R>
+where
+    F: FnOnce(&R) -> R,
+    R: Resource + Component<Mutability = Mutable>,
+{
+    type MutParam = ResMut<'static, R>;
 
-            app.update();
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        **param = (self.f)(param);
+    }
+}
 
-            prop_assert_eq!(app.world().resource::<NumberResource>(), &put);
+impl<F, R> WorldEffect for ResSetWithFn<F, R>
+where
+    F: FnOnce(&R) -> R,
+    R: Resource + Component<Mutability = Mutable>,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<F, R> RecordEffect for ResSetWithFn<F, R>
+where
+    F: FnOnce(&R) -> R,
+    R: Resource + Component<Mutability = Mutable>,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<F, R> DryRunEffect for ResSetWithFn<F, R>
+where
+    F: FnOnce(&R) -> R,
+    R: Resource + Component<Mutability = Mutable> + Debug,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        if let Some(value) = dry_run.resource::<R>().map(self.f) {
+            dry_run.set_resource(value);
         }
+    }
+}
 
-        #[test]
-        fn res_with_correctly_executes_one_way_function(initial: NumberResource, f: OneWayFn) {
-            let expected = NumberResource(f.call(initial.0));
+/// [`Effect`] that sets a `Resource` to the provided value, only if it isn't equal to the current
+/// value.
+///
+/// Unlike [`ResSet`], this doesn't mark the resource as changed if the value stays the same, so
+/// systems reacting to its changes aren't triggered unnecessarily.
+///
+/// Can be constructed by [`res_set_if_neq`].
+///
+/// # Example
+/// In this example, a system is written that resets the `Score` to 0, without triggering change
+/// detection if it is already 0.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u32);
+///
+/// /// Pure system using effects.
+/// fn reset_score_pure() -> ResSetIfNeq<Score> {
+///     res_set_if_neq(Score(0))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn reset_score_impure(mut score: ResMut<Score>) {
+///     score.set_if_neq(Score(0));
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # struct ScoreChanges(u32);
+/// #
+/// # fn app_setup(score: Score) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(score)
+/// #         .init_resource::<ScoreChanges>()
+/// #         .add_systems(PostUpdate, |score: Res<Score>, mut changes: ResMut<ScoreChanges>| {
+/// #             if score.is_changed() {
+/// #                 changes.0 += 1;
+/// #             }
+/// #         });
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> (&Score, &ScoreChanges) {
+/// #     (world.resource::<Score>(), world.resource::<ScoreChanges>())
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(score: Score) {
+/// #         let mut pure_app = app_setup(score);
+/// #         pure_app.add_systems(Update, reset_score_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(score);
+/// #         impure_app.add_systems(Update, reset_score_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResSetIfNeq<R>
+where
+    R: Resource + Component<Mutability = Mutable> + PartialEq,
+{
+    /// The value that the resource will be set to.
+    pub value: R,
+}
 
-            let mut app = App::new();
+/// Construct a new [`ResSetIfNeq`] [`Effect`].
+pub fn res_set_if_neq<R>(value: R) -> ResSetIfNeq<R>
+where
+    R: Resource + Component<Mutability = Mutable> + PartialEq,
+{
+    ResSetIfNeq { value }
+}
 
-            app.insert_resource(initial.clone()).add_systems(
-                Update,
-                (move || res_set_with(move |&NumberResource(n)| NumberResource(f.call(n)))).pipe(affect),
-            );
+impl<R> Effect for ResSetIfNeq<R>
+where
+    R: Resource + Component<Mutability = Mutable> + PartialEq,
+{
+    type MutParam = ResMut<'static, R>;
 
-            prop_assert_eq!(app.world().resource::<NumberResource>(), &initial);
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        param.set_if_neq(self.value);
+    }
+}
+
+impl<R> WorldEffect for ResSetIfNeq<R>
+where
+    R: Resource + Component<Mutability = Mutable> + PartialEq,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<R> RecordEffect for ResSetIfNeq<R>
+where
+    R: Resource + Component<Mutability = Mutable> + PartialEq + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<R> DryRunEffect for ResSetIfNeq<R>
+where
+    R: Resource + Component<Mutability = Mutable> + PartialEq + Debug,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        if dry_run.resource::<R>() != Some(&self.value) {
+            dry_run.set_resource(self.value);
+        }
+    }
+}
+
+/// [`Effect`] that sets a `Resource` to the provided value without marking it as changed.
+///
+/// This is useful for bookkeeping writes that other systems shouldn't react to, since their change
+/// detection won't see them.
+///
+/// Can be constructed by [`res_set_bypass_change_detection`].
+///
+/// # Example
+/// In this example, a system is written that records the frame the `Score` was last checked on,
+/// without triggering change detection.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct LastChecked(u32);
+///
+/// /// Pure system using effects.
+/// fn record_check_pure(last_checked: Res<LastChecked>) -> ResSetBypassChangeDetection<LastChecked> {
+///     res_set_bypass_change_detection(LastChecked(last_checked.0.wrapping_add(1)))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn record_check_impure(mut last_checked: ResMut<LastChecked>) {
+///     let last_checked = last_checked.bypass_change_detection();
+///     last_checked.0 = last_checked.0.wrapping_add(1);
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # struct LastCheckedChanges(u32);
+/// #
+/// # fn app_setup(last_checked: LastChecked) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(last_checked)
+/// #         .init_resource::<LastCheckedChanges>()
+/// #         .add_systems(
+/// #             PostUpdate,
+/// #             |last_checked: Res<LastChecked>, mut changes: ResMut<LastCheckedChanges>| {
+/// #                 if last_checked.is_changed() {
+/// #                     changes.0 += 1;
+/// #                 }
+/// #             },
+/// #         );
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> (&LastChecked, &LastCheckedChanges) {
+/// #     (world.resource::<LastChecked>(), world.resource::<LastCheckedChanges>())
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(last_checked: LastChecked) {
+/// #         let mut pure_app = app_setup(last_checked);
+/// #         pure_app.add_systems(Update, record_check_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(last_checked);
+/// #         impure_app.add_systems(Update, record_check_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResSetBypassChangeDetection<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    /// The value that the resource will be set to.
+    pub value: R,
+}
+
+/// Construct a new [`ResSetBypassChangeDetection`] [`Effect`].
+pub fn res_set_bypass_change_detection<R>(value: R) -> ResSetBypassChangeDetection<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    ResSetBypassChangeDetection { value }
+}
+
+impl<R> Effect for ResSetBypassChangeDetection<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    type MutParam = ResMut<'static, R>;
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        *param.bypass_change_detection() = self.value;
+    }
+}
+
+impl<R> WorldEffect for ResSetBypassChangeDetection<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<R> RecordEffect for ResSetBypassChangeDetection<R>
+where
+    R: Resource + Component<Mutability = Mutable> + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<R> DryRunEffect for ResSetBypassChangeDetection<R>
+where
+    R: Resource + Component<Mutability = Mutable> + Debug,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        dry_run.set_resource(self.value);
+    }
+}
+
+/// Error produced by the `ResTry-` effects when the resource doesn't exist.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MissingResourceError {
+    /// The type name of the missing resource.
+    pub type_name: &'static str,
+}
+
+impl MissingResourceError {
+    /// Construct a new [`MissingResourceError`] for the resource `R`.
+    pub fn new<R>() -> Self
+    where
+        R: Resource,
+    {
+        MissingResourceError {
+            type_name: type_name::<R>(),
+        }
+    }
+}
+
+impl Display for MissingResourceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "resource {} does not exist", self.type_name)
+    }
+}
+
+impl Error for MissingResourceError {}
+
+/// Perform the `Err` case of a `Result` effect, handling the error with the `FallbackErrorHandler`.
+fn handle_error(
+    error: impl Into<BevyError>,
+    param: &mut <<Result<(), BevyError> as Effect>::MutParam as SystemParam>::Item<'_, '_>,
+) {
+    Err::<(), BevyError>(error.into()).affect(param);
+}
+
+/// [`Effect`] that sets a `Resource` to the provided value if it exists.
+///
+/// Unlike [`ResSet`], the system doesn't fail validation if the resource doesn't exist. Instead, a
+/// [`MissingResourceError`] is produced (handled by `bevy`'s `FallbackErrorHandler`), like any
+/// other `Result` effect.
+///
+/// Can be constructed by [`res_try_set`].
+///
+/// # Example
+/// In this example, a system is written that resets the `Score` to 0, if there is one.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u32);
+///
+/// /// Pure system using effects.
+/// fn reset_score_pure() -> ResTrySet<Score> {
+///     res_try_set(Score(0))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn reset_score_impure(score: Option<ResMut<Score>>) -> Result<(), BevyError> {
+///     score.ok_or(MissingResourceError::new::<Score>())?.0 = 0;
+///     Ok(())
+/// }
+/// # use bevy::ecs::error::{ignore, FallbackErrorHandler};
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(score: Option<Score>) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(FallbackErrorHandler(ignore));
+/// #     if let Some(score) = score {
+/// #         app.insert_resource(score);
+/// #     }
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> Option<&Score> {
+/// #     world.get_resource::<Score>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(score: Option<Score>) {
+/// #         let mut pure_app = app_setup(score);
+/// #         pure_app.add_systems(Update, reset_score_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(score);
+/// #         impure_app.add_systems(Update, reset_score_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResTrySet<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    /// The value that the resource will be set to.
+    pub value: R,
+}
+
+/// Construct a new [`ResTrySet`] [`Effect`].
+pub fn res_try_set<R>(value: R) -> ResTrySet<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    ResTrySet { value }
+}
+
+impl<R> Effect for ResTrySet<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    type MutParam = (
+        Option<ResMut<'static, R>>,
+        <Result<(), BevyError> as Effect>::MutParam,
+    );
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        match &mut param.0 {
+            Some(resource) => **resource = self.value,
+            None => handle_error(MissingResourceError::new::<R>(), &mut param.1),
+        }
+    }
+}
+
+impl<R> WorldEffect for ResTrySet<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<R> RecordEffect for ResTrySet<R>
+where
+    R: Resource + Component<Mutability = Mutable> + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<R> DryRunEffect for ResTrySet<R>
+where
+    R: Resource + Component<Mutability = Mutable> + Debug,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        if dry_run.resource::<R>().is_some() {
+            dry_run.set_resource(self.value);
+        }
+    }
+}
+
+/// [`Effect`] that transforms a `Resource` with the provided `R -> R` function if it exists.
+///
+/// Unlike [`ResSetWith`], the system doesn't fail validation if the resource doesn't exist.
+/// Instead, a [`MissingResourceError`] is produced (handled by `bevy`'s `FallbackErrorHandler`),
+/// like any other `Result` effect.
+///
+/// Can be constructed by [`res_try_set_with`].
+///
+/// # Example
+/// In this example, a system is written that increments the `Updates` by 1, if there are any.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Updates(u32);
+///
+/// /// Pure system using effects.
+/// fn increment_updates_pure() -> ResTrySetWith<Updates> {
+///     res_try_set_with(|Updates(n)| Updates(n + 1))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn increment_updates_impure(updates: Option<ResMut<Updates>>) -> Result<(), BevyError> {
+///     updates.ok_or(MissingResourceError::new::<Updates>())?.0 += 1;
+///     Ok(())
+/// }
+/// # use bevy::ecs::error::{ignore, FallbackErrorHandler};
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(updates: Option<Updates>) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(FallbackErrorHandler(ignore));
+/// #     if let Some(updates) = updates {
+/// #         app.insert_resource(updates);
+/// #     }
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> Option<&Updates> {
+/// #     world.get_resource::<Updates>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(updates: Option<Updates>) {
+/// #         let mut pure_app = app_setup(updates);
+/// #         pure_app.add_systems(Update, increment_updates_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(updates);
+/// #         impure_app.add_systems(Update, increment_updates_impure);
+/// #
+/// #         for _ in 0..20 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(derive_more::Debug)]
+pub struct ResTrySetWith<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    /// The function that maps the resource to its new value.
+    #[debug("{} -> {}", type_name::<&R>(), type_name::<R>())]
+    pub f: Box<dyn FnOnce(&R) -> R>,
+}
+
+/// Construct a new [`ResTrySetWith`] [`Effect`].
+pub fn res_try_set_with<F, R>(f: F) -> ResTrySetWith<R>
+where
+    F: FnOnce(&R) -> R + 'static,
+    R: Resource + Component<Mutability = Mutable>,
+{
+    ResTrySetWith { f: Box::new(f) }
+}
+
+impl<R> Effect for ResTrySetWith<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    type MutParam = (
+        Option<ResMut<'static, R>>,
+        <Result<(), BevyError> as Effect>::MutParam,
+    );
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        match &mut param.0 {
+            Some(resource) => **resource = (self.f)(resource),
+            None => handle_error(MissingResourceError::new::<R>(), &mut param.1),
+        }
+    }
+}
+
+impl<R> WorldEffect for ResTrySetWith<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<R> RecordEffect for ResTrySetWith<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<R> DryRunEffect for ResTrySetWith<R>
+where
+    R: Resource + Component<Mutability = Mutable> + Debug,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        if let Some(value) = dry_run.resource::<R>().map(self.f) {
+            dry_run.set_resource(value);
+        }
+    }
+}
+
+/// Type alias for the fallible mapping function used in [`ResTrySetWithResult`].
+pub type BoxedResTrySetWithResultFn<R, Er> = Box<dyn FnOnce(&R) -> Result<R, Er>>;
+
+/// [`Effect`] that transforms a `Resource` with the provided `R -> Result<R, Er>` function if it
+/// exists.
+///
+/// This is the fallible version of [`ResTrySetWith`]. Both a missing resource (as a
+/// [`MissingResourceError`]) and an `Err` returned by the function are handled by `bevy`'s
+/// `FallbackErrorHandler`, like any other `Result` effect. The resource is left unchanged in
+/// either case.
+///
+/// Can be constructed by [`res_try_set_with_result`].
+///
+/// # Example
+/// In this example, a system is written that spends a `Gold` coin, failing if there is none left.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// struct Gold(u8);
+///
+/// fn spend(Gold(gold): &Gold) -> Result<Gold, BevyError> {
+///     Ok(Gold(gold.checked_sub(1).ok_or("not enough gold")?))
+/// }
+///
+/// /// Pure system using effects.
+/// fn spend_gold_pure() -> ResTrySetWithResult<Gold, BevyError> {
+///     res_try_set_with_result(spend)
+/// }
+///
+/// /// Equivalent impure system.
+/// fn spend_gold_impure(gold: Option<ResMut<Gold>>) -> Result<(), BevyError> {
+///     let mut gold = gold.ok_or(MissingResourceError::new::<Gold>())?;
+///     *gold = spend(&gold)?;
+///     Ok(())
+/// }
+/// # use bevy::ecs::error::{ignore, FallbackErrorHandler};
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(gold: Option<Gold>) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(FallbackErrorHandler(ignore));
+/// #     if let Some(gold) = gold {
+/// #         app.insert_resource(gold);
+/// #     }
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> Option<&Gold> {
+/// #     world.get_resource::<Gold>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(gold in proptest::option::of((0..3u8).prop_map(Gold))) {
+/// #         let mut pure_app = app_setup(gold);
+/// #         pure_app.add_systems(Update, spend_gold_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(gold);
+/// #         impure_app.add_systems(Update, spend_gold_impure);
+/// #
+/// #         for _ in 0..5 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(derive_more::Debug)]
+pub struct ResTrySetWithResult<R, Er>
+where
+    R: Resource + Component<Mutability = Mutable>,
+    Er: Into<BevyError>,
+{
+    /// The function that maps the resource to its new value, or an error.
+    #[debug("{} -> {}", type_name::<&R>(), type_name::<Result<R, Er>>())]
+    pub f: BoxedResTrySetWithResultFn<R, Er>,
+}
+
+/// Construct a new [`ResTrySetWithResult`] [`Effect`].
+pub fn res_try_set_with_result<F, R, Er>(f: F) -> ResTrySetWithResult<R, Er>
+where
+    F: FnOnce(&R) -> Result<R, Er> + 'static,
+    R: Resource + Component<Mutability = Mutable>,
+    Er: Into<BevyError>,
+{
+    ResTrySetWithResult { f: Box::new(f) }
+}
+
+impl<R, Er> Effect for ResTrySetWithResult<R, Er>
+where
+    R: Resource + Component<Mutability = Mutable>,
+    Er: Into<BevyError>,
+{
+    type MutParam = (
+        Option<ResMut<'static, R>>,
+        <Result<(), BevyError> as Effect>::MutParam,
+    );
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        let Some(resource) = &mut param.0 else {
+            handle_error(MissingResourceError::new::<R>(), &mut param.1);
+            return;
+        };
+
+        match (self.f)(resource) {
+            Ok(value) => **resource = value,
+            Err(error) => handle_error(error, &mut param.1),
+        }
+    }
+}
+
+impl<R, Er> WorldEffect for ResTrySetWithResult<R, Er>
+where
+    R: Resource + Component<Mutability = Mutable>,
+    Er: Into<BevyError>,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<R, Er> RecordEffect for ResTrySetWithResult<R, Er>
+where
+    R: Resource + Component<Mutability = Mutable>,
+    Er: Into<BevyError>,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<R, Er> DryRunEffect for ResTrySetWithResult<R, Er>
+where
+    R: Resource + Component<Mutability = Mutable> + Debug,
+    Er: Into<BevyError>,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        if let Some(Ok(value)) = dry_run.resource::<R>().map(self.f) {
+            dry_run.set_resource(value);
+        }
+    }
+}
+
+/// [`WorldEffect`] that inserts a `Resource` immediately, replacing it if it already exists.
+///
+/// Unlike [`CommandInsertResource`], this isn't deferred, so the inserted resource is visible to
+/// the effects performed after it.
+///
+/// Can be constructed by [`res_insert`].
+///
+/// # Example
+/// In this example, a system is written that starts the game with a `Score` of 0.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u32);
+///
+/// /// Pure system using effects.
+/// fn start_game_pure() -> ResInsert<Score> {
+///     res_insert(Score(0))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn start_game_impure(world: &mut World) {
+///     world.insert_resource(Score(0));
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(score: Option<Score>) -> App {
+/// #     let mut app = App::new();
+/// #     if let Some(score) = score {
+/// #         app.insert_resource(score);
+/// #     }
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> Option<&Score> {
+/// #     world.get_resource::<Score>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(score: Option<Score>) {
+/// #         let mut pure_app = app_setup(score);
+/// #         pure_app.add_systems(Update, start_game_pure.pipe(affect_world));
+/// #
+/// #         let mut impure_app = app_setup(score);
+/// #         impure_app.add_systems(Update, start_game_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// [`CommandInsertResource`]: crate::effects::command::CommandInsertResource
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResInsert<R>
+where
+    R: Resource,
+{
+    /// The value of the inserted resource.
+    pub value: R,
+}
+
+/// Construct a new [`ResInsert`] [`WorldEffect`].
+pub fn res_insert<R>(value: R) -> ResInsert<R>
+where
+    R: Resource,
+{
+    ResInsert { value }
+}
+
+impl<R> WorldEffect for ResInsert<R>
+where
+    R: Resource,
+{
+    fn affect_world(self, world: &mut World) {
+        world.insert_resource(self.value);
+    }
+}
+
+/// [`WorldEffect`] that removes a `Resource` immediately, if it exists.
+///
+/// Unlike [`CommandRemoveResource`], this isn't deferred, so the resource is already missing for
+/// the effects performed after it.
+///
+/// Can be constructed by [`res_remove`].
+///
+/// # Example
+/// In this example, a system is written that removes the `GoToLevel` resource once the level
+/// transition is complete.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct GoToLevel(usize);
+///
+/// /// Pure system using effects.
+/// fn complete_level_transition_pure() -> ResRemove<GoToLevel> {
+///     res_remove::<GoToLevel>()
+/// }
+///
+/// /// Equivalent impure system.
+/// fn complete_level_transition_impure(world: &mut World) {
+///     world.remove_resource::<GoToLevel>();
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(resource: Option<GoToLevel>) -> App {
+/// #     let mut app = App::new();
+/// #
+/// #     if let Some(resource) = resource {
+/// #         app.insert_resource(resource);
+/// #     }
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &World) -> Option<&GoToLevel> {
+/// #     world.get_resource::<GoToLevel>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(resource: Option<GoToLevel>) {
+/// #         let mut pure_app = app_setup(resource);
+/// #         pure_app.add_systems(Update, complete_level_transition_pure.pipe(affect_world));
+/// #
+/// #         let mut impure_app = app_setup(resource);
+/// #         impure_app.add_systems(Update, complete_level_transition_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// [`CommandRemoveResource`]: crate::effects::command::CommandRemoveResource
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResRemove<R>
+where
+    R: Resource,
+{
+    resource: PhantomData<fn() -> R>,
+}
+
+impl<R> ResRemove<R>
+where
+    R: Resource,
+{
+    /// Construct a new [`ResRemove`]
+    pub fn new() -> Self {
+        ResRemove {
+            resource: PhantomData,
+        }
+    }
+}
+
+/// Construct a new [`ResRemove`] [`WorldEffect`].
+pub fn res_remove<R>() -> ResRemove<R>
+where
+    R: Resource,
+{
+    ResRemove::new()
+}
+
+impl<R> WorldEffect for ResRemove<R>
+where
+    R: Resource,
+{
+    fn affect_world(self, world: &mut World) {
+        world.remove_resource::<R>();
+    }
+}
+
+/// [`WorldEffect`] that initializes a `Resource` with its `FromWorld` (or `Default`) value
+/// immediately, if it doesn't exist already.
+///
+/// Can be constructed by [`res_init_default`].
+///
+/// # Example
+/// In this example, a system is written that makes sure there is a `Score`.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u32);
+///
+/// /// Pure system using effects.
+/// fn init_score_pure() -> ResInitDefault<Score> {
+///     res_init_default::<Score>()
+/// }
+///
+/// /// Equivalent impure system.
+/// fn init_score_impure(world: &mut World) {
+///     world.init_resource::<Score>();
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(score: Option<Score>) -> App {
+/// #     let mut app = App::new();
+/// #     if let Some(score) = score {
+/// #         app.insert_resource(score);
+/// #     }
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> Option<&Score> {
+/// #     world.get_resource::<Score>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(score: Option<Score>) {
+/// #         let mut pure_app = app_setup(score);
+/// #         pure_app.add_systems(Update, init_score_pure.pipe(affect_world));
+/// #
+/// #         let mut impure_app = app_setup(score);
+/// #         impure_app.add_systems(Update, init_score_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResInitDefault<R>
+where
+    R: Resource + FromWorld,
+{
+    resource: PhantomData<fn() -> R>,
+}
+
+impl<R> ResInitDefault<R>
+where
+    R: Resource + FromWorld,
+{
+    /// Construct a new [`ResInitDefault`]
+    pub fn new() -> Self {
+        ResInitDefault {
+            resource: PhantomData,
+        }
+    }
+}
+
+/// Construct a new [`ResInitDefault`] [`WorldEffect`].
+pub fn res_init_default<R>() -> ResInitDefault<R>
+where
+    R: Resource + FromWorld,
+{
+    ResInitDefault::new()
+}
+
+impl<R> WorldEffect for ResInitDefault<R>
+where
+    R: Resource + FromWorld,
+{
+    fn affect_world(self, world: &mut World) {
+        world.init_resource::<R>();
+    }
+}
+
+/// [`WorldEffect`] that sets a `Resource` to the provided value if it exists, and inserts it
+/// otherwise.
+///
+/// Unlike [`ResSet`], this doesn't require the resource to exist. Unlike [`ResInsert`], an
+/// existing resource is mutated in place rather than replaced.
+///
+/// Can be constructed by [`res_set_or_insert`].
+///
+/// # Example
+/// In this example, a system is written that records the `Score` as the `HighScore`, which may
+/// not exist yet.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Score(u32);
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct HighScore(u32);
+///
+/// /// Pure system using effects.
+/// fn record_high_score_pure(score: Res<Score>) -> ResSetOrInsert<HighScore> {
+///     res_set_or_insert(HighScore(score.0))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn record_high_score_impure(world: &mut World) {
+///     let high_score = HighScore(world.resource::<Score>().0);
+///
+///     match world.get_resource_mut::<HighScore>() {
+///         Some(mut existing) => *existing = high_score,
+///         None => world.insert_resource(high_score),
+///     }
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(score: Score, high_score: Option<HighScore>) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(score);
+/// #     if let Some(high_score) = high_score {
+/// #         app.insert_resource(high_score);
+/// #     }
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> Option<&HighScore> {
+/// #     world.get_resource::<HighScore>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(score: Score, high_score: Option<HighScore>) {
+/// #         let mut pure_app = app_setup(score, high_score);
+/// #         pure_app.add_systems(Update, record_high_score_pure.pipe(affect_world));
+/// #
+/// #         let mut impure_app = app_setup(score, high_score);
+/// #         impure_app.add_systems(Update, record_high_score_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResSetOrInsert<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    /// The value that the resource will be set to.
+    pub value: R,
+}
+
+/// Construct a new [`ResSetOrInsert`] [`WorldEffect`].
+pub fn res_set_or_insert<R>(value: R) -> ResSetOrInsert<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    ResSetOrInsert { value }
+}
+
+impl<R> WorldEffect for ResSetOrInsert<R>
+where
+    R: Resource + Component<Mutability = Mutable>,
+{
+    fn affect_world(self, world: &mut World) {
+        match world.get_resource_mut::<R>() {
+            Some(mut existing) => *existing = self.value,
+            None => world.insert_resource(self.value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use bevy::ecs::error::{ErrorContext, FallbackErrorHandler, ignore};
+    use bevy::ecs::system::RunSystemOnce;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::dry_run::StateChange;
+    use crate::effects::number_data::NumberResource;
+    use crate::effects::one_way_fn::OneWayFn;
+    use crate::system_combinators::{affect, affect_dry_run, affect_world};
+
+    proptest! {
+        #[test]
+        fn res_put_overwrites_initial_state(initial: NumberResource, put: NumberResource) {
+            let mut app = App::new();
+
+            prop_assume!(initial != put);
+
+            app.insert_resource(initial)
+                .add_systems(Update, (move || res_set(put)).pipe(affect));
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &initial);
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &put);
+        }
+
+        #[test]
+        fn res_with_correctly_executes_one_way_function(initial: NumberResource, f: OneWayFn) {
+            let expected = NumberResource(f.call(initial.0));
+
+            let mut app = App::new();
+
+            app.insert_resource(initial.clone()).add_systems(
+                Update,
+                (move || res_set_with(move |&NumberResource(n)| NumberResource(f.call(n)))).pipe(affect),
+            );
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &initial);
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &expected);
+        }
+
+        #[test]
+        fn res_set_with_fn_correctly_executes_one_way_function(initial: NumberResource, f: OneWayFn) {
+            let expected = NumberResource(f.call(initial.0));
+
+            let mut app = App::new();
+
+            app.insert_resource(initial).add_systems(
+                Update,
+                (move || res_set_with_fn(move |&NumberResource(n)| NumberResource(f.call(n)))).pipe(affect),
+            );
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &expected);
+        }
+
+        #[test]
+        fn res_set_with_dry_run_reports_change_without_mutating(initial: NumberResource, f: OneWayFn) {
+            let expected = NumberResource(f.call(initial.0));
+
+            let mut world = World::new();
+            world.insert_resource(initial);
+
+            let (diff, ()) = world
+                .run_system_once(
+                    (move || res_set_with(move |&NumberResource(n)| NumberResource(f.call(n))))
+                        .pipe(affect_dry_run),
+                )
+                .unwrap();
+
+            prop_assert_eq!(
+                diff.0,
+                vec![StateChange::Resource {
+                    type_name: type_name::<NumberResource>(),
+                    before: Some(format!("{initial:?}")),
+                    after: format!("{expected:?}"),
+                }]
+            );
+
+            prop_assert_eq!(world.resource::<NumberResource>(), &initial);
+        }
+
+        #[test]
+        fn res_insert_is_visible_to_following_effects(put: NumberResource, f: OneWayFn) {
+            let expected = NumberResource(f.call(put.0));
+
+            let mut app = App::new();
+
+            app.add_systems(
+                Update,
+                (move || {
+                    (
+                        res_insert(put),
+                        res_set_with(move |&NumberResource(n)| NumberResource(f.call(n))),
+                    )
+                })
+                .pipe(affect_world),
+            );
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &expected);
+        }
+
+        #[test]
+        fn res_remove_is_visible_to_following_effects(initial: NumberResource) {
+            let mut app = App::new();
+
+            app.insert_resource(initial).add_systems(
+                Update,
+                (|| (res_remove::<NumberResource>(), res_init_default::<NumberResource>())).pipe(affect_world),
+            );
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &NumberResource::default());
+        }
+
+        #[test]
+        fn res_set_or_insert_sets_resource_whether_or_not_it_exists(initial: Option<NumberResource>, put: NumberResource) {
+            let mut app = App::new();
+
+            if let Some(initial) = initial {
+                app.insert_resource(initial);
+            }
+
+            app.add_systems(Update, (move || res_set_or_insert(put)).pipe(affect_world));
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &put);
+        }
+
+        #[test]
+        fn res_set_if_neq_only_marks_unequal_values_changed(initial: NumberResource, value: NumberResource) {
+            let mut world = World::new();
+            world.insert_resource(initial);
+            world.clear_trackers();
+
+            world
+                .run_system_once((move || res_set_if_neq(value)).pipe(affect))
+                .unwrap();
+
+            let resource_ref = world.resource_ref::<NumberResource>();
+
+            prop_assert_eq!(*resource_ref, value);
+            prop_assert_eq!(resource_ref.is_changed(), initial != value);
+        }
+
+        #[test]
+        fn res_set_bypass_change_detection_does_not_mark_changed(initial: NumberResource, value: NumberResource) {
+            let mut world = World::new();
+            world.insert_resource(initial);
+            world.clear_trackers();
+
+            world
+                .run_system_once((move || res_set_bypass_change_detection(value)).pipe(affect))
+                .unwrap();
+
+            let resource_ref = world.resource_ref::<NumberResource>();
+
+            prop_assert_eq!(*resource_ref, value);
+            prop_assert!(!resource_ref.is_changed());
+        }
+
+        #[test]
+        fn res_try_set_handles_missing_resource(put: NumberResource) {
+            static HANDLED: Mutex<Option<MissingResourceError>> = Mutex::new(None);
+
+            fn record_missing_resource(error: BevyError, _: ErrorContext) {
+                *HANDLED.lock().unwrap() = error.downcast_ref::<MissingResourceError>().copied();
+            }
+
+            *HANDLED.lock().unwrap() = None;
+
+            let mut app = App::new();
+
+            app.insert_resource(FallbackErrorHandler(record_missing_resource))
+                .add_systems(Update, (move || res_try_set(put)).pipe(affect));
+
+            app.update();
+
+            prop_assert!(app.world().get_resource::<NumberResource>().is_none());
+            prop_assert_eq!(
+                *HANDLED.lock().unwrap(),
+                Some(MissingResourceError::new::<NumberResource>())
+            );
+        }
+
+        #[test]
+        fn res_try_set_with_result_sets_ok_and_handles_err(initial: NumberResource, f: OneWayFn, fail: bool) {
+            let expected = if fail { initial } else { NumberResource(f.call(initial.0)) };
+
+            let mut app = App::new();
+
+            app.insert_resource(FallbackErrorHandler(ignore))
+                .insert_resource(initial)
+                .add_systems(
+                    Update,
+                    (move || {
+                        res_try_set_with_result(move |&NumberResource(n)| {
+                            if fail {
+                                Err("failed")
+                            } else {
+                                Ok(NumberResource(f.call(n)))
+                            }
+                        })
+                    })
+                    .pipe(affect),
+                );
 
             app.update();
 
@@ -0,0 +1,406 @@
+//! [`Effect`]s that operate on `States`.
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::state::state::FreelyMutableState;
+
+use crate::Effect;
+use crate::dry_run::{DryRun, DryRunEffect};
+use crate::effect_record::{EffectRecorder, RecordEffect};
+use crate::effects::entity_command::{EntityCommandInsert, entity_command_insert};
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
+
+/// [`Effect`] that queues a transition to the provided state by setting `NextState<S>`.
+///
+/// Can be constructed with [`next_state_set`].
+///
+/// *Requires the `state` feature to be enabled.*
+///
+/// # Example
+/// In this example, a system is written that ends the game when the player runs out of lives.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(States, Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+/// enum GamePhase {
+///     #[default]
+///     Playing,
+///     GameOver,
+/// }
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
+/// struct Lives(u8);
+///
+/// /// Pure system using effects.
+/// fn game_over_pure(lives: Res<Lives>) -> Option<NextStateSet<GamePhase>> {
+///     (lives.0 == 0).then(|| next_state_set(GamePhase::GameOver))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn game_over_impure(lives: Res<Lives>, mut next_phase: ResMut<NextState<GamePhase>>) {
+///     if lives.0 == 0 {
+///         next_phase.set(GamePhase::GameOver);
+///     }
+/// }
+/// #
+/// # use bevy::state::app::StatesPlugin;
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(lives: u8) -> App {
+/// #     let mut app = App::new();
+/// #     app.add_plugins(StatesPlugin)
+/// #         .init_state::<GamePhase>()
+/// #         .insert_resource(Lives(lives));
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &World) -> GamePhase {
+/// #     *world.resource::<State<GamePhase>>().get()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(lives in 0..3u8) {
+/// #         let mut pure_app = app_setup(lives);
+/// #         pure_app.add_systems(Update, game_over_pure.pipe(affect));
+/// #
+/// #         let mut impure_app = app_setup(lives);
+/// #         impure_app.add_systems(Update, game_over_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(test_state(pure_app.world()), test_state(impure_app.world()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NextStateSet<S>
+where
+    S: FreelyMutableState,
+{
+    /// The state to transition to.
+    pub state: S,
+}
+
+/// Construct a new [`NextStateSet`] [`Effect`].
+///
+/// *Requires the `state` feature to be enabled.*
+pub fn next_state_set<S>(state: S) -> NextStateSet<S>
+where
+    S: FreelyMutableState,
+{
+    NextStateSet { state }
+}
+
+impl<S> Effect for NextStateSet<S>
+where
+    S: FreelyMutableState,
+{
+    type MutParam = ResMut<'static, NextState<S>>;
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        param.set(self.state);
+    }
+}
+
+impl<S> WorldEffect for NextStateSet<S>
+where
+    S: FreelyMutableState,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<S> RecordEffect for NextStateSet<S>
+where
+    S: FreelyMutableState,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<S> DryRunEffect for NextStateSet<S>
+where
+    S: FreelyMutableState,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        dry_run.set_resource(NextState::Pending(self.state));
+    }
+}
+
+/// [`Effect`] that reads the current state `S`, then supplies it to the provided effect-producing
+/// function to cause another effect.
+///
+/// Can be constructed with [`state_in_and`].
+///
+/// *Requires the `state` feature to be enabled.*
+///
+/// # Example
+/// In this example, a system is written that toggles between the running and paused states.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(States, Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+/// enum GameState {
+///     #[default]
+///     Running,
+///     Paused,
+/// }
+///
+/// /// Pure system using effects.
+/// fn toggle_pause_pure() -> StateInAnd<GameState, NextStateSet<GameState>> {
+///     state_in_and(|state| {
+///         next_state_set(match state {
+///             GameState::Running => GameState::Paused,
+///             GameState::Paused => GameState::Running,
+///         })
+///     })
+/// }
+///
+/// /// Equivalent impure system.
+/// fn toggle_pause_impure(
+///     state: Res<State<GameState>>,
+///     mut next_state: ResMut<NextState<GameState>>,
+/// ) {
+///     next_state.set(match state.get() {
+///         GameState::Running => GameState::Paused,
+///         GameState::Paused => GameState::Running,
+///     });
+/// }
+/// #
+/// # use bevy::state::app::StatesPlugin;
+/// #
+/// # fn app_setup() -> App {
+/// #     let mut app = App::new();
+/// #     app.add_plugins(StatesPlugin).init_state::<GameState>();
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &World) -> GameState {
+/// #     *world.resource::<State<GameState>>().get()
+/// # }
+/// #
+/// # fn main() {
+/// #     let mut pure_app = app_setup();
+/// #     pure_app.add_systems(Update, toggle_pause_pure.pipe(affect));
+/// #
+/// #     let mut impure_app = app_setup();
+/// #     impure_app.add_systems(Update, toggle_pause_impure);
+/// #
+/// #     for _ in 0..5 {
+/// #         assert_eq!(test_state(pure_app.world()), test_state(impure_app.world()));
+/// #         pure_app.update();
+/// #         impure_app.update();
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - in this example, a `NextStateSet` is used as the additional [`Effect`], but other
+///   [`Effect`]s are available.
+#[derive(derive_more::Debug)]
+pub struct StateInAnd<S, E>
+where
+    S: States,
+    E: Effect,
+{
+    /// The `&S -> Effect` function that may cause another effect.
+    #[debug("{0} -> {1}", std::any::type_name::<&S>(), std::any::type_name::<E>())]
+    pub f: Box<dyn FnOnce(&S) -> E>,
+}
+
+/// Construct a new [`StateInAnd`] [`Effect`].
+///
+/// *Requires the `state` feature to be enabled.*
+pub fn state_in_and<S, E, F>(f: F) -> StateInAnd<S, E>
+where
+    S: States,
+    E: Effect,
+    F: FnOnce(&S) -> E + 'static,
+{
+    StateInAnd { f: Box::new(f) }
+}
+
+impl<S, E> Effect for StateInAnd<S, E>
+where
+    S: States,
+    E: Effect,
+{
+    type MutParam = (Res<'static, State<S>>, E::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        (self.f)(param.0.get()).affect(&mut param.1);
+    }
+}
+
+impl<S, E> WorldEffect for StateInAnd<S, E>
+where
+    S: States,
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<S, E> RecordEffect for StateInAnd<S, E>
+where
+    S: States,
+    E: Effect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// Construct an [`EntityCommandInsert`] [`Effect`] that inserts `DespawnOnExit(state)`, despawning
+/// the entity when `state` is exited.
+///
+/// *Requires the `state` feature to be enabled.*
+///
+/// # Example
+/// In this example, a system is written that scopes newly spawned enemies to the `Playing` phase.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(States, Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+/// enum GamePhase {
+///     #[default]
+///     Playing,
+///     GameOver,
+/// }
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Component)]
+/// struct Enemy;
+///
+/// /// Pure system using effects.
+/// fn scope_enemies_pure(
+///     enemies: Query<Entity, Added<Enemy>>,
+/// ) -> Vec<EntityCommandInsert<DespawnOnExit<GamePhase>>> {
+///     enemies
+///         .iter()
+///         .map(|entity| despawn_on_exit(entity, GamePhase::Playing))
+///         .collect()
+/// }
+///
+/// /// Equivalent impure system.
+/// fn scope_enemies_impure(enemies: Query<Entity, Added<Enemy>>, mut commands: Commands) {
+///     for entity in &enemies {
+///         commands
+///             .entity(entity)
+///             .insert(DespawnOnExit(GamePhase::Playing));
+///     }
+/// }
+/// #
+/// # use bevy::state::app::StatesPlugin;
+/// #
+/// # fn app_setup() -> App {
+/// #     let mut app = App::new();
+/// #     app.add_plugins(StatesPlugin).init_state::<GamePhase>();
+/// #     app.world_mut().spawn_batch([Enemy; 3]);
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> usize {
+/// #     world.query::<&Enemy>().iter(world).count()
+/// # }
+/// #
+/// # fn main() {
+/// #     let mut pure_app = app_setup();
+/// #     pure_app.add_systems(Update, scope_enemies_pure.pipe(affect));
+/// #
+/// #     let mut impure_app = app_setup();
+/// #     impure_app.add_systems(Update, scope_enemies_impure);
+/// #
+/// #     for app in [&mut pure_app, &mut impure_app] {
+/// #         app.update();
+/// #         app.world_mut()
+/// #             .resource_mut::<NextState<GamePhase>>()
+/// #             .set(GamePhase::GameOver);
+/// #         app.update();
+/// #     }
+/// #
+/// #     assert_eq!(test_state(pure_app.world_mut()), 0);
+/// #     assert_eq!(test_state(impure_app.world_mut()), 0);
+/// # }
+/// ```
+pub fn despawn_on_exit<S>(entity: Entity, state: S) -> EntityCommandInsert<DespawnOnExit<S>>
+where
+    S: States,
+{
+    entity_command_insert(entity, DespawnOnExit(state))
+}
+
+/// Construct an [`EntityCommandInsert`] [`Effect`] that inserts `DespawnOnEnter(state)`,
+/// despawning the entity when `state` is entered.
+///
+/// *Requires the `state` feature to be enabled.*
+pub fn despawn_on_enter<S>(entity: Entity, state: S) -> EntityCommandInsert<DespawnOnEnter<S>>
+where
+    S: States,
+{
+    entity_command_insert(entity, DespawnOnEnter(state))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::state::app::StatesPlugin;
+    use proptest::prelude::*;
+    use proptest_derive::Arbitrary;
+
+    use super::*;
+    use crate::effects::number_data::NumberResource;
+    use crate::effects::resource::res_set;
+    use crate::system_combinators::affect;
+
+    #[derive(States, Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Arbitrary)]
+    enum NumberState {
+        #[default]
+        Zero,
+        One,
+        Two,
+    }
+
+    fn app_setup(initial: NumberState) -> App {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin).insert_state(initial);
+        app
+    }
+
+    proptest! {
+        #[test]
+        fn next_state_set_transitions_state(initial: NumberState, next: NumberState) {
+            let mut app = app_setup(initial);
+
+            app.add_systems(Update, (move || next_state_set(next)).pipe(affect));
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<State<NumberState>>().get(), &initial);
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<State<NumberState>>().get(), &next);
+        }
+
+        #[test]
+        fn state_in_and_supplies_current_state(initial: NumberState) {
+            let mut app = app_setup(initial);
+
+            app.init_resource::<NumberResource>().add_systems(
+                Update,
+                (|| state_in_and(|state: &NumberState| res_set(NumberResource(*state as u128)))).pipe(affect),
+            );
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<NumberResource>(), &NumberResource(initial as u128));
+        }
+    }
+}
@@ -0,0 +1,573 @@
+//! [`Effect`]s that depend on `Time`, and scaling effects by the timestep.
+use std::any::type_name;
+use std::fmt::Debug;
+use std::ops::Mul;
+
+use bevy::ecs::component::Mutable;
+use bevy::ecs::query::QueryFilter;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use either::Either;
+use variadics_please::all_tuples;
+
+use crate::dry_run::{DryRun, DryRunEffect};
+use crate::effect_record::{EffectRecorder, RecordEffect};
+use crate::effects::query::QueryAffect;
+use crate::effects::query_entity::QueryEntityAffect;
+use crate::query_data_effects::ComponentAdd;
+use crate::world_effect::{WorldEffect, affect_world_with_system_state};
+use crate::{Effect, QueryDataEffect};
+
+/// Type alias for the effect-producing function used in [`TimeAnd`].
+pub type BoxedTimeAndFn<E> = Box<dyn FnOnce(&Time) -> E>;
+
+/// [`Effect`] that reads the current `Time`, then supplies it to the provided effect-producing
+/// function to cause another effect.
+///
+/// `Time` is the generic clock of the schedule the effect is performed in. So, in `FixedUpdate`,
+/// it provides the fixed timestep as its delta. See [`FixedTimeAnd`] for reading the fixed time
+/// outside of `FixedUpdate`.
+///
+/// Can be constructed with [`time_and`].
+///
+/// # Example
+/// In this example, a system is written in `FixedUpdate` that integrates the travelled distance.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Resource)]
+/// struct Distance(f32);
+///
+/// const SPEED: f32 = 10.0;
+///
+/// /// Pure system using effects.
+/// fn travel_pure() -> TimeAnd<ResSetWith<Distance>> {
+///     time_and(|time| {
+///         let delta = time.delta_secs();
+///         res_set_with(move |distance: &Distance| Distance(distance.0 + SPEED * delta))
+///     })
+/// }
+///
+/// /// Equivalent impure system.
+/// fn travel_impure(time: Res<Time>, mut distance: ResMut<Distance>) {
+///     distance.0 += SPEED * time.delta_secs();
+/// }
+/// #
+/// # use std::time::Duration;
+/// #
+/// # use bevy::time::{TimePlugin, TimeUpdateStrategy};
+/// #
+/// # fn app_setup() -> App {
+/// #     let mut app = App::new();
+/// #     app.add_plugins(TimePlugin)
+/// #         .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(20)))
+/// #         .init_resource::<Distance>();
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &Distance {
+/// #     world.resource::<Distance>()
+/// # }
+/// #
+/// # fn main() {
+/// #     let mut pure_app = app_setup();
+/// #     pure_app.add_systems(FixedUpdate, travel_pure.pipe(affect));
+/// #
+/// #     let mut impure_app = app_setup();
+/// #     impure_app.add_systems(FixedUpdate, travel_impure);
+/// #
+/// #     for _ in 0..10 {
+/// #         assert_eq!(resource_state(pure_app.world()), resource_state(impure_app.world()));
+/// #         pure_app.update();
+/// #         impure_app.update();
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - in this example, a `ResSetWith` is used as the additional [`Effect`], but other [`Effect`]s
+///   are available.
+/// - for effects that only need to be scaled by the timestep, see [`TimeScaled`].
+#[derive(derive_more::Debug)]
+pub struct TimeAnd<E>
+where
+    E: Effect,
+{
+    /// The `&Time -> Effect` function that may cause another effect.
+    #[debug("{0} -> {1}", type_name::<&Time>(), type_name::<E>())]
+    pub f: BoxedTimeAndFn<E>,
+}
+
+/// Construct a new [`TimeAnd`] [`Effect`].
+pub fn time_and<E, F>(f: F) -> TimeAnd<E>
+where
+    E: Effect,
+    F: FnOnce(&Time) -> E + 'static,
+{
+    TimeAnd { f: Box::new(f) }
+}
+
+impl<E> Effect for TimeAnd<E>
+where
+    E: Effect,
+{
+    type MutParam = (Res<'static, Time>, E::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        (self.f)(&param.0).affect(&mut param.1);
+    }
+}
+
+impl<E> WorldEffect for TimeAnd<E>
+where
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<E> RecordEffect for TimeAnd<E>
+where
+    E: Effect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// Type alias for the effect-producing function used in [`FixedTimeAnd`].
+pub type BoxedFixedTimeAndFn<E> = Box<dyn FnOnce(&Time<Fixed>) -> E>;
+
+/// [`Effect`] that reads the current `Time<Fixed>`, then supplies it to the provided
+/// effect-producing function to cause another effect.
+///
+/// Unlike [`TimeAnd`], this provides the fixed clock in any schedule, including its overstep. This
+/// is useful for interpolating between fixed steps in `Update`.
+///
+/// Can be constructed with [`fixed_time_and`].
+///
+/// # Example
+/// In this example, a system is written in `Update` that stores how far the fixed clock has
+/// progressed into the next step, for interpolation.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Resource)]
+/// struct Interpolation(f32);
+///
+/// /// Pure system using effects.
+/// fn interpolate_pure() -> FixedTimeAnd<ResSet<Interpolation>> {
+///     fixed_time_and(|fixed| res_set(Interpolation(fixed.overstep_fraction())))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn interpolate_impure(fixed: Res<Time<Fixed>>, mut interpolation: ResMut<Interpolation>) {
+///     interpolation.0 = fixed.overstep_fraction();
+/// }
+/// #
+/// # use std::time::Duration;
+/// #
+/// # use bevy::time::{TimePlugin, TimeUpdateStrategy};
+/// #
+/// # fn app_setup() -> App {
+/// #     let mut app = App::new();
+/// #     app.add_plugins(TimePlugin)
+/// #         .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(20)))
+/// #         .init_resource::<Interpolation>();
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &Interpolation {
+/// #     world.resource::<Interpolation>()
+/// # }
+/// #
+/// # fn main() {
+/// #     let mut pure_app = app_setup();
+/// #     pure_app.add_systems(Update, interpolate_pure.pipe(affect));
+/// #
+/// #     let mut impure_app = app_setup();
+/// #     impure_app.add_systems(Update, interpolate_impure);
+/// #
+/// #     for _ in 0..10 {
+/// #         assert_eq!(resource_state(pure_app.world()), resource_state(impure_app.world()));
+/// #         pure_app.update();
+/// #         impure_app.update();
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - in this example, a `ResSet` is used as the additional [`Effect`], but other [`Effect`]s are
+///   available.
+#[derive(derive_more::Debug)]
+pub struct FixedTimeAnd<E>
+where
+    E: Effect,
+{
+    /// The `&Time<Fixed> -> Effect` function that may cause another effect.
+    #[debug("{0} -> {1}", type_name::<&Time<Fixed>>(), type_name::<E>())]
+    pub f: BoxedFixedTimeAndFn<E>,
+}
+
+/// Construct a new [`FixedTimeAnd`] [`Effect`].
+pub fn fixed_time_and<E, F>(f: F) -> FixedTimeAnd<E>
+where
+    E: Effect,
+    F: FnOnce(&Time<Fixed>) -> E + 'static,
+{
+    FixedTimeAnd { f: Box::new(f) }
+}
+
+impl<E> Effect for FixedTimeAnd<E>
+where
+    E: Effect,
+{
+    type MutParam = (Res<'static, Time<Fixed>>, E::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        (self.f)(&param.0).affect(&mut param.1);
+    }
+}
+
+impl<E> WorldEffect for FixedTimeAnd<E>
+where
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<E> RecordEffect for FixedTimeAnd<E>
+where
+    E: Effect,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+/// Effects that add a delta to a component, which can be scaled, e.g. by the timestep.
+///
+/// Used by [`TimeScaled`]. Implemented for [`ComponentAdd`], and for [`QueryAffect`] and
+/// [`QueryEntityAffect`] of [`ComponentDeltaScale`] query-data effects.
+///
+/// # Algebra
+/// [`ComponentDeltaScale`] is implemented for some algebraic types too:
+/// - `Option<T>` (where `T` is [`ComponentDeltaScale`])
+/// - `Either<L, R>` where (`L` and `R` are [`ComponentDeltaScale`])
+/// - `Vec<T>` (where `T` is [`ComponentDeltaScale`])
+/// - Tuples of [`ComponentDeltaScale`]s.
+pub trait ComponentDeltaScale {
+    /// Scale the delta of this effect by `scale`.
+    fn delta_scale(self, scale: f32) -> Self;
+}
+
+impl<C> ComponentDeltaScale for ComponentAdd<C>
+where
+    C: Component<Mutability = Mutable> + std::ops::AddAssign + Mul<f32, Output = C>,
+{
+    fn delta_scale(self, scale: f32) -> Self {
+        ComponentAdd {
+            delta: self.delta * scale,
+        }
+    }
+}
+
+impl<QueryDataE, Filter> ComponentDeltaScale for QueryAffect<QueryDataE, Filter>
+where
+    QueryDataE: QueryDataEffect + ComponentDeltaScale,
+    Filter: QueryFilter,
+{
+    fn delta_scale(mut self, scale: f32) -> Self {
+        self.query_data_effect = self.query_data_effect.delta_scale(scale);
+        self
+    }
+}
+
+impl<QueryDataE, Filter> ComponentDeltaScale for QueryEntityAffect<QueryDataE, Filter>
+where
+    QueryDataE: QueryDataEffect + ComponentDeltaScale,
+    Filter: QueryFilter,
+{
+    fn delta_scale(mut self, scale: f32) -> Self {
+        self.query_data_effect = self.query_data_effect.delta_scale(scale);
+        self
+    }
+}
+
+impl<T> ComponentDeltaScale for Option<T>
+where
+    T: ComponentDeltaScale,
+{
+    fn delta_scale(self, scale: f32) -> Self {
+        self.map(|t| t.delta_scale(scale))
+    }
+}
+
+impl<L, R> ComponentDeltaScale for Either<L, R>
+where
+    L: ComponentDeltaScale,
+    R: ComponentDeltaScale,
+{
+    fn delta_scale(self, scale: f32) -> Self {
+        self.map_either(|l| l.delta_scale(scale), |r| r.delta_scale(scale))
+    }
+}
+
+impl<T> ComponentDeltaScale for Vec<T>
+where
+    T: ComponentDeltaScale,
+{
+    fn delta_scale(self, scale: f32) -> Self {
+        self.into_iter().map(|t| t.delta_scale(scale)).collect()
+    }
+}
+
+macro_rules! impl_delta_scale {
+    ($(($T:ident, $t:ident)),*) => {
+        impl<$($T),*> ComponentDeltaScale for ($($T,)*)
+        where $($T: ComponentDeltaScale,)*
+        {
+            fn delta_scale(self, scale: f32) -> Self {
+                let ($($t,)*) = self;
+
+                ($($t.delta_scale(scale),)*)
+            }
+        }
+    }
+}
+
+all_tuples!(impl_delta_scale, 1, 15, T, t);
+
+/// [`Effect`] that scales the provided [`ComponentDeltaScale`] effect by the current `Time` delta, in
+/// seconds, before performing it.
+///
+/// Like [`TimeAnd`], this reads the generic clock, so in `FixedUpdate` the effect is scaled by the
+/// fixed timestep.
+///
+/// Can be constructed with [`time_scaled`].
+///
+/// # Example
+/// In this example, a system is written in `FixedUpdate` that applies gravity to all heights.
+/// ```
+/// use std::ops::{AddAssign, Mul};
+///
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Component)]
+/// struct Height(f32);
+///
+/// impl AddAssign for Height {
+///     fn add_assign(&mut self, rhs: Height) {
+///         self.0 += rhs.0;
+///     }
+/// }
+///
+/// impl Mul<f32> for Height {
+///     type Output = Height;
+///
+///     fn mul(self, rhs: f32) -> Height {
+///         Height(self.0 * rhs)
+///     }
+/// }
+///
+/// const GRAVITY: f32 = -9.8;
+///
+/// /// Pure system using effects.
+/// fn fall_pure() -> TimeScaled<QueryAffect<ComponentAdd<Height>>> {
+///     time_scaled(query_affect(component_add(Height(GRAVITY))))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn fall_impure(time: Res<Time>, mut query: Query<&mut Height>) {
+///     for mut height in &mut query {
+///         height.0 += GRAVITY * time.delta_secs();
+///     }
+/// }
+/// #
+/// # use std::time::Duration;
+/// #
+/// # use bevy::time::{TimePlugin, TimeUpdateStrategy};
+/// #
+/// # fn app_setup() -> App {
+/// #     let mut app = App::new();
+/// #     app.add_plugins(TimePlugin)
+/// #         .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(20)));
+/// #     app.world_mut().spawn_batch([Height(0.0), Height(10.0), Height(100.0)]);
+/// #     app
+/// # }
+/// #
+/// # fn test_state(world: &mut World) -> Vec<Height> {
+/// #     world.query::<&Height>().iter(world).copied().collect()
+/// # }
+/// #
+/// # fn main() {
+/// #     let mut pure_app = app_setup();
+/// #     pure_app.add_systems(FixedUpdate, fall_pure.pipe(affect));
+/// #
+/// #     let mut impure_app = app_setup();
+/// #     impure_app.add_systems(FixedUpdate, fall_impure);
+/// #
+/// #     for _ in 0..10 {
+/// #         assert_eq!(test_state(pure_app.world_mut()), test_state(impure_app.world_mut()));
+/// #         pure_app.update();
+/// #         impure_app.update();
+/// #     }
+/// # }
+/// ```
+///
+/// Not shown...
+/// - in this example, a `QueryAffect` is used as the scaled [`Effect`], but other
+///   [`ComponentDeltaScale`] effects are available.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimeScaled<E>
+where
+    E: Effect + ComponentDeltaScale,
+{
+    /// The [`Effect`] that will be scaled by the timestep.
+    pub effect: E,
+}
+
+/// Construct a new [`TimeScaled`] [`Effect`].
+pub fn time_scaled<E>(effect: E) -> TimeScaled<E>
+where
+    E: Effect + ComponentDeltaScale,
+{
+    TimeScaled { effect }
+}
+
+impl<E> Effect for TimeScaled<E>
+where
+    E: Effect + ComponentDeltaScale,
+{
+    type MutParam = (Res<'static, Time>, E::MutParam);
+
+    fn affect(self, param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+        self.effect
+            .delta_scale(param.0.delta_secs())
+            .affect(&mut param.1);
+    }
+}
+
+impl<E> WorldEffect for TimeScaled<E>
+where
+    E: Effect + ComponentDeltaScale,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self, world);
+    }
+}
+
+impl<E> RecordEffect for TimeScaled<E>
+where
+    E: Effect + ComponentDeltaScale + Debug,
+{
+    fn record(self, recorder: &mut EffectRecorder) {
+        recorder.record(&self);
+    }
+}
+
+impl<E> DryRunEffect for TimeScaled<E>
+where
+    E: DryRunEffect + ComponentDeltaScale,
+{
+    fn dry_run(self, dry_run: &mut DryRun<'_>) {
+        if let Some(scale) = dry_run.resource::<Time>().map(Time::delta_secs) {
+            self.effect.delta_scale(scale).dry_run(dry_run);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::AddAssign;
+    use std::time::Duration;
+
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::effects::query::query_affect;
+    use crate::effects::resource::res_set_with;
+    use crate::query_data_effects::component_add;
+    use crate::system_combinators::affect;
+
+    /// Test component storing a distance.
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Component)]
+    struct Distance(f32);
+
+    impl AddAssign for Distance {
+        fn add_assign(&mut self, rhs: Distance) {
+            self.0 += rhs.0;
+        }
+    }
+
+    impl Mul<f32> for Distance {
+        type Output = Distance;
+
+        fn mul(self, rhs: f32) -> Distance {
+            Distance(self.0 * rhs)
+        }
+    }
+
+    /// Test resource storing a duration.
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+    struct LastDelta(Duration);
+
+    proptest! {
+        #[test]
+        fn time_and_supplies_time_delta(millis: u16) {
+            let delta = Duration::from_millis(u64::from(millis));
+
+            let mut time = Time::<()>::default();
+            time.advance_by(delta);
+
+            let mut app = App::new();
+
+            app.insert_resource(time)
+                .init_resource::<LastDelta>()
+                .add_systems(
+                    Update,
+                    (|| time_and(|time| {
+                        let delta = time.delta();
+                        res_set_with(move |_: &LastDelta| LastDelta(delta))
+                    }))
+                    .pipe(affect),
+                );
+
+            app.update();
+
+            prop_assert_eq!(app.world().resource::<LastDelta>(), &LastDelta(delta));
+        }
+
+        #[test]
+        fn time_scaled_scales_component_add_by_time_delta(initial: u16, delta: u16, millis: u16) {
+            let mut time = Time::<()>::default();
+            time.advance_by(Duration::from_millis(u64::from(millis)));
+            let scale = time.delta_secs();
+
+            let mut app = App::new();
+
+            let entity = app.world_mut().spawn(Distance(f32::from(initial))).id();
+            app.insert_resource(time).add_systems(
+                Update,
+                (move || time_scaled(query_affect::<_, ()>(component_add(Distance(f32::from(delta))))))
+                    .pipe(affect),
+            );
+
+            app.update();
+
+            let mut expected = Distance(f32::from(initial));
+            expected += Distance(f32::from(delta)) * scale;
+
+            prop_assert_eq!(app.world().get::<Distance>(entity), Some(&expected));
+        }
+    }
+}
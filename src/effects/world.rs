@@ -0,0 +1,220 @@
+//! [`WorldEffect`]s that require exclusive `World` access.
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+
+use crate::world_effect::affect_world_with_system_state;
+use crate::{Effect, WorldEffect};
+
+/// [`WorldEffect`] that runs the schedule with the provided label.
+///
+/// Can be constructed by [`world_run_schedule`].
+///
+/// # Example
+/// In this example, a system is written that runs a `Simulation` schedule when the simulation is
+/// not paused.
+/// ```
+/// use bevy::ecs::schedule::ScheduleLabel;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, ScheduleLabel)]
+/// struct Simulation;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Paused(bool);
+///
+/// /// Pure system using effects.
+/// fn run_simulation_pure(paused: Res<Paused>) -> Option<WorldRunSchedule<Simulation>> {
+///     (!paused.0).then_some(world_run_schedule(Simulation))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn run_simulation_impure(world: &mut World) {
+///     if !world.resource::<Paused>().0 {
+///         world.run_schedule(Simulation);
+///     }
+/// }
+/// #
+/// # use proptest::prelude::*;
+/// #
+/// # #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Resource)]
+/// # struct Steps(u32);
+/// #
+/// # fn app_setup(paused: Paused) -> App {
+/// #     let mut app = App::new();
+/// #     app.insert_resource(paused)
+/// #         .init_resource::<Steps>()
+/// #         .add_systems(Simulation, |mut steps: ResMut<Steps>| steps.0 += 1);
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &Steps {
+/// #     world.get_resource::<Steps>().unwrap()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(paused: Paused) {
+/// #         let mut pure_app = app_setup(paused);
+/// #         pure_app.add_systems(Update, run_simulation_pure.pipe(affect_world));
+/// #
+/// #         let mut impure_app = app_setup(paused);
+/// #         impure_app.add_systems(Update, run_simulation_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #              prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #              pure_app.update();
+/// #              impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WorldRunSchedule<L>
+where
+    L: ScheduleLabel,
+{
+    /// The label of the schedule to run.
+    pub label: L,
+}
+
+/// Construct a new [`WorldRunSchedule`] [`WorldEffect`].
+pub fn world_run_schedule<L>(label: L) -> WorldRunSchedule<L>
+where
+    L: ScheduleLabel,
+{
+    WorldRunSchedule { label }
+}
+
+impl<L> WorldEffect for WorldRunSchedule<L>
+where
+    L: ScheduleLabel,
+{
+    fn affect_world(self, world: &mut World) {
+        world.run_schedule(self.label);
+    }
+}
+
+/// [`WorldEffect`] that performs the provided [`Effect`].
+///
+/// This library's [`Effect`]s are [`WorldEffect`]s already. This wrapper is for performing other
+/// [`Effect`]s as [`WorldEffect`]s, like those defined with the [`Effect`] derive macro.
+///
+/// Can be constructed by [`as_world_effect`].
+///
+/// # Example
+/// In this example, a system is written that performs a custom [`Effect`] and runs a schedule in
+/// the same pipeline.
+/// ```
+/// use bevy::ecs::schedule::ScheduleLabel;
+/// use bevy::ecs::system::assert_is_system;
+/// use bevy::ecs::system::SystemParam;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, ScheduleLabel)]
+/// struct Simulation;
+///
+/// /// Custom effect that logs a message.
+/// struct Log(&'static str);
+///
+/// impl Effect for Log {
+///     type MutParam = ();
+///
+///     fn affect(self, _: &mut <Self::MutParam as SystemParam>::Item<'_, '_>) {
+///         info!("{}", self.0);
+///     }
+/// }
+///
+/// fn run_simulation() -> (AsWorldEffect<Log>, WorldRunSchedule<Simulation>) {
+///     (
+///         as_world_effect(Log("running simulation")),
+///         world_run_schedule(Simulation),
+///     )
+/// }
+///
+/// assert_is_system(run_simulation.pipe(affect_world));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AsWorldEffect<E>
+where
+    E: Effect,
+{
+    /// The effect to perform.
+    pub effect: E,
+}
+
+/// Construct a new [`AsWorldEffect`] [`WorldEffect`].
+pub fn as_world_effect<E>(effect: E) -> AsWorldEffect<E>
+where
+    E: Effect,
+{
+    AsWorldEffect { effect }
+}
+
+impl<E> WorldEffect for AsWorldEffect<E>
+where
+    E: Effect,
+    E::MutParam: 'static,
+{
+    fn affect_world(self, world: &mut World) {
+        affect_world_with_system_state(self.effect, world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::effects::command::command_spawn;
+    use crate::effects::number_data::{NumberComponent, NumberResource};
+    use crate::effects::resource::res_set;
+    use crate::system_combinators::affect_world;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, ScheduleLabel)]
+    struct TestSchedule;
+
+    proptest! {
+        #[test]
+        fn effects_and_world_effects_are_performed_in_order(initial: NumberResource, put: NumberResource) {
+            let mut app = App::new();
+
+            app.insert_resource(initial)
+                .add_systems(TestSchedule, |n: Res<NumberResource>, mut commands: Commands| {
+                    commands.spawn(NumberComponent::<0>(n.0));
+                })
+                .add_systems(
+                    Update,
+                    (move || {
+                        (
+                            res_set(put),
+                            world_run_schedule(TestSchedule),
+                            command_spawn(NumberComponent::<1>(put.0)),
+                        )
+                    })
+                    .pipe(affect_world),
+                );
+
+            app.update();
+
+            let world = app.world_mut();
+
+            prop_assert_eq!(world.resource::<NumberResource>(), &put);
+
+            let n0s = world
+                .query::<&NumberComponent<0>>()
+                .iter(world)
+                .copied()
+                .collect::<Vec<_>>();
+            prop_assert_eq!(n0s, vec![NumberComponent::<0>(put.0)]);
+
+            let n1s = world
+                .query::<&NumberComponent<1>>()
+                .iter(world)
+                .copied()
+                .collect::<Vec<_>>();
+            prop_assert_eq!(n1s, vec![NumberComponent::<1>(put.0)]);
+        }
+    }
+}
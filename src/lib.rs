@@ -16,11 +16,13 @@
 //! This crate provides the following set of [feature flags]:
 //! - `derive`: enables the [`Effect`] derive macro for structs and enums of effects
 //! - `asset`: enables the `bevy/bevy_asset` feature and [`Asset`-related effects]
+//! - `state`: enables the `bevy/bevy_state` feature and [`States`-related effects]
 //!
 //! None of these are enabled by default.
 //!
 //! [feature flags]: https://doc.rust-lang.org/cargo/reference/features.html#the-features-section
 //! [`Asset`-related effects]: effects::asset
+//! [`States`-related effects]: effects::state
 #![warn(missing_docs)]
 #![deny(rustdoc::all)]
 
@@ -32,6 +34,12 @@ pub use effect_out::{EffectOut, effect_out};
 
 pub mod effects;
 
+mod world_effect;
+pub use world_effect::WorldEffect;
+
+mod coalesce;
+pub use coalesce::Coalesce;
+
 mod query_data_effect;
 pub use query_data_effect::QueryDataEffect;
 
@@ -41,6 +49,12 @@ pub mod system_combinators;
 
 pub mod effect_composition;
 
+pub mod effect_record;
+
+pub mod dry_run;
+
+pub mod undo;
+
 pub mod prelude;
 
 /// Derive macro for the [`Effect`] trait. See that trait for more details.
@@ -2,50 +2,144 @@
 
 pub use either::Either;
 
+pub use crate::dry_run::{DryRunEffect, DryRunQueryDataEffect, StateDiff};
+pub use crate::effect_record::{RecordEffect, RecordedEffects};
+pub use crate::effects::boxed::{BoxedEffect, boxed_effect};
 pub use crate::effects::command::{
+    CommandInsertBatch,
     CommandInsertResource,
     CommandQueue,
+    CommandRegisterSystemAnd,
     CommandRemoveResource,
+    CommandRunSystem,
+    CommandRunSystemWith,
     CommandSpawn,
     CommandSpawnAnd,
+    CommandSpawnAndFn,
+    CommandSpawnBatch,
     CommandTrigger,
+    CommandTriggerWith,
+    CommandTryInsertBatch,
+    command_add_observer,
+    command_insert_batch,
     command_insert_resource,
     command_queue,
+    command_register_system_and,
     command_remove_resource,
+    command_run_system,
+    command_run_system_with,
     command_spawn,
     command_spawn_and,
+    command_spawn_and_fn,
+    command_spawn_batch,
     command_trigger,
+    command_trigger_with,
+    command_try_insert_batch,
+};
+pub use crate::effects::delayed::{
+    CancelDelayed,
+    Delayed,
+    DelayedEffectsPlugin,
+    DelayedHandle,
+    after,
+    after_frames,
+    cancel_delayed,
 };
 pub use crate::effects::entity_command::{
+    CloneAll,
+    CloneAllow,
+    CloneDeny,
+    CloneFilter,
+    EntityCommandAnd,
+    EntityCommandCloneAnd,
+    EntityCommandCloneComponents,
     EntityCommandDespawn,
     EntityCommandInsert,
+    EntityCommandInsertIfNew,
     EntityCommandInsertRecursive,
+    EntityCommandObserve,
     EntityCommandQueue,
     EntityCommandRemove,
     EntityCommandRemoveRecursive,
+    EntityCommandRestore,
+    EntityCommandTrigger,
+    EntityCommandTryDespawn,
+    EntityCommandTryInsert,
+    PartialBundleError,
+    entity_command_and,
+    entity_command_clone_and,
+    entity_command_clone_components,
     entity_command_despawn,
     entity_command_insert,
+    entity_command_insert_if_new,
     entity_command_insert_recursive,
+    entity_command_observe,
     entity_command_queue,
     entity_command_remove,
     entity_command_remove_recursive,
+    entity_command_restore,
+    entity_command_trigger,
+    entity_command_try_despawn,
+    entity_command_try_insert,
+};
+pub use crate::effects::error::{
+    AffectOrHandle,
+    AffectOrHandleFn,
+    affect_or_handle,
+    affect_or_handle_fn,
 };
-pub use crate::effects::error::{AffectOrHandle, affect_or_handle};
 pub use crate::effects::iter::{AffectMany, affect_many};
-pub use crate::effects::local::{LocalSetAnd, local_set_and};
+pub use crate::effects::local::{
+    LocalCounter,
+    LocalOnce,
+    LocalPush,
+    LocalSet,
+    LocalSetAnd,
+    LocalSetAndFn,
+    LocalSetWith,
+    local_counter,
+    local_once,
+    local_push,
+    local_set,
+    local_set_and,
+    local_set_and_fn,
+    local_set_with,
+};
 pub use crate::effects::message::{
     MessageWrite,
+    MessageWriteBatch,
+    MessagesClear,
+    MessagesCollectAnd,
+    MessagesFoldAnd,
     MessagesReadAnd,
+    MessagesReadAndFn,
     message_write,
+    message_write_batch,
+    messages_clear,
+    messages_collect_and,
+    messages_fold_and,
     messages_read_and,
+    messages_read_and_fn,
+};
+pub use crate::effects::non_send::{
+    NonSendInsert,
+    NonSendSet,
+    NonSendSetWith,
+    non_send_insert,
+    non_send_set,
+    non_send_set_with,
 };
 pub use crate::effects::query::{
     QueryAffect,
     QueryMap,
     QueryMapAnd,
+    QueryMapAndFn,
+    QueryMapFn,
     query_affect,
     query_map,
     query_map_and,
+    query_map_and_fn,
+    query_map_fn,
 };
 pub use crate::effects::query_entity::{
     QueryEntityAffect,
@@ -55,22 +149,101 @@ pub use crate::effects::query_entity::{
     query_entity_map,
     query_entity_map_and,
 };
-pub use crate::effects::resource::{ResSet, ResSetWith, res_set, res_set_with};
+pub use crate::effects::query_par::{QueryParAffect, QueryParMap, query_par_affect, query_par_map};
+pub use crate::effects::queue::{EffectEnqueue, EffectQueue, EffectQueuePlugin, effect_enqueue};
+pub use crate::effects::resource::{
+    MissingResourceError,
+    ResInitDefault,
+    ResInsert,
+    ResRemove,
+    ResSet,
+    ResSetBypassChangeDetection,
+    ResSetIfNeq,
+    ResSetOrInsert,
+    ResSetWith,
+    ResSetWithFn,
+    ResTrySet,
+    ResTrySetWith,
+    ResTrySetWithResult,
+    res_init_default,
+    res_insert,
+    res_remove,
+    res_set,
+    res_set_bypass_change_detection,
+    res_set_if_neq,
+    res_set_or_insert,
+    res_set_with,
+    res_set_with_fn,
+    res_try_set,
+    res_try_set_with,
+    res_try_set_with_result,
+};
+pub use crate::effects::time::{
+    ComponentDeltaScale,
+    FixedTimeAnd,
+    TimeAnd,
+    TimeScaled,
+    fixed_time_and,
+    time_and,
+    time_scaled,
+};
+pub use crate::effects::world::{
+    AsWorldEffect,
+    WorldRunSchedule,
+    as_world_effect,
+    world_run_schedule,
+};
 #[cfg(feature = "asset")]
 pub use crate::effects::{
     asset::AssetAddAnd,
     asset::AssetInsert,
     asset::AssetServerLoadAnd,
+    asset::AssetServerLoadAndFn,
     asset::asset_add_and,
     asset::asset_insert,
     asset::asset_server_load_and,
+    asset::asset_server_load_and_fn,
+};
+#[cfg(feature = "state")]
+pub use crate::effects::{
+    state::NextStateSet,
+    state::StateInAnd,
+    state::despawn_on_enter,
+    state::despawn_on_exit,
+    state::next_state_set,
+    state::state_in_and,
+};
+pub use crate::query_data_effects::{
+    ComponentAdd,
+    ComponentSet,
+    ComponentSetBypassChangeDetection,
+    ComponentSetIfNeq,
+    ComponentsSet,
+    ComponentsSetBypassChangeDetection,
+    ComponentsSetIfNeq,
+    component_add,
+    component_set,
+    component_set_bypass_change_detection,
+    component_set_if_neq,
+    components_set,
+    components_set_bypass_change_detection,
+    components_set_if_neq,
 };
-pub use crate::query_data_effects::{ComponentSet, ComponentsSet, component_set, components_set};
 pub use crate::system_combinators::{
     affect,
+    affect_dry_run,
+    affect_each_message,
+    affect_each_message_out,
+    affect_undoable,
+    affect_world,
+    in_and_coalesce,
     in_and_extend,
     in_and_then,
     in_and_then_compose,
     pure,
+    record,
+    redo,
+    undo,
 };
-pub use crate::{Effect, EffectOut, QueryDataEffect, effect_out};
+pub use crate::undo::{InvertibleEffect, InvertibleQueryDataEffect, UndoStack};
+pub use crate::{Coalesce, Effect, EffectOut, QueryDataEffect, WorldEffect, effect_out};
@@ -1,8 +1,11 @@
 use bevy::ecs::query::QueryData;
+use bevy::prelude::Entity;
 use either::Either;
 use variadics_please::all_tuples;
 
+use crate::dry_run::{DryRun, DryRunQueryDataEffect};
 use crate::query_data_effect::QueryDataEffect;
+use crate::undo::InvertibleQueryDataEffect;
 
 macro_rules! impl_query_data_effect {
     ($(($QDE:ident, $qde:ident, $qd:ident)),*) => {
@@ -19,6 +22,34 @@ macro_rules! impl_query_data_effect {
                 $($qde.affect($qd));*
             }
         }
+
+        impl<$($QDE),*> DryRunQueryDataEffect for ($($QDE,)*)
+        where $($QDE: DryRunQueryDataEffect,)*
+        {
+            #[allow(unused_variables)]
+            fn dry_run(self, entity: Entity, dry_run: &mut DryRun<'_>) {
+                let ($($qde,)*) = self;
+
+                $($qde.dry_run(entity, dry_run);)*
+            }
+        }
+
+        impl<$($QDE),*> InvertibleQueryDataEffect for ($($QDE,)*)
+        where $($QDE: InvertibleQueryDataEffect,)*
+        {
+            type Inverse = ($(<$QDE as InvertibleQueryDataEffect>::Inverse,)*);
+
+            #[allow(clippy::unused_unit)]
+            fn affect_invertible(
+                self,
+                query_data: &mut <Self::MutQueryData as QueryData>::Item<'_, '_>,
+            ) -> Self::Inverse {
+                let ($($qde,)*) = self;
+                let ($($qd,)*) = query_data;
+
+                ($($qde.affect_invertible($qd),)*)
+            }
+        }
     }
 }
 
@@ -46,6 +77,41 @@ where
     }
 }
 
+impl<QDE0, QDE1> DryRunQueryDataEffect for Either<QDE0, QDE1>
+where
+    QDE0: DryRunQueryDataEffect,
+    QDE1: DryRunQueryDataEffect,
+{
+    fn dry_run(self, entity: Entity, dry_run: &mut DryRun<'_>) {
+        match self {
+            Either::Left(query_data_effect) => query_data_effect.dry_run(entity, dry_run),
+            Either::Right(query_data_effect) => query_data_effect.dry_run(entity, dry_run),
+        }
+    }
+}
+
+impl<QDE0, QDE1> InvertibleQueryDataEffect for Either<QDE0, QDE1>
+where
+    QDE0: InvertibleQueryDataEffect,
+    QDE1: InvertibleQueryDataEffect,
+{
+    type Inverse = Either<QDE0::Inverse, QDE1::Inverse>;
+
+    fn affect_invertible(
+        self,
+        query_data: &mut <Self::MutQueryData as QueryData>::Item<'_, '_>,
+    ) -> Self::Inverse {
+        match self {
+            Either::Left(query_data_effect) => {
+                Either::Left(query_data_effect.affect_invertible(&mut query_data.0))
+            }
+            Either::Right(query_data_effect) => {
+                Either::Right(query_data_effect.affect_invertible(&mut query_data.1))
+            }
+        }
+    }
+}
+
 impl<QDE> QueryDataEffect for Option<QDE>
 where
     QDE: QueryDataEffect,
@@ -63,6 +129,31 @@ where
     }
 }
 
+impl<QDE> DryRunQueryDataEffect for Option<QDE>
+where
+    QDE: DryRunQueryDataEffect,
+{
+    fn dry_run(self, entity: Entity, dry_run: &mut DryRun<'_>) {
+        if let Some(query_data_effect) = self {
+            query_data_effect.dry_run(entity, dry_run);
+        }
+    }
+}
+
+impl<QDE> InvertibleQueryDataEffect for Option<QDE>
+where
+    QDE: InvertibleQueryDataEffect,
+{
+    type Inverse = Option<QDE::Inverse>;
+
+    fn affect_invertible(
+        self,
+        query_data: &mut <Self::MutQueryData as QueryData>::Item<'_, '_>,
+    ) -> Self::Inverse {
+        self.map(|query_data_effect| query_data_effect.affect_invertible(&mut query_data.0))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bevy::prelude::*;
@@ -1,9 +1,14 @@
+use std::fmt::Debug;
+use std::ops::AddAssign;
+
 use bevy::ecs::component::Mutable;
 use bevy::ecs::query::QueryData;
 use bevy::prelude::*;
 use variadics_please::all_tuples;
 
 use crate::QueryDataEffect;
+use crate::dry_run::{DryRun, DryRunQueryDataEffect};
+use crate::undo::InvertibleQueryDataEffect;
 
 /// [`QueryDataEffect`] that sets a component to the given value.
 ///
@@ -39,6 +44,29 @@ where
     }
 }
 
+impl<C> DryRunQueryDataEffect for ComponentSet<C>
+where
+    C: Component<Mutability = Mutable> + Debug,
+{
+    fn dry_run(self, entity: Entity, dry_run: &mut DryRun<'_>) {
+        dry_run.set_component(entity, self.component);
+    }
+}
+
+impl<C> InvertibleQueryDataEffect for ComponentSet<C>
+where
+    C: Component<Mutability = Mutable>,
+{
+    type Inverse = ComponentSet<C>;
+
+    fn affect_invertible(
+        self,
+        query_data: &mut <Self::MutQueryData as QueryData>::Item<'_, '_>,
+    ) -> Self::Inverse {
+        component_set(std::mem::replace(&mut **query_data, self.component))
+    }
+}
+
 /// [`QueryDataEffect`] that sets multiple (up to 15) components to the given values.
 ///
 /// If you want to set single component, see [`ComponentSet`].
@@ -71,11 +99,301 @@ macro_rules! impl_query_data_effect_for_components_set {
                 $(**$q = $c);*
             }
         }
+
+        impl<$($C,)*> DryRunQueryDataEffect for ComponentsSet<($($C,)*)>
+        where
+            $($C: Component<Mutability = Mutable> + Debug),*
+        {
+            fn dry_run(self, entity: Entity, dry_run: &mut DryRun<'_>) {
+                let ($($c,)*) = self.components;
+
+                $(dry_run.set_component(entity, $c);)*
+            }
+        }
+
+        impl<$($C,)*> InvertibleQueryDataEffect for ComponentsSet<($($C,)*)>
+        where
+            $($C: Component<Mutability = Mutable>),*
+        {
+            type Inverse = Self;
+
+            fn affect_invertible(
+                self,
+                query_data: &mut <Self::MutQueryData as QueryData>::Item<'_, '_>,
+            ) -> Self::Inverse {
+                let ($($q,)*) = query_data;
+                let ($($c,)*) = self.components;
+
+                components_set(($(std::mem::replace(&mut **$q, $c),)*))
+            }
+        }
     }
 }
 
 all_tuples!(impl_query_data_effect_for_components_set, 1, 15, C, q, c);
 
+/// [`QueryDataEffect`] that sets a component to the given value, only if it isn't equal to the
+/// current value.
+///
+/// Unlike [`ComponentSet`], this doesn't mark the component as changed if the value stays the
+/// same, so `Changed<C>` filters aren't triggered unnecessarily.
+///
+/// Can be constructed by [`component_set_if_neq`].
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct ComponentSetIfNeq<C>
+where
+    C: Component<Mutability = Mutable> + PartialEq,
+{
+    /// The value to set the component to.
+    pub component: C,
+}
+
+/// Constructs a [`ComponentSetIfNeq`] [`QueryDataEffect`].
+pub fn component_set_if_neq<C>(component: C) -> ComponentSetIfNeq<C>
+where
+    C: Component<Mutability = Mutable> + PartialEq,
+{
+    ComponentSetIfNeq { component }
+}
+
+impl<C> QueryDataEffect for ComponentSetIfNeq<C>
+where
+    C: Component<Mutability = Mutable> + PartialEq,
+{
+    type MutQueryData = &'static mut C;
+    type Filter = With<C>;
+
+    fn affect(self, query_data: &mut <Self::MutQueryData as QueryData>::Item<'_, '_>) {
+        query_data.set_if_neq(self.component);
+    }
+}
+
+impl<C> DryRunQueryDataEffect for ComponentSetIfNeq<C>
+where
+    C: Component<Mutability = Mutable> + PartialEq + Debug,
+{
+    fn dry_run(self, entity: Entity, dry_run: &mut DryRun<'_>) {
+        if dry_run.component::<C>(entity) != Some(&self.component) {
+            dry_run.set_component(entity, self.component);
+        }
+    }
+}
+
+/// [`QueryDataEffect`] that sets multiple (up to 15) components to the given values, only setting
+/// those that aren't equal to their current values.
+///
+/// Unlike [`ComponentsSet`], this doesn't mark components as changed if their values stay the
+/// same, so `Changed<C>` filters aren't triggered unnecessarily.
+///
+/// Can be constructed by [`components_set_if_neq`].
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct ComponentsSetIfNeq<Cs> {
+    /// The values to set the components to.
+    pub components: Cs,
+}
+
+/// Constructs a [`ComponentsSetIfNeq`] [`QueryDataEffect`].
+pub fn components_set_if_neq<Cs>(components: Cs) -> ComponentsSetIfNeq<Cs> {
+    ComponentsSetIfNeq { components }
+}
+
+macro_rules! impl_query_data_effect_for_components_set_if_neq {
+    ($(($C:ident, $q:ident, $c:ident)),*) => {
+        impl<$($C,)*> QueryDataEffect for ComponentsSetIfNeq<($($C,)*)>
+        where
+            $($C: Component<Mutability = Mutable> + PartialEq),*
+        {
+            type MutQueryData = ($(&'static mut $C,)*);
+            type Filter = ($(With<$C>,)*);
+
+            fn affect(self, query_data: &mut <Self::MutQueryData as QueryData>::Item<'_, '_>) {
+                let ($($q,)*) = query_data;
+                let ($($c,)*) = self.components;
+
+                $($q.set_if_neq($c);)*
+            }
+        }
+
+        impl<$($C,)*> DryRunQueryDataEffect for ComponentsSetIfNeq<($($C,)*)>
+        where
+            $($C: Component<Mutability = Mutable> + PartialEq + Debug),*
+        {
+            fn dry_run(self, entity: Entity, dry_run: &mut DryRun<'_>) {
+                let ($($c,)*) = self.components;
+
+                $(
+                    if dry_run.component::<$C>(entity) != Some(&$c) {
+                        dry_run.set_component(entity, $c);
+                    }
+                )*
+            }
+        }
+    }
+}
+
+all_tuples!(
+    impl_query_data_effect_for_components_set_if_neq,
+    1,
+    15,
+    C,
+    q,
+    c
+);
+
+/// [`QueryDataEffect`] that sets a component to the given value without marking it as changed.
+///
+/// This is useful for bookkeeping writes that other systems shouldn't react to, since
+/// `Changed<C>` filters won't see them.
+///
+/// Can be constructed by [`component_set_bypass_change_detection`].
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct ComponentSetBypassChangeDetection<C>
+where
+    C: Component<Mutability = Mutable>,
+{
+    /// The value to set the component to.
+    pub component: C,
+}
+
+/// Constructs a [`ComponentSetBypassChangeDetection`] [`QueryDataEffect`].
+pub fn component_set_bypass_change_detection<C>(
+    component: C,
+) -> ComponentSetBypassChangeDetection<C>
+where
+    C: Component<Mutability = Mutable>,
+{
+    ComponentSetBypassChangeDetection { component }
+}
+
+impl<C> QueryDataEffect for ComponentSetBypassChangeDetection<C>
+where
+    C: Component<Mutability = Mutable>,
+{
+    type MutQueryData = &'static mut C;
+    type Filter = With<C>;
+
+    fn affect(self, query_data: &mut <Self::MutQueryData as QueryData>::Item<'_, '_>) {
+        *query_data.bypass_change_detection() = self.component;
+    }
+}
+
+impl<C> DryRunQueryDataEffect for ComponentSetBypassChangeDetection<C>
+where
+    C: Component<Mutability = Mutable> + Debug,
+{
+    fn dry_run(self, entity: Entity, dry_run: &mut DryRun<'_>) {
+        dry_run.set_component(entity, self.component);
+    }
+}
+
+/// [`QueryDataEffect`] that sets multiple (up to 15) components to the given values without
+/// marking them as changed.
+///
+/// This is useful for bookkeeping writes that other systems shouldn't react to, since
+/// `Changed<C>` filters won't see them.
+///
+/// Can be constructed by [`components_set_bypass_change_detection`].
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct ComponentsSetBypassChangeDetection<Cs> {
+    /// The values to set the components to.
+    pub components: Cs,
+}
+
+/// Constructs a [`ComponentsSetBypassChangeDetection`] [`QueryDataEffect`].
+pub fn components_set_bypass_change_detection<Cs>(
+    components: Cs,
+) -> ComponentsSetBypassChangeDetection<Cs> {
+    ComponentsSetBypassChangeDetection { components }
+}
+
+macro_rules! impl_query_data_effect_for_components_set_bypass_change_detection {
+    ($(($C:ident, $q:ident, $c:ident)),*) => {
+        impl<$($C,)*> QueryDataEffect for ComponentsSetBypassChangeDetection<($($C,)*)>
+        where
+            $($C: Component<Mutability = Mutable>),*
+        {
+            type MutQueryData = ($(&'static mut $C,)*);
+            type Filter = ($(With<$C>,)*);
+
+            fn affect(self, query_data: &mut <Self::MutQueryData as QueryData>::Item<'_, '_>) {
+                let ($($q,)*) = query_data;
+                let ($($c,)*) = self.components;
+
+                $(*$q.bypass_change_detection() = $c;)*
+            }
+        }
+
+        impl<$($C,)*> DryRunQueryDataEffect for ComponentsSetBypassChangeDetection<($($C,)*)>
+        where
+            $($C: Component<Mutability = Mutable> + Debug),*
+        {
+            fn dry_run(self, entity: Entity, dry_run: &mut DryRun<'_>) {
+                let ($($c,)*) = self.components;
+
+                $(dry_run.set_component(entity, $c);)*
+            }
+        }
+    }
+}
+
+all_tuples!(
+    impl_query_data_effect_for_components_set_bypass_change_detection,
+    1,
+    15,
+    C,
+    q,
+    c
+);
+
+/// [`QueryDataEffect`] that adds the given delta to a component.
+///
+/// Since the delta is usually a rate of change, this effect can be scaled by the timestep with
+/// [`TimeScaled`].
+///
+/// Can be constructed by [`component_add`].
+///
+/// [`TimeScaled`]: crate::effects::time::TimeScaled
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct ComponentAdd<C>
+where
+    C: Component<Mutability = Mutable> + AddAssign,
+{
+    /// The value to add to the component.
+    pub delta: C,
+}
+
+/// Constructs a [`ComponentAdd`] [`QueryDataEffect`].
+pub fn component_add<C>(delta: C) -> ComponentAdd<C>
+where
+    C: Component<Mutability = Mutable> + AddAssign,
+{
+    ComponentAdd { delta }
+}
+
+impl<C> QueryDataEffect for ComponentAdd<C>
+where
+    C: Component<Mutability = Mutable> + AddAssign,
+{
+    type MutQueryData = &'static mut C;
+    type Filter = With<C>;
+
+    fn affect(self, query_data: &mut <Self::MutQueryData as QueryData>::Item<'_, '_>) {
+        **query_data += self.delta;
+    }
+}
+
+impl<C> DryRunQueryDataEffect for ComponentAdd<C>
+where
+    C: Component<Mutability = Mutable> + AddAssign + Clone + Debug,
+{
+    fn dry_run(self, entity: Entity, dry_run: &mut DryRun<'_>) {
+        if let Some(mut component) = dry_run.component::<C>(entity).cloned() {
+            component += self.delta;
+            dry_run.set_component(entity, component);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::*;
@@ -126,5 +444,91 @@ mod tests {
                 &components.1
             );
         }
+
+        #[test]
+        fn component_set_if_neq_only_marks_unequal_values_changed(initial: NumberComponent<0>, component: NumberComponent<0>) {
+            let mut app = App::new();
+
+            let entity = app.world_mut().spawn(initial).id();
+            app.world_mut().clear_trackers();
+
+            let component_set_if_neq = component_set_if_neq(component);
+
+            app.world_mut()
+                .query::<<ComponentSetIfNeq<NumberComponent<0>> as QueryDataEffect>::MutQueryData>()
+                .iter_mut(app.world_mut())
+                .for_each(|mut query_data| component_set_if_neq.affect(&mut query_data));
+
+            let component_ref = app.world().entity(entity).get_ref::<NumberComponent<0>>().unwrap();
+
+            prop_assert_eq!(*component_ref, component);
+            prop_assert_eq!(component_ref.is_changed(), initial != component);
+        }
+
+        #[test]
+        fn component_set_bypass_change_detection_does_not_mark_changed(initial: NumberComponent<0>, component: NumberComponent<0>) {
+            let mut app = App::new();
+
+            let entity = app.world_mut().spawn(initial).id();
+            app.world_mut().clear_trackers();
+
+            let component_set = component_set_bypass_change_detection(component);
+
+            app.world_mut()
+                .query::<<ComponentSetBypassChangeDetection<NumberComponent<0>> as QueryDataEffect>::MutQueryData>()
+                .iter_mut(app.world_mut())
+                .for_each(|mut query_data| component_set.affect(&mut query_data));
+
+            let component_ref = app.world().entity(entity).get_ref::<NumberComponent<0>>().unwrap();
+
+            prop_assert_eq!(*component_ref, component);
+            prop_assert!(!component_ref.is_changed());
+        }
+
+        #[test]
+        fn components_set_if_neq_only_marks_unequal_values_changed(initial: (NumberComponent<0>, NumberComponent<1>), components: (NumberComponent<0>, NumberComponent<1>)) {
+            let mut app = App::new();
+
+            let entity = app.world_mut().spawn(initial).id();
+            app.world_mut().clear_trackers();
+
+            let components_set_if_neq = components_set_if_neq(components);
+
+            app.world_mut()
+                .query::<<ComponentsSetIfNeq<(NumberComponent<0>, NumberComponent<1>)> as QueryDataEffect>::MutQueryData>()
+                .iter_mut(app.world_mut())
+                .for_each(|mut query_data| components_set_if_neq.affect(&mut query_data));
+
+            let component_ref_0 = app.world().entity(entity).get_ref::<NumberComponent<0>>().unwrap();
+            let component_ref_1 = app.world().entity(entity).get_ref::<NumberComponent<1>>().unwrap();
+
+            prop_assert_eq!(*component_ref_0, components.0);
+            prop_assert_eq!(*component_ref_1, components.1);
+            prop_assert_eq!(component_ref_0.is_changed(), initial.0 != components.0);
+            prop_assert_eq!(component_ref_1.is_changed(), initial.1 != components.1);
+        }
+
+        #[test]
+        fn components_set_bypass_change_detection_does_not_mark_changed(initial: (NumberComponent<0>, NumberComponent<1>), components: (NumberComponent<0>, NumberComponent<1>)) {
+            let mut app = App::new();
+
+            let entity = app.world_mut().spawn(initial).id();
+            app.world_mut().clear_trackers();
+
+            let components_set = components_set_bypass_change_detection(components);
+
+            app.world_mut()
+                .query::<<ComponentsSetBypassChangeDetection<(NumberComponent<0>, NumberComponent<1>)> as QueryDataEffect>::MutQueryData>()
+                .iter_mut(app.world_mut())
+                .for_each(|mut query_data| components_set.affect(&mut query_data));
+
+            let component_ref_0 = app.world().entity(entity).get_ref::<NumberComponent<0>>().unwrap();
+            let component_ref_1 = app.world().entity(entity).get_ref::<NumberComponent<1>>().unwrap();
+
+            prop_assert_eq!(*component_ref_0, components.0);
+            prop_assert_eq!(*component_ref_1, components.1);
+            prop_assert!(!component_ref_0.is_changed());
+            prop_assert!(!component_ref_1.is_changed());
+        }
     }
 }
@@ -3,6 +3,21 @@
 //! [`QueryDataEffect`]: crate::QueryDataEffect
 
 mod components;
-pub use components::{ComponentSet, ComponentsSet, component_set, components_set};
+pub use components::{
+    ComponentAdd,
+    ComponentSet,
+    ComponentSetBypassChangeDetection,
+    ComponentSetIfNeq,
+    ComponentsSet,
+    ComponentsSetBypassChangeDetection,
+    ComponentsSetIfNeq,
+    component_add,
+    component_set,
+    component_set_bypass_change_detection,
+    component_set_if_neq,
+    components_set,
+    components_set_bypass_change_detection,
+    components_set_if_neq,
+};
 
 mod algebra;
@@ -3,8 +3,11 @@
 use bevy::ecs::system::StaticSystemParam;
 use bevy::prelude::*;
 
-use crate::effect_composition::{combine, extend};
-use crate::{Effect, EffectOut};
+use crate::dry_run::{DryRun, DryRunEffect, StateDiff};
+use crate::effect_composition::{coalesce, combine, extend};
+use crate::effect_record::{EffectRecorder, RecordEffect, RecordedEffects};
+use crate::undo::{InvertibleEffect, UndoStack};
+use crate::{Coalesce, Effect, EffectOut, WorldEffect};
 
 /// `bevy` system that accepts [`Effect`]s as pipe input and performs their state transition.
 ///
@@ -41,6 +44,277 @@ where
     out
 }
 
+/// Exclusive `bevy` system that accepts [`WorldEffect`]s as pipe input and performs their state
+/// transition.
+///
+/// Unlike [`affect`], this has exclusive access to the `World`, so it can perform state
+/// transitions that can't be expressed as a mutation of a `SystemParam`. Since this library's
+/// [`Effect`]s are [`WorldEffect`]s too, a single pipeline can return a mix of both. See
+/// [`WorldEffect`] for how [`Effect`]s behave when performed this way.
+///
+/// Being exclusive, this system can't run in parallel with any other system, so [`affect`] should
+/// be preferred when the effects don't require exclusive access.
+///
+/// # Examples
+/// ```
+/// use bevy::ecs::schedule::ScheduleLabel;
+/// use bevy::ecs::system::RunSystemOnce;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, ScheduleLabel)]
+/// struct Autosave;
+///
+/// #[derive(Debug, PartialEq, Eq, Resource)]
+/// struct Round(u32);
+///
+/// #[derive(Debug, PartialEq, Eq, Resource)]
+/// struct SavedRound(u32);
+///
+/// fn next_round_and_save(round: Res<Round>) -> (ResSet<Round>, WorldRunSchedule<Autosave>) {
+///     (res_set(Round(round.0 + 1)), world_run_schedule(Autosave))
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(Round(0));
+/// world.insert_resource(SavedRound(0));
+///
+/// let mut autosave = Schedule::new(Autosave);
+/// autosave.add_systems(|round: Res<Round>, mut saved: ResMut<SavedRound>| saved.0 = round.0);
+/// world.add_schedule(autosave);
+///
+/// world
+///     .run_system_once(next_round_and_save.pipe(affect_world))
+///     .unwrap();
+///
+/// // the round was set before the autosave schedule ran
+/// assert_eq!(world.resource::<SavedRound>(), &SavedRound(1));
+/// ```
+pub fn affect_world<E>(In(effect): In<E>, world: &mut World)
+where
+    E: WorldEffect,
+{
+    effect.affect_world(world);
+}
+
+/// `bevy` system that accepts [`Effect`]s as pipe input and records them instead of performing
+/// their state transition.
+///
+/// Accepts the same pipe input as [`affect`], but instead of calling [`Effect::affect`], pushes
+/// [`EffectRecord`]s of the effect to the [`RecordedEffects`] resource, which must be initialized
+/// in the world. Continuation effects like [`CommandSpawnAnd`] are expanded where possible (see
+/// [`RecordEffect`]). Like [`affect`], the `output: O` of the [`EffectOut<E, O>`] is returned.
+///
+/// This is mostly useful for testing systems whose effects can't be compared with `PartialEq`,
+/// like those containing boxed functions.
+///
+/// # Examples
+/// ```
+/// use bevy::ecs::system::RunSystemOnce;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Debug, Component)]
+/// struct Player;
+///
+/// #[derive(Debug, Component)]
+/// struct Sword;
+///
+/// fn spawn_armed_player() -> CommandSpawnAnd<Player, CommandSpawn<(Sword, ChildOf)>> {
+///     command_spawn_and(Player, |player| command_spawn((Sword, ChildOf(player))))
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<RecordedEffects>();
+///
+/// world.run_system_once(spawn_armed_player.pipe(record)).unwrap();
+///
+/// let recorded = world.resource::<RecordedEffects>();
+///
+/// assert_eq!(recorded.len(), 1);
+/// assert_eq!(recorded[0].continuation.len(), 1);
+/// assert!(recorded[0].continuation[0].debug.contains("Sword"));
+///
+/// // nothing was spawned
+/// assert_eq!(world.query::<&Player>().iter(&world).count(), 0);
+/// ```
+///
+/// [`EffectOut<E, O>`]: EffectOut
+/// [`EffectRecord`]: crate::effect_record::EffectRecord
+/// [`CommandSpawnAnd`]: crate::effects::command::CommandSpawnAnd
+pub fn record<IntoEffectOut, E, O>(
+    In(into_effect_out): In<IntoEffectOut>,
+    mut recorded_effects: ResMut<RecordedEffects>,
+) -> O
+where
+    IntoEffectOut: Into<EffectOut<E, O>>,
+    E: RecordEffect,
+{
+    let EffectOut { effect, out } = into_effect_out.into();
+
+    let mut recorder = EffectRecorder::default();
+    effect.record(&mut recorder);
+    recorded_effects.extend(recorder.into_records());
+
+    out
+}
+
+/// `bevy` system that accepts [`Effect`]s as pipe input and reports the state changes they would
+/// cause, without performing them.
+///
+/// Accepts the same pipe input as [`affect`], but instead of calling [`Effect::affect`], evaluates
+/// the effect against the current `World` (see [`DryRunEffect`]). Returns the resulting
+/// [`StateDiff`] along with the `output: O` of the [`EffectOut<E, O>`].
+///
+/// # Examples
+/// ```
+/// use bevy::ecs::system::RunSystemOnce;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::dry_run::StateChange;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Debug, Resource)]
+/// struct Score(u32);
+///
+/// fn reset_score() -> ResSet<Score> {
+///     res_set(Score(0))
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(Score(10));
+///
+/// let (diff, ()) = world.run_system_once(reset_score.pipe(affect_dry_run)).unwrap();
+///
+/// assert_eq!(
+///     diff[0],
+///     StateChange::Resource {
+///         type_name: std::any::type_name::<Score>(),
+///         before: Some("Score(10)".to_string()),
+///         after: "Score(0)".to_string(),
+///     }
+/// );
+///
+/// // the score was not actually reset
+/// assert_eq!(world.resource::<Score>().0, 10);
+/// ```
+///
+/// [`EffectOut<E, O>`]: EffectOut
+pub fn affect_dry_run<IntoEffectOut, E, O>(
+    In(into_effect_out): In<IntoEffectOut>,
+    world: &World,
+) -> (StateDiff, O)
+where
+    IntoEffectOut: Into<EffectOut<E, O>>,
+    E: DryRunEffect,
+{
+    let EffectOut { effect, out } = into_effect_out.into();
+
+    let mut dry_run = DryRun::new(world);
+    effect.dry_run(&mut dry_run);
+
+    (dry_run.into_diff(), out)
+}
+
+/// `bevy` system that accepts [`InvertibleEffect`]s as pipe input, performs their state
+/// transition, and pushes their inverses to the [`UndoStack`].
+///
+/// Accepts the same pipe input as [`affect`], and returns the `output: O` of the
+/// [`EffectOut<E, O>`] likewise. The inverses of the effect are pushed as a single entry, so they
+/// can be reverted together by the [`undo`] system.
+///
+/// The effect's inverse type must be self-inverse, and is used as the type parameter of the
+/// [`UndoStack`] resource, which must be initialized in the world.
+///
+/// # Examples
+/// ```
+/// use bevy::ecs::system::RunSystemOnce;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Debug, PartialEq, Eq, Resource)]
+/// struct Zoom(u32);
+///
+/// fn zoom_in(zoom: Res<Zoom>) -> ResSet<Zoom> {
+///     res_set(Zoom(zoom.0 + 1))
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(Zoom(1));
+/// world.init_resource::<UndoStack<ResSet<Zoom>>>();
+///
+/// world.run_system_once(zoom_in.pipe(affect_undoable)).unwrap();
+/// assert_eq!(world.resource::<Zoom>(), &Zoom(2));
+///
+/// world.run_system_once(undo::<ResSet<Zoom>>).unwrap();
+/// assert_eq!(world.resource::<Zoom>(), &Zoom(1));
+///
+/// world.run_system_once(redo::<ResSet<Zoom>>).unwrap();
+/// assert_eq!(world.resource::<Zoom>(), &Zoom(2));
+/// ```
+///
+/// [`EffectOut<E, O>`]: EffectOut
+pub fn affect_undoable<IntoEffectOut, E, O>(
+    In(into_effect_out): In<IntoEffectOut>,
+    param: StaticSystemParam<E::MutParam>,
+    mut undo_stack: ResMut<UndoStack<E::Inverse>>,
+) -> O
+where
+    IntoEffectOut: Into<EffectOut<E, O>>,
+    E: InvertibleEffect,
+    E::Inverse: InvertibleEffect<Inverse = E::Inverse> + Send + Sync + 'static,
+{
+    let EffectOut { effect, out } = into_effect_out.into();
+
+    let mut inverses = Vec::new();
+    effect.affect_invertible(&mut param.into_inner(), &mut inverses);
+    undo_stack.push(inverses);
+
+    out
+}
+
+/// `bevy` system that reverts the latest entry of the [`UndoStack`], making it available to
+/// [`redo`].
+///
+/// Does nothing if there is nothing to undo. See [`affect_undoable`] for more details.
+pub fn undo<I>(param: StaticSystemParam<I::MutParam>, mut undo_stack: ResMut<UndoStack<I>>)
+where
+    I: InvertibleEffect<Inverse = I> + Send + Sync + 'static,
+{
+    let Some(inverses) = undo_stack.undo.pop() else {
+        return;
+    };
+
+    let mut param = param.into_inner();
+    let mut redo = Vec::new();
+    inverses
+        .into_iter()
+        .rev()
+        .for_each(|inverse| inverse.affect_invertible(&mut param, &mut redo));
+
+    undo_stack.redo.push(redo);
+}
+
+/// `bevy` system that reapplies the latest entry reverted by [`undo`], making it available to
+/// [`undo`] again.
+///
+/// Does nothing if there is nothing to redo. See [`affect_undoable`] for more details.
+pub fn redo<I>(param: StaticSystemParam<I::MutParam>, mut undo_stack: ResMut<UndoStack<I>>)
+where
+    I: InvertibleEffect<Inverse = I> + Send + Sync + 'static,
+{
+    let Some(redo) = undo_stack.redo.pop() else {
+        return;
+    };
+
+    let mut param = param.into_inner();
+    let mut inverses = Vec::new();
+    redo.into_iter()
+        .rev()
+        .for_each(|effect| effect.affect_invertible(&mut param, &mut inverses));
+
+    undo_stack.undo.push(inverses);
+}
+
 /// Higher-order `bevy` system constructor for composing two systems with effects via piping.
 ///
 /// Accepts an effect-returning system `s` and returns a system that composes the effects of the
@@ -227,6 +501,222 @@ where
     in_and_then_compose(s, extend)
 }
 
+/// Higher-order `bevy` system constructor for concatenating the `Vec` effects of two systems via
+/// piping, and removing the redundant effects.
+///
+/// Accepts a `Vec`-effect-returning system `s` and returns a system that concatenates the
+/// `Vec` effect of the piped-in system and `s`, then coalesces them (see [`Coalesce`]).
+///
+/// See [`in_and_extend`] if you only want to concatenate the effects.
+///
+/// If the piped-in system returns [`EffectOut<E, O>`] instead of a simple effect, then the
+/// `output: O` is passed into the given system `s`.
+///
+/// # Examples
+/// ```
+/// use bevy::ecs::system::RunSystemOnce;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// fn heal_all(query: Query<Entity, With<Health>>) -> Vec<QueryEntityAffect<ComponentSet<Health>>> {
+///     query
+///         .iter()
+///         .map(|entity| query_entity_affect(entity, component_set(Health(100))))
+///         .collect()
+/// }
+///
+/// fn kill_all(query: Query<Entity, With<Health>>) -> Vec<QueryEntityAffect<ComponentSet<Health>>> {
+///     query
+///         .iter()
+///         .map(|entity| query_entity_affect(entity, component_set(Health(0))))
+///         .collect()
+/// }
+///
+/// let mut world = World::new();
+/// let entity = world.spawn(Health(50)).id();
+///
+/// world
+///     .run_system_once(heal_all.pipe(in_and_coalesce(kill_all)).pipe(affect))
+///     .unwrap();
+///
+/// // only the last write was performed
+/// assert_eq!(world.get::<Health>(entity).unwrap().0, 0);
+/// ```
+///
+/// [`EffectOut<E, O>`]: EffectOut
+pub fn in_and_coalesce<IntoEffectOut1, E, O1, System, Marker, IntoEffectOut2, O2>(
+    s: System,
+) -> impl FnMut(In<IntoEffectOut1>, StaticSystemParam<System::Param>) -> EffectOut<Vec<E>, O2>
+where
+    System: SystemParamFunction<Marker, Out = IntoEffectOut2>,
+    IntoEffectOut1: Into<EffectOut<Vec<E>, O1>>,
+    IntoEffectOut2: Into<EffectOut<Vec<E>, O2>>,
+    E: Coalesce,
+    for<'a> System::In: SystemInput<Inner<'a> = O1>,
+{
+    in_and_then_compose(s, coalesce)
+}
+
+/// Higher-order `bevy` system constructor for handling messages with an effect-returning system.
+///
+/// Accepts an effect-returning system `s` taking an [`InRef<M>`] input, and returns a system that
+/// runs `s` once for every unread message `M` and performs the collected effects, as if they were
+/// piped into [`affect`]. This makes message handlers as short as observer systems.
+///
+/// See [`affect_each_message_out`] if `s` returns [`EffectOut<E, O>`] and the outputs are needed.
+///
+/// Note that `s` runs for every message before any of the effects are performed. So, if the
+/// handled messages should accumulate, the effects themselves should read the current state
+/// (e.g. [`ResSetWith`] instead of [`ResSet`]).
+///
+/// Not to be confused with `bevy`'s `on_message` run condition, which only checks for unread
+/// messages.
+///
+/// # Examples
+/// In this example, a message handler is written that subtracts `Damage` from `Health`.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Resource)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Health(u8);
+///
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq, Message)]
+/// # #[derive(proptest_derive::Arbitrary)]
+/// struct Damage(u8);
+///
+/// /// Pure message handler using effects.
+/// fn take_damage_pure(InRef(damage): InRef<Damage>) -> ResSetWith<Health> {
+///     let amount = damage.0;
+///     res_set_with(move |health: &Health| Health(health.0.saturating_sub(amount)))
+/// }
+///
+/// /// Equivalent impure system.
+/// fn take_damage_impure(mut reader: MessageReader<Damage>, mut health: ResMut<Health>) {
+///     for damage in reader.read() {
+///         health.0 = health.0.saturating_sub(damage.0);
+///     }
+/// }
+/// # use proptest::prelude::*;
+/// #
+/// # fn app_setup(health: Health, damages: Vec<Damage>) -> App {
+/// #     let mut app = App::new();
+/// #     app.add_message::<Damage>().insert_resource(health);
+/// #     app.world_mut().write_message_batch(damages);
+/// #
+/// #     app
+/// # }
+/// #
+/// # fn resource_state(world: &World) -> &Health {
+/// #     world.resource::<Health>()
+/// # }
+/// #
+/// # proptest! {
+/// #     fn main(health: Health, damages: Vec<Damage>) {
+/// #         let mut pure_app = app_setup(health, damages.clone());
+/// #         pure_app.add_systems(Update, affect_each_message(take_damage_pure));
+/// #
+/// #         let mut impure_app = app_setup(health, damages.clone());
+/// #         impure_app.add_systems(Update, take_damage_impure);
+/// #
+/// #         for _ in 0..3 {
+/// #             prop_assert_eq!(resource_state(pure_app.world_mut()), resource_state(impure_app.world_mut()));
+/// #             pure_app.update();
+/// #             impure_app.update();
+/// #         }
+/// #     }
+/// # }
+/// ```
+///
+/// [`EffectOut<E, O>`]: EffectOut
+/// [`ResSetWith`]: crate::effects::resource::ResSetWith
+/// [`ResSet`]: crate::effects::resource::ResSet
+#[expect(clippy::type_complexity)]
+pub fn affect_each_message<M, System, Marker, IntoEffectOut, E>(
+    s: System,
+) -> impl FnMut(MessageReader<M>, ParamSet<(System::Param, StaticSystemParam<E::MutParam>)>)
+where
+    M: Message,
+    System: SystemParamFunction<Marker, Out = IntoEffectOut>,
+    IntoEffectOut: Into<EffectOut<E, ()>>,
+    E: Effect,
+    for<'a> System::In: SystemInput<Inner<'a> = &'a M>,
+{
+    let mut s = affect_each_message_out(s);
+
+    move |reader, params| {
+        s(reader, params);
+    }
+}
+
+/// Higher-order `bevy` system constructor for handling messages with an effect-returning system,
+/// returning the outputs.
+///
+/// Like [`affect_each_message`], but `s` may return [`EffectOut<E, O>`], and the outputs are
+/// collected into a `Vec` and returned by the system. So, the returned system can be piped into
+/// more systems.
+///
+/// # Examples
+/// ```
+/// use bevy::ecs::system::RunSystemOnce;
+/// use bevy::prelude::*;
+/// use bevy_pipe_affect::prelude::*;
+///
+/// #[derive(Resource)]
+/// struct Health(u8);
+///
+/// #[derive(Message)]
+/// struct Damage(u8);
+///
+/// fn take_damage(InRef(damage): InRef<Damage>) -> EffectOut<ResSetWith<Health>, u8> {
+///     let amount = damage.0;
+///     effect_out(
+///         res_set_with(move |health: &Health| Health(health.0.saturating_sub(amount))),
+///         amount,
+///     )
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Messages<Damage>>();
+/// world.insert_resource(Health(10));
+/// world.write_message_batch([Damage(3), Damage(4)]);
+///
+/// let amounts = world
+///     .run_system_once(affect_each_message_out(take_damage))
+///     .unwrap();
+///
+/// assert_eq!(amounts, vec![3, 4]);
+/// assert_eq!(world.resource::<Health>().0, 3);
+/// ```
+///
+/// [`EffectOut<E, O>`]: EffectOut
+#[expect(clippy::type_complexity)]
+pub fn affect_each_message_out<M, System, Marker, IntoEffectOut, E, O>(
+    mut s: System,
+) -> impl FnMut(MessageReader<M>, ParamSet<(System::Param, StaticSystemParam<E::MutParam>)>) -> Vec<O>
+where
+    M: Message,
+    System: SystemParamFunction<Marker, Out = IntoEffectOut>,
+    IntoEffectOut: Into<EffectOut<E, O>>,
+    E: Effect,
+    for<'a> System::In: SystemInput<Inner<'a> = &'a M>,
+{
+    move |mut reader, mut params| {
+        let EffectOut { effect, out } = reader
+            .read()
+            .map(|message| s.run(message, params.p0()).into())
+            .collect::<EffectOut<Vec<E>, Vec<O>>>();
+
+        effect.affect(&mut params.p1().into_inner());
+
+        out
+    }
+}
+
 /// Identity function for read-only-systems.
 ///
 /// This totally-optional function can be used if you want the pureness of your systems to be
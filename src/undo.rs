@@ -0,0 +1,130 @@
+//! Types for performing [`Effect`]s invertibly, and undoing/redoing them.
+//!
+//! See the [`affect_undoable`], [`undo`] and [`redo`] systems for the main entrypoints.
+//!
+//! [`affect_undoable`]: crate::system_combinators::affect_undoable
+//! [`undo`]: crate::system_combinators::undo
+//! [`redo`]: crate::system_combinators::redo
+use bevy::ecs::query::QueryData;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::{Effect, QueryDataEffect};
+
+/// An [`Effect`] that can capture the state it overwrites, producing effects that revert it.
+///
+/// Implemented for...
+/// - [`ResSet`]
+/// - [`QueryEntityAffect`] of an [`InvertibleQueryDataEffect`], like [`ComponentSet`] and
+///   [`ComponentsSet`]
+/// - [`EntityCommandInsert`], [`EntityCommandRemove`] and [`EntityCommandRestore`]
+/// - `Vec`s, `Option`s, `Result`s and `Either`s of the above
+///
+/// The inverse of an effect is typically "self-inverse" (`Inverse = Self`), or has a self-inverse
+/// inverse, so that it can be stored in an [`UndoStack`].
+///
+/// Custom effects built from invertible effects can implement it by performing those effects with
+/// [`InvertibleEffect::affect_invertible`], converting their inverses into their own `Inverse`.
+///
+/// [`ResSet`]: crate::effects::resource::ResSet
+/// [`QueryEntityAffect`]: crate::effects::query_entity::QueryEntityAffect
+/// [`ComponentSet`]: crate::query_data_effects::ComponentSet
+/// [`ComponentsSet`]: crate::query_data_effects::ComponentsSet
+/// [`EntityCommandInsert`]: crate::effects::entity_command::EntityCommandInsert
+/// [`EntityCommandRemove`]: crate::effects::entity_command::EntityCommandRemove
+/// [`EntityCommandRestore`]: crate::effects::entity_command::EntityCommandRestore
+pub trait InvertibleEffect: Effect {
+    /// The [`Effect`] that reverts this effect.
+    type Inverse: Effect;
+
+    /// Perform the state transition, pushing the effects that revert it to `inverses`.
+    ///
+    /// Inverses are pushed in the order their state transitions are performed, so they should be
+    /// applied in reverse order.
+    fn affect_invertible(
+        self,
+        param: &mut <Self::MutParam as SystemParam>::Item<'_, '_>,
+        inverses: &mut Vec<Self::Inverse>,
+    );
+}
+
+/// A [`QueryDataEffect`] that can capture the query data it overwrites, producing a query data
+/// effect that reverts it.
+///
+/// Used for implementing [`InvertibleEffect`] on query effects.
+pub trait InvertibleQueryDataEffect: QueryDataEffect {
+    /// The [`QueryDataEffect`] that reverts this effect.
+    type Inverse: QueryDataEffect<MutQueryData = Self::MutQueryData>;
+
+    /// Perform the state transition on the query data, returning the effect that reverts it.
+    fn affect_invertible(
+        self,
+        query_data: &mut <Self::MutQueryData as QueryData>::Item<'_, '_>,
+    ) -> Self::Inverse;
+}
+
+/// `Resource` storing the inverses of effects performed by [`affect_undoable`] systems.
+///
+/// Each entry is the list of inverses of a single [`affect_undoable`] run. Running the [`undo`]
+/// system reverts the latest entry and makes it available to [`redo`]. Performing a new
+/// undoable effect clears the redo history.
+///
+/// The type parameter `I` is the self-inverse effect type stored in the stack. Use `Either` to
+/// store different kinds of effects in the same stack.
+///
+/// Must be initialized in the world for [`affect_undoable`], [`undo`] and [`redo`] systems to run.
+///
+/// [`affect_undoable`]: crate::system_combinators::affect_undoable
+/// [`undo`]: crate::system_combinators::undo
+/// [`redo`]: crate::system_combinators::redo
+#[derive(Debug, Resource)]
+pub struct UndoStack<I>
+where
+    I: InvertibleEffect<Inverse = I> + Send + Sync + 'static,
+{
+    pub(crate) undo: Vec<Vec<I>>,
+    pub(crate) redo: Vec<Vec<I>>,
+}
+
+impl<I> Default for UndoStack<I>
+where
+    I: InvertibleEffect<Inverse = I> + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        UndoStack {
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+}
+
+impl<I> UndoStack<I>
+where
+    I: InvertibleEffect<Inverse = I> + Send + Sync + 'static,
+{
+    /// The number of entries that can be undone.
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// The number of entries that can be redone.
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+
+    /// Clear the undo and redo history.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Push a new entry of inverses to the undo history, clearing the redo history.
+    ///
+    /// Empty entries are ignored.
+    pub(crate) fn push(&mut self, inverses: Vec<I>) {
+        if !inverses.is_empty() {
+            self.undo.push(inverses);
+            self.redo.clear();
+        }
+    }
+}
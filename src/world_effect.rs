@@ -0,0 +1,80 @@
+//! Types for performing state transitions with exclusive `World` access.
+//!
+//! See the [`WorldEffect`] trait and the [`affect_world`] system for the main entrypoints.
+//!
+//! [`affect_world`]: crate::system_combinators::affect_world
+use std::any::type_name_of_val;
+
+use bevy::ecs::error::ErrorContext;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+
+use crate::Effect;
+
+/// Define a state transition in `bevy`'s ECS that is performed with exclusive `World` access.
+///
+/// Can be returned by `bevy` systems and `pipe`d into [`affect_world`] to perform the transition.
+///
+/// This is a parallel to the [`Effect`] trait for state transitions that can't be expressed as a
+/// mutation of a `SystemParam`, like running a schedule (see [`WorldRunSchedule`]).
+///
+/// All of this library's [`Effect`]s implement `WorldEffect` too, so [`affect_world`] systems can
+/// perform a mix of both. Algebraic effects like tuples, `Vec`s, `Option`s, `Either`s and
+/// `Result`s are `WorldEffect`s when their inner effects are. Custom [`Effect`]s can be performed
+/// as `WorldEffect`s by wrapping them in [`AsWorldEffect`].
+///
+/// When performed as a `WorldEffect`, an [`Effect`] accesses its `MutParam` through a temporary
+/// `SystemState`, which is applied immediately afterwards. This has some consequences...
+/// - deferred effects like commands are applied before the next effect is performed, rather than
+///   at the end of the schedule
+/// - effects that rely on persistent system state, like [`LocalSetAnd`] and [`MessagesReadAnd`],
+///   see a fresh state every time
+///
+/// [`affect_world`]: crate::system_combinators::affect_world
+/// [`WorldRunSchedule`]: crate::effects::world::WorldRunSchedule
+/// [`AsWorldEffect`]: crate::effects::world::AsWorldEffect
+/// [`LocalSetAnd`]: crate::effects::local::LocalSetAnd
+/// [`MessagesReadAnd`]: crate::effects::message::MessagesReadAnd
+pub trait WorldEffect {
+    /// Perform the state transition on the `World`.
+    fn affect_world(self, world: &mut World);
+}
+
+/// Perform an [`Effect`] on the `World` through a temporary `SystemState` of its `MutParam`.
+pub(crate) fn affect_world_with_system_state<E>(effect: E, world: &mut World)
+where
+    E: Effect,
+    E::MutParam: 'static,
+{
+    let mut state = SystemState::<E::MutParam>::new(world);
+
+    let result = state
+        .get_mut(world)
+        .map(|mut param| effect.affect(&mut param));
+
+    if let Err(e) = result {
+        if !e.skipped {
+            let context = affect_world_error_context(world);
+            (world.fallback_error_handler())(e.into(), context);
+        }
+
+        return;
+    }
+
+    state.apply(world);
+}
+
+/// The [`ErrorContext`] for errors produced while performing a [`WorldEffect`].
+///
+/// [`WorldEffect`]s are performed inside the exclusive [`affect_world`] system, so errors are
+/// attributed to it.
+///
+/// [`affect_world`]: crate::system_combinators::affect_world
+pub(crate) fn affect_world_error_context(world: &World) -> ErrorContext {
+    let name = type_name_of_val(&crate::system_combinators::affect_world::<()>);
+
+    ErrorContext::System {
+        name: name.split('<').next().unwrap_or(name).into(),
+        last_run: world.last_change_tick(),
+    }
+}
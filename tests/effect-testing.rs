@@ -6,7 +6,7 @@ use bevy_pipe_affect::prelude::*;
 // ANCHOR_END: import_preludes
 
 // ANCHOR: detect_deaths
-#[derive(Component)]
+#[derive(Debug, Component)]
 struct Health(u32);
 
 #[derive(Debug, PartialEq, Eq, Message)]
@@ -30,7 +30,7 @@ fn detect_deaths(query: Query<(Entity, &Health)>) -> Vec<MessageWrite<DeathMessa
 // ANCHOR: test_detect_deaths
 use bevy::ecs::system::RunSystemOnce;
 
-#[derive(Resource)]
+#[derive(Debug, Resource)]
 struct UnhealthyEntity(Entity);
 
 fn test_detect_deaths() {
@@ -63,7 +63,106 @@ fn test_detect_deaths() {
 }
 // ANCHOR_END: test_detect_deaths
 
+// ANCHOR: test_record_detect_deaths
+fn test_record_detect_deaths() {
+    let mut world = World::new();
+    world.init_resource::<RecordedEffects>();
+
+    let _setup = world
+        .run_system_once(
+            (|| {
+                command_spawn_and(Health(100), |_| {
+                    command_spawn_and(Health(0), |entity| {
+                        command_insert_resource(UnhealthyEntity(entity))
+                    })
+                })
+            })
+            .pipe(affect)
+            .pipe(ApplyDeferred),
+        )
+        .unwrap();
+
+    // Record the effects instead of performing them
+    world.run_system_once(detect_deaths.pipe(record)).unwrap();
+
+    let UnhealthyEntity(entity) = world.get_resource::<UnhealthyEntity>().unwrap();
+
+    let recorded = world.resource::<RecordedEffects>();
+
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(
+        recorded[0].debug,
+        format!("{:?}", message_write(DeathMessage(*entity)))
+    );
+}
+// ANCHOR_END: test_record_detect_deaths
+
+// ANCHOR: test_record_setup
+use bevy_pipe_affect::effect_record::EffectRecorder;
+
+fn test_record_setup() {
+    let mut world = World::new();
+    world.init_resource::<RecordedEffects>();
+
+    // Record the setup effects instead of performing them
+    world
+        .run_system_once(
+            (|| {
+                command_spawn_and(Health(100), |_| {
+                    command_spawn_and(Health(0), |entity| {
+                        command_insert_resource(UnhealthyEntity(entity))
+                    })
+                })
+            })
+            .pipe(record),
+        )
+        .unwrap();
+
+    // Nothing was spawned, so the continuations were supplied placeholder entities
+    assert!(world.query::<&Health>().iter(&world).next().is_none());
+
+    let mut placeholders = EffectRecorder::default();
+    let _healthy = placeholders.placeholder_entity();
+    let unhealthy = placeholders.placeholder_entity();
+
+    let recorded = world.resource::<RecordedEffects>();
+
+    assert_eq!(recorded.len(), 1);
+    assert!(recorded[0].debug.contains("Health(100)"));
+
+    let [spawn_unhealthy] = recorded[0].continuation.as_slice() else {
+        panic!("expected the healthy spawn to continue into one effect");
+    };
+
+    assert!(spawn_unhealthy.debug.contains("Health(0)"));
+
+    let [insert_unhealthy] = spawn_unhealthy.continuation.as_slice() else {
+        panic!("expected the unhealthy spawn to continue into one effect");
+    };
+
+    assert_eq!(
+        insert_unhealthy.type_name,
+        std::any::type_name::<CommandInsertResource<UnhealthyEntity>>()
+    );
+    assert_eq!(
+        insert_unhealthy.debug,
+        format!("{:?}", command_insert_resource(UnhealthyEntity(unhealthy)))
+    );
+    assert!(insert_unhealthy.continuation.is_empty());
+}
+// ANCHOR_END: test_record_setup
+
 #[test]
 fn cargo_test_detect_deaths() {
     test_detect_deaths()
 }
+
+#[test]
+fn cargo_test_record_detect_deaths() {
+    test_record_detect_deaths()
+}
+
+#[test]
+fn cargo_test_record_setup() {
+    test_record_setup()
+}